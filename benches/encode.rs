@@ -0,0 +1,204 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use qr_code::qr_code::encoding::encode;
+use qr_code::qr_code::render::RenderOptions;
+use qr_code::qr_code::stages::{build_matrix, generate_error_correction};
+use qr_code::qr_code::{
+    BitBuffer, Encoder, EncodingMode, ErrorCorrectionLevel, QrCode, QrCodeOptions,
+};
+
+const VERSIONS: [i16; 4] = [1, 10, 25, 40];
+
+const ALPHANUMERIC_CHARACTERS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/**
+ * The largest payloads that fit in a version 40-L code for each encoding mode
+ */
+fn version_40_payloads() -> Vec<(&'static str, String, EncodingMode)> {
+    vec![
+        (
+            "numeric",
+            "0123456789".chars().cycle().take(7089).collect(),
+            EncodingMode::Numeric,
+        ),
+        (
+            "alphanumeric",
+            ALPHANUMERIC_CHARACTERS.chars().cycle().take(4296).collect(),
+            EncodingMode::Alphanumeric,
+        ),
+        (
+            "byte",
+            "Hello, World!".chars().cycle().take(2953).collect(),
+            EncodingMode::Byte,
+        ),
+    ]
+}
+
+/**
+ * An alphanumeric version-L code filled to capacity for each benchmarked version
+ */
+fn codes_per_version() -> Vec<(i16, QrCode)> {
+    // alphanumeric capacity of versions 1, 10, 25 and 40 at level L
+    let capacities = [25, 395, 1853, 4296];
+
+    VERSIONS
+        .iter()
+        .zip(capacities)
+        .map(|(&version, capacity)| {
+            let data = ALPHANUMERIC_CHARACTERS
+                .chars()
+                .cycle()
+                .take(capacity)
+                .collect();
+            let options = QrCodeOptions {
+                min_version: version,
+                max_version: version,
+                ..QrCodeOptions::default()
+            };
+            let qr_code = QrCode::create_with_options(
+                data,
+                EncodingMode::Alphanumeric,
+                ErrorCorrectionLevel::Low,
+                &options,
+            )
+            .unwrap();
+
+            (version, qr_code)
+        })
+        .collect()
+}
+
+fn bench_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+
+    for (version, qr_code) in codes_per_version() {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(version),
+            &qr_code.data,
+            |b, data| b.iter(|| encode(black_box(data), &EncodingMode::Alphanumeric)),
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_error_correction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("error correction");
+
+    for (version, qr_code) in codes_per_version() {
+        let data_bits = BitBuffer::from(qr_code.report().unwrap().data_code_words.as_slice());
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(version),
+            &data_bits,
+            |b, bits| {
+                b.iter(|| {
+                    generate_error_correction(
+                        black_box(bits),
+                        &qr_code.error_correction_level,
+                        &qr_code.version,
+                    )
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_masking(c: &mut Criterion) {
+    let mut group = c.benchmark_group("placement and masking");
+
+    for (version, qr_code) in codes_per_version() {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(version),
+            &qr_code.code_words,
+            |b, code_words| {
+                b.iter(|| {
+                    build_matrix(
+                        black_box(code_words),
+                        &qr_code.error_correction_level,
+                        &qr_code.version,
+                        None,
+                    )
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_render(c: &mut Criterion) {
+    let options = RenderOptions::default();
+    let codes = codes_per_version();
+
+    let mut group = c.benchmark_group("render png");
+    for (version, qr_code) in &codes {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(version),
+            qr_code,
+            |b, qr_code| b.iter(|| qr_code.to_png(black_box(&options))),
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("render svg");
+    for (version, qr_code) in &codes {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(version),
+            qr_code,
+            |b, qr_code| b.iter(|| qr_code.to_svg(black_box(&options))),
+        );
+    }
+    group.finish();
+}
+
+fn bench_create(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create version 40-L");
+
+    for (name, data, mode) in version_40_payloads() {
+        group.bench_function(name, |b| {
+            b.iter(|| QrCode::create(black_box(data.clone()), mode, ErrorCorrectionLevel::Low))
+        });
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("encode_into version 40-L");
+    let options = QrCodeOptions::default();
+    let mut encoder = Encoder::new();
+    let mut qr_code = encoder
+        .encode(
+            "0",
+            EncodingMode::Numeric,
+            ErrorCorrectionLevel::Low,
+            &options,
+        )
+        .unwrap();
+
+    for (name, data, mode) in version_40_payloads() {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                encoder.encode_into(
+                    &mut qr_code,
+                    black_box(&data),
+                    mode,
+                    ErrorCorrectionLevel::Low,
+                    &options,
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_encode,
+    bench_error_correction,
+    bench_masking,
+    bench_render,
+    bench_create
+);
+criterion_main!(benches);
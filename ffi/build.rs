@@ -0,0 +1,28 @@
+use std::env;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    generate_header();
+}
+
+/**
+ * Writes the C header for the crate to OUT_DIR/qr_code.h, tests/header.rs checks that
+ * include/qr_code.h matches it
+ */
+fn generate_header() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    let config = cbindgen::Config::from_file(Path::new(&crate_dir).join("cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("the C header can be generated from src/lib.rs")
+        .write_to_file(Path::new(&out_dir).join("qr_code.h"));
+}
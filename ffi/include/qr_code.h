@@ -0,0 +1,122 @@
+#ifndef QR_CODE_H
+#define QR_CODE_H
+
+/* Generated from src/lib.rs by cbindgen, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ picks the most compact mode that encodes the data
+ */
+#define QR_MODE_AUTO 0
+
+#define QR_MODE_NUMERIC 1
+
+#define QR_MODE_ALPHANUMERIC 2
+
+#define QR_MODE_BYTE 3
+
+#define QR_EC_LOW 0
+
+#define QR_EC_MEDIUM 1
+
+#define QR_EC_QUARTILE 2
+
+#define QR_EC_HIGH 3
+
+/*
+ the widest quiet zone `qr_to_svg` accepts, in modules
+ */
+#define QR_MAX_QUIET_ZONE 100
+
+/*
+ the largest module size `qr_to_svg` accepts, in pixels
+ */
+#define QR_MAX_MODULE_SIZE 100
+
+/*
+ * A created code, named `QrCode` in the C header. Only ever handed out as a pointer from
+ * `qr_create` and given back to `qr_free`.
+ */
+typedef struct QrCode QrCode;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ * The message of the last call that failed on this thread, or NULL if none has. The string is
+ * owned by the library and stays valid until the next call fails on the same thread, do not free
+ * it.
+ */
+const char *qr_last_error(void);
+
+/*
+ * Creates a code for NUL terminated UTF-8 text with one of the `QR_MODE_` and `QR_EC_` constants.
+ * Returns NULL and sets `qr_last_error` when the code cannot be created. The caller owns the code
+ * and must release it with `qr_free`.
+ *
+ * # Safety
+ *
+ * `data` must be NULL or point to a NUL terminated string.
+ */
+struct QrCode *qr_create(const char *data, uint8_t mode, uint8_t error_correction);
+
+/*
+ * Releases a code from `qr_create`, NULL is ignored
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed yet, and is invalid
+ * afterwards.
+ */
+void qr_free(struct QrCode *code);
+
+/*
+ * The number of modules along each side without the quiet zone, 0 for NULL
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed.
+ */
+size_t qr_width(const struct QrCode *code);
+
+/*
+ * Whether the module at column x and row y is dark, false for NULL and positions outside the code
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed.
+ */
+bool qr_get_module(const struct QrCode *code, size_t x, size_t y);
+
+/*
+ * Renders the code as a NUL terminated SVG document with the default colors and styles, a quiet
+ * zone of 0 to `QR_MAX_QUIET_ZONE` modules and 1 to `QR_MAX_MODULE_SIZE` pixels per module. Returns
+ * NULL and sets `qr_last_error` for a NULL code or a size out of range. The caller owns the string
+ * and must release it with `qr_string_free`, not `free`.
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed.
+ */
+char *qr_to_svg(const struct QrCode *code, size_t quiet_zone, size_t module_size);
+
+/*
+ * Releases a string from `qr_to_svg`, NULL is ignored
+ *
+ * # Safety
+ *
+ * `string` must be NULL or a string from `qr_to_svg` that has not been freed yet.
+ */
+void qr_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* QR_CODE_H */
@@ -0,0 +1,244 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use qr_code::qr_code::render::RenderOptions;
+use qr_code::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode};
+
+/// picks the most compact mode that encodes the data
+pub const QR_MODE_AUTO: u8 = 0;
+pub const QR_MODE_NUMERIC: u8 = 1;
+pub const QR_MODE_ALPHANUMERIC: u8 = 2;
+pub const QR_MODE_BYTE: u8 = 3;
+
+pub const QR_EC_LOW: u8 = 0;
+pub const QR_EC_MEDIUM: u8 = 1;
+pub const QR_EC_QUARTILE: u8 = 2;
+pub const QR_EC_HIGH: u8 = 3;
+
+/// the widest quiet zone `qr_to_svg` accepts, in modules
+pub const QR_MAX_QUIET_ZONE: usize = 100;
+/// the largest module size `qr_to_svg` accepts, in pixels
+pub const QR_MAX_MODULE_SIZE: usize = 100;
+
+/**
+ * A created code, named `QrCode` in the C header. Only ever handed out as a pointer from
+ * `qr_create` and given back to `qr_free`.
+ */
+pub struct QrCodeHandle {
+    qr_code: QrCode,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    // the messages can quote the data, which never holds a NUL as it came from a C string
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+/**
+ * Runs the closure, setting `qr_last_error` and returning none when it fails. A panic counts as a
+ * failure, it must never unwind into C.
+ */
+fn call<T>(f: impl FnOnce() -> Result<T, String>) -> Option<T> {
+    let result = panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err("The library failed unexpectedly".to_string()));
+
+    result.map_err(|message| set_last_error(&message)).ok()
+}
+
+/**
+ * The message of the last call that failed on this thread, or NULL if none has. The string is
+ * owned by the library and stays valid until the next call fails on the same thread, do not free
+ * it.
+ */
+#[no_mangle]
+pub extern "C" fn qr_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/**
+ * Creates a code for NUL terminated UTF-8 text with one of the `QR_MODE_` and `QR_EC_` constants.
+ * Returns NULL and sets `qr_last_error` when the code cannot be created. The caller owns the code
+ * and must release it with `qr_free`.
+ *
+ * # Safety
+ *
+ * `data` must be NULL or point to a NUL terminated string.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn qr_create(
+    data: *const c_char,
+    mode: u8,
+    error_correction: u8,
+) -> *mut QrCodeHandle {
+    if data.is_null() {
+        set_last_error("The data must not be NULL");
+        return ptr::null_mut();
+    }
+
+    let data = match CStr::from_ptr(data).to_str() {
+        Ok(data) => data,
+        Err(_) => {
+            set_last_error("The data is not valid UTF-8");
+            return ptr::null_mut();
+        }
+    };
+
+    call(|| create(data, mode, error_correction)).map_or(ptr::null_mut(), |qr_code| {
+        Box::into_raw(Box::new(QrCodeHandle { qr_code }))
+    })
+}
+
+fn create(data: &str, mode: u8, error_correction: u8) -> Result<QrCode, String> {
+    let encoding_mode = match mode {
+        QR_MODE_AUTO => EncodingMode::detect(data),
+        QR_MODE_NUMERIC => EncodingMode::Numeric,
+        QR_MODE_ALPHANUMERIC => EncodingMode::Alphanumeric,
+        QR_MODE_BYTE => EncodingMode::Byte,
+        _ => return Err(format!("Unknown mode {}", mode)),
+    };
+    let error_correction_level = match error_correction {
+        QR_EC_LOW => ErrorCorrectionLevel::Low,
+        QR_EC_MEDIUM => ErrorCorrectionLevel::Medium,
+        QR_EC_QUARTILE => ErrorCorrectionLevel::Quartile,
+        QR_EC_HIGH => ErrorCorrectionLevel::High,
+        _ => {
+            return Err(format!(
+                "Unknown error correction level {}",
+                error_correction
+            ))
+        }
+    };
+
+    QrCode::create(data.to_string(), encoding_mode, error_correction_level)
+}
+
+/**
+ * Releases a code from `qr_create`, NULL is ignored
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed yet, and is invalid
+ * afterwards.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn qr_free(code: *mut QrCodeHandle) {
+    if !code.is_null() {
+        drop(Box::from_raw(code));
+    }
+}
+
+/**
+ * The number of modules along each side without the quiet zone, 0 for NULL
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn qr_width(code: *const QrCodeHandle) -> usize {
+    code.as_ref().map_or(0, |code| code.qr_code.width())
+}
+
+/**
+ * Whether the module at column x and row y is dark, false for NULL and positions outside the code
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn qr_get_module(code: *const QrCodeHandle, x: usize, y: usize) -> bool {
+    match code.as_ref() {
+        Some(code) if x < code.qr_code.width() && y < code.qr_code.width() => {
+            code.qr_code.get_module(x, y)
+        }
+        _ => false,
+    }
+}
+
+/**
+ * Renders the code as a NUL terminated SVG document with the default colors and styles, a quiet
+ * zone of 0 to `QR_MAX_QUIET_ZONE` modules and 1 to `QR_MAX_MODULE_SIZE` pixels per module. Returns
+ * NULL and sets `qr_last_error` for a NULL code or a size out of range. The caller owns the string
+ * and must release it with `qr_string_free`, not `free`.
+ *
+ * # Safety
+ *
+ * `code` must be NULL or a code from `qr_create` that has not been freed.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn qr_to_svg(
+    code: *const QrCodeHandle,
+    quiet_zone: usize,
+    module_size: usize,
+) -> *mut c_char {
+    let Some(code) = code.as_ref() else {
+        set_last_error("The code must not be NULL");
+        return ptr::null_mut();
+    };
+
+    call(|| to_svg(&code.qr_code, quiet_zone, module_size))
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+fn to_svg(qr_code: &QrCode, quiet_zone: usize, module_size: usize) -> Result<CString, String> {
+    if quiet_zone > QR_MAX_QUIET_ZONE {
+        return Err(format!(
+            "The quiet zone must be 0 to {} modules, found {}",
+            QR_MAX_QUIET_ZONE, quiet_zone
+        ));
+    }
+    if !(1..=QR_MAX_MODULE_SIZE).contains(&module_size) {
+        return Err(format!(
+            "The module size must be 1 to {} pixels, found {}",
+            QR_MAX_MODULE_SIZE, module_size
+        ));
+    }
+
+    let svg = qr_code.to_svg(&RenderOptions {
+        quiet_zone,
+        module_size,
+        ..RenderOptions::default()
+    });
+
+    CString::new(svg).map_err(|_| "The SVG contains a NUL byte".to_string())
+}
+
+/**
+ * Releases a string from `qr_to_svg`, NULL is ignored
+ *
+ * # Safety
+ *
+ * `string` must be NULL or a string from `qr_to_svg` that has not been freed yet.
+ */
+#[no_mangle]
+pub unsafe extern "C" fn qr_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+#[test]
+fn test_call_catches_panics() {
+    let last_error = || unsafe { CStr::from_ptr(qr_last_error()) }.to_str().unwrap();
+
+    let result: Option<()> = call(|| panic!("a bug in the encoder"));
+    assert_eq!(result, None);
+    assert_eq!(last_error(), "The library failed unexpectedly");
+
+    assert_eq!(call::<()>(|| Err("12a".to_string())), None);
+    assert_eq!(last_error(), "12a");
+
+    assert_eq!(call(|| Ok(1)), Some(1));
+}
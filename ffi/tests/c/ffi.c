@@ -0,0 +1,77 @@
+/*
+ * Exercises the C API and its ownership rules, built and run by tests/c_program.rs. Every code and
+ * string is released, so a leak or double free shows up under the address sanitizer.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "qr_code.h"
+
+#define CHECK(condition)                                                           \
+    do {                                                                           \
+        if (!(condition)) {                                                        \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+            return 1;                                                              \
+        }                                                                          \
+    } while (0)
+
+int main(void) {
+    CHECK(qr_last_error() == NULL);
+
+    QrCode *code = qr_create("HELLO WORLD", QR_MODE_ALPHANUMERIC, QR_EC_QUARTILE);
+    CHECK(code != NULL);
+    CHECK(qr_width(code) == 21);
+
+    /* the top left finder pattern: a dark ring, a light ring and a dark 3x3 centre */
+    CHECK(qr_get_module(code, 0, 0));
+    CHECK(!qr_get_module(code, 1, 1));
+    CHECK(qr_get_module(code, 3, 3));
+    CHECK(!qr_get_module(code, 21, 0));
+    CHECK(!qr_get_module(code, 0, 21));
+
+    char *svg = qr_to_svg(code, 4, 10);
+    CHECK(svg != NULL);
+    CHECK(strstr(svg, "<svg") != NULL);
+    qr_string_free(svg);
+
+    /* sizes out of range fail rather than overflowing or allocating huge documents */
+    svg = qr_to_svg(code, QR_MAX_QUIET_ZONE, QR_MAX_MODULE_SIZE);
+    CHECK(svg != NULL);
+    qr_string_free(svg);
+    CHECK(qr_to_svg(code, QR_MAX_QUIET_ZONE + 1, 10) == NULL);
+    CHECK(strstr(qr_last_error(), "quiet zone") != NULL);
+    CHECK(qr_to_svg(code, SIZE_MAX, 10) == NULL);
+    CHECK(qr_to_svg(code, 4, 0) == NULL);
+    CHECK(strstr(qr_last_error(), "module size") != NULL);
+    CHECK(qr_to_svg(code, 4, SIZE_MAX) == NULL);
+
+    /* codes are independent of each other */
+    QrCode *other = qr_create("https://example.com", QR_MODE_AUTO, QR_EC_HIGH);
+    CHECK(other != NULL);
+    CHECK(qr_width(other) > 21);
+    qr_free(code);
+    CHECK(qr_width(other) > 21);
+    qr_free(other);
+
+    /* failures return NULL and leave a message owned by the library */
+    CHECK(qr_create("12a", QR_MODE_NUMERIC, QR_EC_LOW) == NULL);
+    const char *error = qr_last_error();
+    CHECK(error != NULL);
+    CHECK(strstr(error, "\"a\"") != NULL);
+
+    CHECK(qr_create(NULL, QR_MODE_AUTO, QR_EC_LOW) == NULL);
+    CHECK(qr_create("12", 9, QR_EC_LOW) == NULL);
+    CHECK(strstr(qr_last_error(), "mode") != NULL);
+    CHECK(qr_create("12", QR_MODE_AUTO, 9) == NULL);
+    CHECK(qr_create("\xff\xfe", QR_MODE_BYTE, QR_EC_LOW) == NULL);
+    CHECK(strstr(qr_last_error(), "UTF-8") != NULL);
+
+    /* NULL is accepted everywhere */
+    CHECK(qr_width(NULL) == 0);
+    CHECK(!qr_get_module(NULL, 0, 0));
+    CHECK(qr_to_svg(NULL, 4, 10) == NULL);
+    qr_free(NULL);
+    qr_string_free(NULL);
+
+    return 0;
+}
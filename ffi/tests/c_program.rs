@@ -0,0 +1,40 @@
+#![cfg(unix)]
+// Compiles and runs tests/c/ffi.c against the static library cargo builds with this test and the
+// header in include/. On Linux the program runs under the address sanitizer, so leaks and double
+// frees fail it.
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_c_program() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // cargo builds the library next to the test, in target/<profile>/deps
+    let test_binary = env::current_exe().unwrap();
+    let deps_dir = test_binary.parent().unwrap();
+
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi_test");
+    let mut cc = Command::new("cc");
+    cc.args(["-Wall", "-Wextra", "-Werror", "-g"])
+        .arg(manifest_dir.join("tests/c/ffi.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(deps_dir.join("libqr_code_ffi.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program);
+    if cfg!(target_os = "linux") {
+        cc.arg("-fsanitize=address");
+    }
+    assert!(
+        cc.status().unwrap().success(),
+        "the C program failed to compile"
+    );
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,14 @@
+// The build script generates the header into OUT_DIR, so a change to the C API that is not
+// regenerated into the checked-in include/qr_code.h fails here rather than in a C build.
+
+const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/qr_code.h"));
+const CHECKED_IN: &str = include_str!("../include/qr_code.h");
+
+#[test]
+fn test_header_is_up_to_date() {
+    assert!(
+        GENERATED == CHECKED_IN,
+        "include/qr_code.h is out of date, regenerate it from ffi/ with \
+         `cbindgen --config cbindgen.toml --output include/qr_code.h`\n{GENERATED}"
+    );
+}
@@ -0,0 +1,65 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use qr_code::qr_code::{
+    decode_matrix, EncodingMode, ErrorCorrectionLevel, Knockout, KnockoutShape, QrCode,
+    QrCodeOptions,
+};
+
+#[derive(Arbitrary, Debug)]
+struct Input<'a> {
+    data: &'a [u8],
+    encoding_mode: u8,
+    error_correction_level: u8,
+    min_version: i16,
+    max_version: i16,
+    mask: Option<u8>,
+    knockout: Option<(u8, u8, bool)>,
+    raise_error_correction: bool,
+}
+
+// arbitrary bytes and options either create a QR code or are refused, and every code created
+// decodes back to its data
+fuzz_target!(|input: Input| {
+    let data = String::from_utf8_lossy(input.data).into_owned();
+
+    let encoding_mode = match input.encoding_mode % 4 {
+        0 => EncodingMode::Numeric,
+        1 => EncodingMode::Alphanumeric,
+        2 => EncodingMode::Byte,
+        _ => EncodingMode::detect(&data),
+    };
+    let error_correction_level = match input.error_correction_level % 4 {
+        0 => ErrorCorrectionLevel::Low,
+        1 => ErrorCorrectionLevel::Medium,
+        2 => ErrorCorrectionLevel::Quartile,
+        _ => ErrorCorrectionLevel::High,
+    };
+
+    let options = QrCodeOptions {
+        min_version: input.min_version,
+        max_version: input.max_version,
+        mask: input.mask,
+        knockout: input.knockout.map(|(width, height, circle)| {
+            let shape = if circle {
+                KnockoutShape::Circle
+            } else {
+                KnockoutShape::Rectangle
+            };
+            Knockout::new(width as usize, height as usize).shape(shape)
+        }),
+        raise_error_correction: input.raise_error_correction,
+    };
+
+    let Ok(qr_code) =
+        QrCode::create_with_options(data.clone(), encoding_mode, error_correction_level, &options)
+    else {
+        return;
+    };
+
+    let decoded = decode_matrix(&qr_code.to_matrix(4)).expect("a created code decodes");
+    assert_eq!(decoded.data, data);
+    assert_eq!(decoded.version, qr_code.version);
+    assert_eq!(decoded.mask, qr_code.mask);
+});
@@ -0,0 +1,52 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use qr_code::qr_code::{decode_matrix, EncodingMode, ErrorCorrectionLevel, QrCode};
+
+#[derive(Arbitrary, Debug)]
+enum Input<'a> {
+    /// a bitmap of any shape, one bit per pixel row by row
+    Image { width: u8, pixels: &'a [u8] },
+    /// a real code with modules flipped, to get past the format information and into the data
+    Damaged { data: &'a str, flips: Vec<(u8, u8)> },
+}
+
+// the decoder refuses what it cannot read rather than panicking
+fuzz_target!(|input: Input| {
+    let modules = match input {
+        Input::Image { width, pixels } => {
+            let width = width as usize;
+            let bits: Vec<bool> = pixels
+                .iter()
+                .flat_map(|byte| (0..8).map(move |bit| byte & (0x80 >> bit) != 0))
+                .collect();
+
+            if width == 0 {
+                vec![bits]
+            } else {
+                bits.chunks(width).map(|row| row.to_vec()).collect()
+            }
+        }
+        Input::Damaged { data, flips } => {
+            let Ok(qr_code) = QrCode::create(
+                data.to_string(),
+                EncodingMode::Byte,
+                ErrorCorrectionLevel::Medium,
+            ) else {
+                return;
+            };
+
+            let mut modules = qr_code.to_matrix(1);
+            let width = modules.len();
+            for (x, y) in flips {
+                let (x, y) = (x as usize % width, y as usize % width);
+                modules[y][x] = !modules[y][x];
+            }
+
+            modules
+        }
+    };
+
+    let _ = decode_matrix(&modules);
+});
@@ -0,0 +1,27 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use qr_code::qr_code::encoding::encode;
+use qr_code::qr_code::EncodingMode;
+
+#[derive(Arbitrary, Debug)]
+enum Mode {
+    Numeric,
+    Alphanumeric,
+    Byte,
+}
+
+// any text in any mode either encodes or is refused, the mode detected for it always encodes
+fuzz_target!(|input: (Mode, &str)| {
+    let (mode, data) = input;
+    let mode = match mode {
+        Mode::Numeric => EncodingMode::Numeric,
+        Mode::Alphanumeric => EncodingMode::Alphanumeric,
+        Mode::Byte => EncodingMode::Byte,
+    };
+
+    let _ = encode(data, &mode);
+
+    assert!(encode(data, &EncodingMode::detect(data)).is_ok());
+});
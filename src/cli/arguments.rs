@@ -0,0 +1,576 @@
+use std::path::PathBuf;
+
+use crate::cli::error::CliError;
+use crate::cli::output::OutputFormat;
+use qr_code::qr_code::render::{
+    Cmyk, Color, Fill, FinderShape, Length, Logo, ModuleShape, PhysicalSize, PrintOptions,
+    RenderOptions,
+};
+use qr_code::qr_code::{ErrorCorrectionLevel, Knockout, KnockoutShape, QrCodeOptions};
+
+/**
+ * The options for creating and rendering a code, shared by USAGE and BATCH_USAGE. A macro rather
+ * than a constant so concat! can build both of them from it.
+ */
+macro_rules! code_options {
+    () => {
+"  -m, --mode <MODE>         auto, numeric, alphanumeric or byte [default: auto]
+  -e, --ec <LEVEL>          Error correction level: low, medium, quartile or high [default: medium]
+      --min-version <N>     Smallest version to use, 1 to 40 [default: 1]
+      --max-version <N>     Largest version to use, 1 to 40 [default: 40]
+      --mask <N>            Mask pattern to use, 0 to 7 [default: lowest penalty]
+      --logo <PATH>         Image to draw in the middle of SVG output, the error correction level
+                            is raised when needed to recover the modules it covers
+      --logo-size <N>       Width and height of the logo area in modules [default: 7]
+      --logo-shape <SHAPE>  rectangle or circle [default: rectangle]
+  -q, --quiet-zone <N>      Light modules around the symbol [default: 4]
+  -s, --module-size <N>     Pixels per module in image output [default: 10]
+      --size <LENGTH>       Fit image output to a printed size, such as 25mm, picking a whole number
+                            of pixels per module
+      --dpi <N>             Resolution written to PNG, BMP and SVG output [default: 300 with --size]
+      --min-module <LENGTH> Smallest printable module with --size [default: 0.33mm]
+      --module-shape <SHAPE>
+                            square, circle, rounded, vertical-bars or horizontal-bars
+                            [default: square]
+      --finder-shape <SHAPE>
+                            Outer ring of the finder patterns: square, rounded or circle
+                            [default: square]
+      --eye-shape <SHAPE>   Inner eye of the finder patterns: square, rounded or circle
+                            [default: square]
+      --corner-radius <N>   Radius of rounded shapes, as a percentage of their width
+                            [default: 25]
+      --foreground <FILL>   Dark modules: #RRGGBB, linear:<ANGLE>:<FROM>:<TO> or
+                            radial:<CENTRE>:<EDGE> [default: #000000]
+      --finder-color <FILL> Finder patterns, in the same form as --foreground [default: foreground]
+      --background <COLOR>  Light modules and quiet zone [default: #FFFFFF]
+      --background-image <PATH>
+                            Image drawn behind the modules of SVG output
+      --invert              Swap dark and light modules, quiet zone included
+      --print-size <LENGTH> Size of EPS, PostScript and PDF output including the quiet zone, such as
+                            30mm or 1.5in [default: 30mm]
+      --cmyk <C,M,Y,K>      Ink for dark modules in print output, in percent [default: 0,0,0,100]
+      --cmyk-background <C,M,Y,K>
+                            Ink for light modules in print output [default: none]
+      --dark-text <TEXT>    Text for each dark module in matrix output [default: 1]
+      --light-text <TEXT>   Text for each light module in matrix output [default: 0]
+"
+    };
+}
+
+pub const USAGE: &str = concat!(
+    "\
+Usage: qr-creator [OPTIONS] [DATA]
+       qr-creator batch [OPTIONS] <INPUT>
+
+Arguments:
+  [DATA]  Text to encode, read from stdin when omitted or \"-\"
+
+Options:
+  -f, --file <PATH>         Read the data from a file instead
+",
+    code_options!(),
+    "  -t, --format <FORMAT>     terminal, png, svg, bmp, pbm, pbm-plain, pgm, gif, eps, ps, pdf, zpl,
+                            zpl-graphic, escpos, escpos-raster, html, html-grid, matrix or json
+                            [default: from the output extension, or terminal]
+  -o, --output <PATH>       Write to a file instead of stdout
+  -d, --details             Print details about the QR code to stderr
+      --explain             Print every stage of the encoding pipeline to stderr
+  -h, --help                Print help
+  -V, --version             Print version
+
+Exit codes:
+  0  Success
+  1  Invalid arguments
+  2  Invalid input, the data could not be read or encoded in the mode
+  3  The data does not fit in the allowed versions
+  4  The output could not be written
+  5  Some rows of a batch failed
+
+Run qr-creator batch --help for batch generation
+"
+);
+
+pub const BATCH_USAGE: &str = concat!(
+    "\
+Usage: qr-creator batch [OPTIONS] <INPUT>
+
+Generates one QR code per row of a CSV file, or per line of any other file.
+
+CSV files need a header row with a \"data\" column. The optional \"ec\", \"mode\" and \"filename\"
+columns override --ec, --mode and --name for that row. Rows that fail are reported with their row
+number once every row has been processed.
+
+Arguments:
+  <INPUT>  CSV or newline delimited file, empty lines are skipped
+
+Options:
+  -o, --output-dir <DIR>    Directory to write the codes to, created if missing [default: .]
+  -n, --name <TEMPLATE>     File name without extension, {row} is replaced by the row number and
+                            {column} by the value of a CSV column [default: {row}]
+  -t, --format <FORMAT>     png, svg, bmp, pbm, pbm-plain, pgm, gif, eps, ps, pdf, zpl, zpl-graphic,
+                            escpos, escpos-raster, html, html-grid, matrix, json or terminal
+                            [default: png]
+      --csv                 Treat the input as CSV regardless of its extension
+      --lines               Treat the input as one payload per line regardless of its extension
+",
+    code_options!(),
+    "  -h, --help                Print help
+"
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModeArgument {
+    Auto,
+    Numeric,
+    Alphanumeric,
+    Byte,
+}
+
+pub enum DataSource {
+    Argument(String),
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    Csv,
+    Lines,
+}
+
+/**
+ * Options that control how each QR code is created and rendered, shared by both commands
+ */
+#[derive(Clone)]
+pub struct GenerateOptions {
+    pub mode: ModeArgument,
+    pub error_correction_level: ErrorCorrectionLevel,
+    pub qr_code_options: QrCodeOptions,
+    pub render_options: RenderOptions,
+    pub print_options: PrintOptions,
+    /// raster output is fitted to this size once the code is created
+    pub size: Option<Length>,
+    pub min_module_size: Length,
+    /// the dark and light module text of matrix output
+    pub text_modules: (String, String),
+    pub format: Option<OutputFormat>,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> GenerateOptions {
+        GenerateOptions {
+            mode: ModeArgument::Auto,
+            error_correction_level: ErrorCorrectionLevel::Medium,
+            qr_code_options: QrCodeOptions::default(),
+            render_options: RenderOptions::default(),
+            print_options: PrintOptions::default(),
+            size: None,
+            min_module_size: PhysicalSize::new(Length::points(0.0), 1).min_module_size,
+            text_modules: ("1".to_string(), "0".to_string()),
+            format: None,
+        }
+    }
+}
+
+pub struct Arguments {
+    pub data: DataSource,
+    pub options: GenerateOptions,
+    pub output: Option<PathBuf>,
+    pub details: bool,
+    pub explain: bool,
+}
+
+pub struct BatchArguments {
+    pub input: PathBuf,
+    pub input_kind: Option<InputKind>,
+    pub options: GenerateOptions,
+    pub output_directory: PathBuf,
+    pub name_template: String,
+}
+
+pub enum Command {
+    Generate(Arguments),
+    Batch(BatchArguments),
+    Help,
+    BatchHelp,
+    Version,
+}
+
+pub fn parse_arguments(args: Vec<String>) -> Result<Command, CliError> {
+    if args.first().map(String::as_str) == Some("batch") {
+        return parse_batch_arguments(args.into_iter().skip(1).collect());
+    }
+
+    let mut options = GenerateOptions::default();
+    let mut output: Option<PathBuf> = None;
+    let mut details = false;
+    let mut explain = false;
+    let mut data: Option<String> = None;
+    let mut file: Option<PathBuf> = None;
+    let mut args = Flags::new(args);
+
+    while let Some((flag, arg)) = args.next_flag() {
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-f" | "--file" => file = Some(PathBuf::from(args.value(&flag)?)),
+            "-o" | "--output" => output = Some(PathBuf::from(args.value(&flag)?)),
+            "-d" | "--details" => details = true,
+            "--explain" => explain = true,
+            // a lone "-" is DATA read from stdin, so it gets the same check as other DATA
+            _ if flag.starts_with('-') && flag != "-" => {
+                if !parse_generate_option(&flag, &mut args, &mut options)? {
+                    return Err(CliError::Usage(format!("Unknown option \"{}\"", flag)));
+                }
+            }
+            _ => {
+                if data.is_some() {
+                    return Err(CliError::Usage(
+                        "Only one DATA argument can be given, quote data that contains spaces"
+                            .to_string(),
+                    ));
+                }
+
+                data = Some(arg)
+            }
+        }
+    }
+
+    let data = match (data, file) {
+        (Some(_), Some(_)) => {
+            return Err(CliError::Usage(
+                "DATA and --file cannot be used together".to_string(),
+            ))
+        }
+        (Some(data), None) if data == "-" => DataSource::Stdin,
+        (Some(data), None) => DataSource::Argument(data),
+        (None, Some(file)) => DataSource::File(file),
+        (None, None) => DataSource::Stdin,
+    };
+
+    Ok(Command::Generate(Arguments {
+        data,
+        options,
+        output,
+        details,
+        explain,
+    }))
+}
+
+fn parse_batch_arguments(args: Vec<String>) -> Result<Command, CliError> {
+    let mut options = GenerateOptions::default();
+    let mut input: Option<PathBuf> = None;
+    let mut input_kind: Option<InputKind> = None;
+    let mut output_directory = PathBuf::from(".");
+    let mut name_template = "{row}".to_string();
+    let mut args = Flags::new(args);
+
+    while let Some((flag, arg)) = args.next_flag() {
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::BatchHelp),
+            "-o" | "--output-dir" => output_directory = PathBuf::from(args.value(&flag)?),
+            "-n" | "--name" => name_template = args.value(&flag)?,
+            "--csv" => input_kind = Some(InputKind::Csv),
+            "--lines" => input_kind = Some(InputKind::Lines),
+            _ if flag.starts_with('-') => {
+                if !parse_generate_option(&flag, &mut args, &mut options)? {
+                    return Err(CliError::Usage(format!("Unknown option \"{}\"", flag)));
+                }
+            }
+            _ => {
+                if input.is_some() {
+                    return Err(CliError::Usage(
+                        "Only one INPUT file can be given".to_string(),
+                    ));
+                }
+
+                input = Some(PathBuf::from(arg))
+            }
+        }
+    }
+
+    let input = match input {
+        Some(input) => input,
+        None => return Err(CliError::Usage("batch requires an INPUT file".to_string())),
+    };
+
+    Ok(Command::Batch(BatchArguments {
+        input,
+        input_kind,
+        options,
+        output_directory,
+        name_template,
+    }))
+}
+
+/**
+ * Handles the flags shared by both commands, returns false if the flag is not one of them
+ */
+fn parse_generate_option(
+    flag: &str,
+    args: &mut Flags,
+    options: &mut GenerateOptions,
+) -> Result<bool, CliError> {
+    match flag {
+        "-m" | "--mode" => options.mode = parse_mode(&args.value(flag)?)?,
+        "-e" | "--ec" => {
+            options.error_correction_level = parse_error_correction_level(&args.value(flag)?)?
+        }
+        "--min-version" => {
+            options.qr_code_options.min_version =
+                parse_number(flag, &args.value(flag)?, 1, 40)? as i16
+        }
+        "--max-version" => {
+            options.qr_code_options.max_version =
+                parse_number(flag, &args.value(flag)?, 1, 40)? as i16
+        }
+        "--mask" => {
+            options.qr_code_options.mask = Some(parse_number(flag, &args.value(flag)?, 0, 7)? as u8)
+        }
+        "-q" | "--quiet-zone" => {
+            options.render_options.quiet_zone = parse_number(flag, &args.value(flag)?, 0, 100)?
+        }
+        "-s" | "--module-size" => {
+            options.render_options.module_size = parse_number(flag, &args.value(flag)?, 1, 100)?
+        }
+        "-t" | "--format" => options.format = Some(OutputFormat::from_name(&args.value(flag)?)?),
+        "--foreground" => {
+            options.render_options.colors.foreground = parse_fill(flag, &args.value(flag)?)?
+        }
+        "--finder-color" => {
+            options.render_options.colors.finder = Some(parse_fill(flag, &args.value(flag)?)?)
+        }
+        "--background" => {
+            options.render_options.colors.background =
+                Color::from_hex(&args.value(flag)?).map_err(CliError::Usage)?
+        }
+        "--invert" => options.render_options.invert = true,
+        "--background-image" => {
+            options.render_options.colors.background_image = Some(read_image(&args.value(flag)?)?)
+        }
+        "--size" => {
+            options.size = Some(Length::parse(&args.value(flag)?).map_err(CliError::Usage)?)
+        }
+        "--dpi" => {
+            options.render_options.dpi =
+                Some(parse_number(flag, &args.value(flag)?, 1, 100_000)? as u32)
+        }
+        "--min-module" => {
+            options.min_module_size = Length::parse(&args.value(flag)?).map_err(CliError::Usage)?
+        }
+        "--dark-text" => options.text_modules.0 = args.value(flag)?,
+        "--light-text" => options.text_modules.1 = args.value(flag)?,
+        "--print-size" => {
+            options.print_options.size =
+                Length::parse(&args.value(flag)?).map_err(CliError::Usage)?
+        }
+        "--cmyk" => {
+            options.print_options.foreground =
+                Cmyk::parse(&args.value(flag)?).map_err(CliError::Usage)?
+        }
+        "--cmyk-background" => {
+            options.print_options.background =
+                Some(Cmyk::parse(&args.value(flag)?).map_err(CliError::Usage)?)
+        }
+        "--logo" => {
+            options.render_options.logo = Some(read_image(&args.value(flag)?)?);
+            options.qr_code_options.raise_error_correction = true;
+            knockout(options);
+        }
+        "--logo-size" => {
+            let size = parse_number(flag, &args.value(flag)?, 1, 160)?;
+            let knockout = knockout(options);
+            knockout.width = size;
+            knockout.height = size;
+        }
+        "--module-shape" => {
+            options.render_options.style.module_shape =
+                match args.value(flag)?.to_ascii_lowercase().as_str() {
+                    "square" => ModuleShape::Square,
+                    "circle" => ModuleShape::Circle,
+                    "rounded" => ModuleShape::Rounded,
+                    "vertical-bars" => ModuleShape::VerticalBars,
+                    "horizontal-bars" => ModuleShape::HorizontalBars,
+                    shape => {
+                        return Err(CliError::Usage(format!(
+                            "Unknown module shape \"{}\", expected square, circle, rounded, vertical-bars or horizontal-bars",
+                            shape
+                        )))
+                    }
+                }
+        }
+        "--finder-shape" => {
+            options.render_options.style.finder_ring = parse_finder_shape(&args.value(flag)?)?
+        }
+        "--eye-shape" => {
+            options.render_options.style.finder_eye = parse_finder_shape(&args.value(flag)?)?
+        }
+        "--corner-radius" => {
+            options.render_options.style.corner_radius =
+                parse_number(flag, &args.value(flag)?, 0, 50)? as u8
+        }
+        "--logo-shape" => {
+            knockout(options).shape = match args.value(flag)?.to_ascii_lowercase().as_str() {
+                "rectangle" => KnockoutShape::Rectangle,
+                "circle" => KnockoutShape::Circle,
+                shape => {
+                    return Err(CliError::Usage(format!(
+                        "Unknown logo shape \"{}\", expected rectangle or circle",
+                        shape
+                    )))
+                }
+            }
+        }
+        _ => return Ok(false),
+    }
+
+    if options.qr_code_options.min_version > options.qr_code_options.max_version {
+        return Err(CliError::Usage(
+            "--min-version cannot be larger than --max-version".to_string(),
+        ));
+    }
+
+    Ok(true)
+}
+
+/**
+ * Reads an image file to embed in SVG output
+ */
+fn read_image(path: &str) -> Result<Logo, CliError> {
+    let mime_type = Logo::mime_type_for_path(path).ok_or_else(|| {
+        CliError::Usage(format!(
+            "Unknown image format \"{}\", expected png, jpg, gif, webp or svg",
+            path
+        ))
+    })?;
+    let data =
+        std::fs::read(path).map_err(|err| CliError::InvalidInput(format!("{}: {}", path, err)))?;
+
+    Ok(Logo::new(mime_type, data))
+}
+
+/**
+ * Reads a solid color or a gradient, such as #336699, linear:45:#000000:#336699 or
+ * radial:#336699:#000000
+ */
+fn parse_fill(flag: &str, value: &str) -> Result<Fill, CliError> {
+    let parts: Vec<&str> = value.split(':').collect();
+    let color = |hex: &str| Color::from_hex(hex).map_err(CliError::Usage);
+
+    match parts.as_slice() {
+        [hex] => Ok(Fill::Solid(color(hex)?)),
+        ["linear", angle, start, end] => Ok(Fill::LinearGradient {
+            start: color(start)?,
+            end: color(end)?,
+            angle: parse_number(flag, angle, 0, 359)? as u16,
+        }),
+        ["radial", center, edge] => Ok(Fill::RadialGradient {
+            center: color(center)?,
+            edge: color(edge)?,
+        }),
+        _ => Err(CliError::Usage(format!(
+            "\"{}\" is not a fill, expected #RRGGBB, linear:<ANGLE>:<FROM>:<TO> or radial:<CENTRE>:<EDGE>",
+            value
+        ))),
+    }
+}
+
+fn parse_finder_shape(value: &str) -> Result<FinderShape, CliError> {
+    match value.to_ascii_lowercase().as_str() {
+        "square" => Ok(FinderShape::Square),
+        "rounded" => Ok(FinderShape::Rounded),
+        "circle" => Ok(FinderShape::Circle),
+        shape => Err(CliError::Usage(format!(
+            "Unknown finder shape \"{}\", expected square, rounded or circle",
+            shape
+        ))),
+    }
+}
+
+/**
+ * The logo area, added with the default size by whichever logo flag comes first
+ */
+fn knockout(options: &mut GenerateOptions) -> &mut Knockout {
+    options
+        .qr_code_options
+        .knockout
+        .get_or_insert(Knockout::new(7, 7))
+}
+
+/**
+ * Walks the arguments, splitting --flag=value so values can be given either way
+ */
+struct Flags {
+    args: std::vec::IntoIter<String>,
+    inline_value: Option<String>,
+}
+
+impl Flags {
+    fn new(args: Vec<String>) -> Flags {
+        Flags {
+            args: args.into_iter(),
+            inline_value: None,
+        }
+    }
+
+    /**
+     * Returns the flag name along with the original argument
+     */
+    fn next_flag(&mut self) -> Option<(String, String)> {
+        let arg = self.args.next()?;
+
+        match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                self.inline_value = Some(value.to_string());
+                Some((flag.to_string(), arg.clone()))
+            }
+            _ => {
+                self.inline_value = None;
+                Some((arg.clone(), arg))
+            }
+        }
+    }
+
+    fn value(&mut self, flag: &str) -> Result<String, CliError> {
+        match self.inline_value.take().or_else(|| self.args.next()) {
+            Some(value) => Ok(value),
+            None => Err(CliError::Usage(format!("{} requires a value", flag))),
+        }
+    }
+}
+
+pub fn parse_mode(value: &str) -> Result<ModeArgument, CliError> {
+    match value.to_ascii_lowercase().as_str() {
+        "auto" => Ok(ModeArgument::Auto),
+        "numeric" => Ok(ModeArgument::Numeric),
+        "alphanumeric" => Ok(ModeArgument::Alphanumeric),
+        "byte" => Ok(ModeArgument::Byte),
+        _ => Err(CliError::Usage(format!(
+            "Unknown mode \"{}\", expected auto, numeric, alphanumeric or byte",
+            value
+        ))),
+    }
+}
+
+pub fn parse_error_correction_level(value: &str) -> Result<ErrorCorrectionLevel, CliError> {
+    match value.to_ascii_lowercase().as_str() {
+        "l" | "low" => Ok(ErrorCorrectionLevel::Low),
+        "m" | "medium" => Ok(ErrorCorrectionLevel::Medium),
+        "q" | "quartile" => Ok(ErrorCorrectionLevel::Quartile),
+        "h" | "high" => Ok(ErrorCorrectionLevel::High),
+        _ => Err(CliError::Usage(format!(
+            "Unknown error correction level \"{}\", expected low, medium, quartile or high",
+            value
+        ))),
+    }
+}
+
+fn parse_number(flag: &str, value: &str, min: usize, max: usize) -> Result<usize, CliError> {
+    match value.parse::<usize>() {
+        Ok(number) if number >= min && number <= max => Ok(number),
+        _ => Err(CliError::Usage(format!(
+            "{} must be a number from {} to {}, found \"{}\"",
+            flag, min, max, value
+        ))),
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::cli::arguments::{
+    parse_error_correction_level, parse_mode, BatchArguments, GenerateOptions, InputKind,
+};
+use crate::cli::csv::parse_csv;
+use crate::cli::error::CliError;
+use crate::cli::output::OutputFormat;
+use crate::cli::{check_contrast, create_qr_code, render};
+
+/**
+ * A payload to generate along with any values from other CSV columns
+ */
+struct Row {
+    number: usize,
+    data: String,
+    columns: HashMap<String, String>,
+}
+
+/**
+ * Generates a QR code for every row of the input, failures are collected and reported at the end
+ * instead of stopping the batch
+ */
+pub fn run_batch(arguments: &BatchArguments) -> Result<(), CliError> {
+    check_contrast(&arguments.options.render_options)?;
+
+    let text = fs::read_to_string(&arguments.input)
+        .map_err(|err| CliError::InvalidInput(format!("{}: {}", arguments.input.display(), err)))?;
+
+    let input_kind = arguments
+        .input_kind
+        .unwrap_or_else(|| input_kind_from_path(&arguments.input));
+
+    let rows = match input_kind {
+        InputKind::Csv => csv_rows(&text)?,
+        InputKind::Lines => line_rows(&text),
+    };
+
+    let format = arguments.options.format.unwrap_or(OutputFormat::Png);
+
+    fs::create_dir_all(&arguments.output_directory).map_err(|err| {
+        CliError::Output(format!("{}: {}", arguments.output_directory.display(), err))
+    })?;
+
+    let mut failures: Vec<(usize, CliError)> = Vec::new();
+    let mut used_names: HashMap<String, usize> = HashMap::new();
+    let mut generated = 0;
+
+    for row in &rows {
+        let result = generate_row(row, arguments, format, &mut used_names);
+
+        match result {
+            Ok(()) => generated += 1,
+            Err(err) => failures.push((row.number, err)),
+        }
+    }
+
+    eprintln!(
+        "Generated {} of {} codes in {}",
+        generated,
+        rows.len(),
+        arguments.output_directory.display()
+    );
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    for (row, err) in &failures {
+        eprintln!("  Row {}: {}", row, err);
+    }
+
+    Err(CliError::PartialFailure(format!(
+        "{} of {} rows failed",
+        failures.len(),
+        rows.len()
+    )))
+}
+
+fn generate_row(
+    row: &Row,
+    arguments: &BatchArguments,
+    format: OutputFormat,
+    used_names: &mut HashMap<String, usize>,
+) -> Result<(), CliError> {
+    let mut options: GenerateOptions = arguments.options.clone();
+
+    if let Some(level) = non_empty(row.columns.get("ec")) {
+        options.error_correction_level = parse_error_correction_level(level)?;
+    }
+
+    if let Some(mode) = non_empty(row.columns.get("mode")) {
+        options.mode = parse_mode(mode)?;
+    }
+
+    let template = non_empty(row.columns.get("filename")).unwrap_or(&arguments.name_template);
+    let name = expand_template(template, row);
+
+    if name.is_empty() {
+        return Err(CliError::InvalidInput(
+            "The file name template produced an empty name".to_string(),
+        ));
+    }
+
+    let file_name = format!("{}.{}", name, format.extension());
+
+    if let Some(previous_row) = used_names.get(&file_name) {
+        return Err(CliError::InvalidInput(format!(
+            "{} was already written by row {}",
+            file_name, previous_row
+        )));
+    }
+
+    let qr_code = create_qr_code(row.data.clone(), &options)?;
+    let bytes = render(&qr_code, format, &options)?;
+
+    let path = arguments.output_directory.join(&file_name);
+    fs::write(&path, bytes)
+        .map_err(|err| CliError::Output(format!("{}: {}", path.display(), err)))?;
+
+    used_names.insert(file_name, row.number);
+
+    Ok(())
+}
+
+fn input_kind_from_path(path: &Path) -> InputKind {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => InputKind::Csv,
+        _ => InputKind::Lines,
+    }
+}
+
+/**
+ * Every line is a payload, row numbers are line numbers so they match what an editor shows
+ */
+fn line_rows(text: &str) -> Vec<Row> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| Row {
+            number: i + 1,
+            data: line.to_string(),
+            columns: HashMap::new(),
+        })
+        .collect()
+}
+
+/**
+ * The first record is the header, row numbers are the line each record starts on so they match
+ * what a spreadsheet or editor shows
+ */
+fn csv_rows(text: &str) -> Result<Vec<Row>, CliError> {
+    let records = parse_csv(text).map_err(CliError::InvalidInput)?;
+    let mut records = records.into_iter();
+
+    let header: Vec<String> = match records.next() {
+        Some(header) => header
+            .fields
+            .iter()
+            .map(|name| name.trim().to_ascii_lowercase())
+            .collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = header.iter().find(|name| !seen.insert(name.as_str())) {
+        return Err(CliError::InvalidInput(format!(
+            "The CSV header has more than one \"{}\" column",
+            duplicate
+        )));
+    }
+
+    let data_column = match header.iter().position(|name| name == "data") {
+        Some(column) => column,
+        None => {
+            return Err(CliError::InvalidInput(
+                "The CSV header needs a \"data\" column".to_string(),
+            ))
+        }
+    };
+
+    let rows = records
+        .filter(|record| record.fields.iter().any(|field| !field.is_empty()))
+        .map(|record| Row {
+            number: record.line,
+            data: record.fields.get(data_column).cloned().unwrap_or_default(),
+            columns: header
+                .iter()
+                .cloned()
+                .zip(record.fields.iter().cloned())
+                .collect(),
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+/**
+ * Replaces {row} with the row number and {column} with the value of that column, characters that
+ * cannot be used in file names are replaced with underscores
+ */
+fn expand_template(template: &str, row: &Row) -> String {
+    let mut name = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        name += &rest[..start];
+
+        match rest[start..].find('}') {
+            Some(end) => {
+                let key = rest[start + 1..start + end].trim().to_ascii_lowercase();
+
+                if key == "row" {
+                    name += &row.number.to_string();
+                } else if let Some(value) = row.columns.get(&key) {
+                    name += value;
+                } else {
+                    name += &rest[start..start + end + 1];
+                }
+
+                rest = &rest[start + end + 1..];
+            }
+            None => {
+                name += &rest[start..];
+                rest = "";
+            }
+        }
+    }
+
+    name += rest;
+
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+fn non_empty(value: Option<&String>) -> Option<&String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+#[test]
+fn test_expand_template() {
+    let row = Row {
+        number: 7,
+        data: "ABC".to_string(),
+        columns: [("sku".to_string(), "A/1".to_string())]
+            .into_iter()
+            .collect(),
+    };
+
+    assert_eq!(expand_template("{row}", &row), "7");
+    assert_eq!(expand_template("tag-{SKU}-{row}", &row), "tag-A_1-7");
+    assert_eq!(expand_template("{missing}", &row), "{missing}");
+}
@@ -0,0 +1,91 @@
+/**
+ * A record from a CSV file along with the line it starts on, so errors can point at the row
+ */
+pub struct Record {
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+/**
+ * Parses CSV text into records, fields may be quoted with double quotes to contain commas, line
+ * breaks or quotes written as two double quotes.
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc4180
+ */
+pub fn parse_csv(text: &str) -> Result<Vec<Record>, String> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+
+                    field.push(c)
+                }
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                records.push(Record {
+                    line: record_line,
+                    fields: std::mem::take(&mut fields),
+                });
+
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!(
+            "Unterminated quoted field starting on line {}",
+            record_line
+        ));
+    }
+
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push(Record {
+            line: record_line,
+            fields,
+        });
+    }
+
+    Ok(records)
+}
+
+#[test]
+fn test_parse_csv() {
+    let records = parse_csv("data,ec\r\nA1,high\n\"a, \"\"quoted\"\"\nvalue\",\nlast").unwrap();
+
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0].fields, vec!["data", "ec"]);
+    assert_eq!(records[1].fields, vec!["A1", "high"]);
+    assert_eq!(records[2].fields, vec!["a, \"quoted\"\nvalue", ""]);
+    assert_eq!(records[2].line, 3);
+    assert_eq!(records[3].line, 5);
+    assert_eq!(records[3].fields, vec!["last"]);
+}
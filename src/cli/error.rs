@@ -0,0 +1,43 @@
+use std::fmt;
+
+/**
+ * Everything that can go wrong in the command line interface, each kind exits with its own code so
+ * scripts can tell bad input apart from data that is simply too large
+ */
+#[derive(Debug)]
+pub enum CliError {
+    /// unknown flags, missing values or values out of range
+    Usage(String),
+    /// data that cannot be read or cannot be encoded in the requested mode
+    InvalidInput(String),
+    /// data that does not fit in any allowed version
+    Capacity(String),
+    /// failed to write the output
+    Output(String),
+    /// some rows of a batch could not be generated
+    PartialFailure(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => 1,
+            CliError::InvalidInput(_) => 2,
+            CliError::Capacity(_) => 3,
+            CliError::Output(_) => 4,
+            CliError::PartialFailure(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}\n\nRun with --help to see usage", message),
+            CliError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            CliError::Capacity(message) => write!(f, "Capacity: {}", message),
+            CliError::Output(message) => write!(f, "Output: {}", message),
+            CliError::PartialFailure(message) => write!(f, "{}", message),
+        }
+    }
+}
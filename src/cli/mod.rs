@@ -0,0 +1,236 @@
+mod arguments;
+mod batch;
+mod csv;
+mod error;
+mod output;
+
+use std::fs;
+use std::io::{self, Read, Write};
+
+use arguments::{
+    parse_arguments, Arguments, Command, DataSource, GenerateOptions, ModeArgument, BATCH_USAGE,
+    USAGE,
+};
+use batch::run_batch;
+use error::CliError;
+use output::OutputFormat;
+use qr_code::qr_code::encoding::encode;
+use qr_code::qr_code::render::{HtmlLayout, PhysicalSize, RenderOptions, SizeReport};
+use qr_code::qr_code::{EncodingMode, QrCode};
+
+/**
+ * Runs the command line interface and returns the exit code
+ */
+pub fn run(args: Vec<String>) -> i32 {
+    let result = match parse_arguments(args) {
+        Ok(Command::Help) => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Ok(Command::Version) => {
+            println!("qr-creator {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        Ok(Command::BatchHelp) => {
+            print!("{}", BATCH_USAGE);
+            Ok(())
+        }
+        Ok(Command::Generate(arguments)) => generate(&arguments),
+        Ok(Command::Batch(arguments)) => run_batch(&arguments),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            err.exit_code()
+        }
+    }
+}
+
+fn generate(arguments: &Arguments) -> Result<(), CliError> {
+    check_contrast(&arguments.options.render_options)?;
+
+    let data = read_data(&arguments.data)?;
+    let qr_code = create_qr_code(data, &arguments.options)?;
+
+    let format = match (arguments.options.format, &arguments.output) {
+        (Some(format), _) => format,
+        (None, Some(path)) => OutputFormat::from_path(path)?,
+        (None, None) => OutputFormat::Terminal,
+    };
+
+    if arguments.details {
+        eprintln!("Version: {}", qr_code.version.version());
+        eprintln!("Encoding Mode: {}", qr_code.encoding_mode);
+        eprintln!("Error Correction Level: {}", qr_code.error_correction_level);
+        eprintln!("Mask Pattern: {}", qr_code.mask);
+
+        if format.is_print() {
+            let width = qr_code.width() + 2 * arguments.options.render_options.quiet_zone;
+            let module_size = arguments.options.print_options.module_size(width);
+            eprintln!("Module Size: {:.3} mm", module_size.to_millimeters());
+        }
+
+        if let Some(report) = fit_size(&qr_code, format, &arguments.options)? {
+            eprintln!(
+                "Printed Size: {:.2} mm at {} dpi, {} pixels",
+                report.printed_size.to_millimeters(),
+                report.dpi,
+                report.pixels
+            );
+            eprintln!(
+                "Module Size: {:.3} mm, {} pixels",
+                report.printed_module_size.to_millimeters(),
+                report.module_size
+            );
+        }
+    }
+
+    if arguments.explain {
+        eprint!("{}", qr_code.report().map_err(CliError::InvalidInput)?);
+    }
+
+    let bytes = render(&qr_code, format, &arguments.options)?;
+
+    match &arguments.output {
+        Some(path) => fs::write(path, bytes)
+            .map_err(|err| CliError::Output(format!("{}: {}", path.display(), err))),
+        None => io::stdout()
+            .write_all(&bytes)
+            .map_err(|err| CliError::Output(err.to_string())),
+    }
+}
+
+/**
+ * Prints a warning for colors some scanners may struggle with and fails for colors that cannot be
+ * read at all
+ */
+pub fn check_contrast(options: &RenderOptions) -> Result<(), CliError> {
+    let contrast = options.colors.check_contrast();
+
+    for warning in contrast.warnings() {
+        eprintln!("Warning: {}", warning);
+    }
+
+    contrast.validate().map_err(CliError::InvalidInput)
+}
+
+pub fn create_qr_code(data: String, options: &GenerateOptions) -> Result<QrCode, CliError> {
+    let encoding_mode = match options.mode {
+        ModeArgument::Auto => EncodingMode::detect(&data),
+        ModeArgument::Numeric => EncodingMode::Numeric,
+        ModeArgument::Alphanumeric => EncodingMode::Alphanumeric,
+        ModeArgument::Byte => EncodingMode::Byte,
+    };
+
+    // encoding first separates characters the mode cannot hold from data that is too large
+    encode(&data, &encoding_mode).map_err(CliError::InvalidInput)?;
+
+    QrCode::create_with_options(
+        data,
+        encoding_mode,
+        options.error_correction_level,
+        &options.qr_code_options,
+    )
+    .map_err(CliError::Capacity)
+}
+
+/**
+ * Fits image output to the size given with --size, print formats have their own size and the
+ * terminal has none
+ */
+pub fn fit_size(
+    qr_code: &QrCode,
+    format: OutputFormat,
+    options: &GenerateOptions,
+) -> Result<Option<SizeReport>, CliError> {
+    let Some(size) = options.size else {
+        return Ok(None);
+    };
+
+    if format.is_print() || format == OutputFormat::Terminal {
+        return Ok(None);
+    }
+
+    let physical = PhysicalSize {
+        size,
+        dpi: options.render_options.dpi.unwrap_or(300),
+        min_module_size: options.min_module_size,
+    };
+
+    // the data fits, the --size and --dpi given for it are what is wrong
+    qr_code
+        .fit_physical_size(&options.render_options, &physical)
+        .map(Some)
+        .map_err(CliError::Usage)
+}
+
+pub fn render(
+    qr_code: &QrCode,
+    format: OutputFormat,
+    options: &GenerateOptions,
+) -> Result<Vec<u8>, CliError> {
+    let print = &options.print_options;
+    let (dark, light) = &options.text_modules;
+    let fitted;
+    let options = match fit_size(qr_code, format, options)? {
+        Some(report) => {
+            fitted = report.apply(&options.render_options);
+            &fitted
+        }
+        None => &options.render_options,
+    };
+
+    Ok(match format {
+        OutputFormat::Terminal => qr_code.to_terminal_string(options).into_bytes(),
+        OutputFormat::Png => qr_code.to_png(options),
+        OutputFormat::Svg => qr_code.to_svg(options).into_bytes(),
+        OutputFormat::Bmp => qr_code.to_bmp(options),
+        OutputFormat::Pbm => qr_code.to_pbm(options),
+        OutputFormat::PlainPbm => qr_code.to_plain_pbm(options),
+        OutputFormat::Pgm => qr_code.to_pgm(options),
+        OutputFormat::Gif => qr_code.to_gif(options),
+        OutputFormat::Eps => qr_code.to_eps(options, print).into_bytes(),
+        OutputFormat::PostScript => qr_code.to_postscript(options, print).into_bytes(),
+        OutputFormat::Pdf => qr_code.to_pdf(options, print),
+        OutputFormat::Zpl => qr_code
+            .to_zpl(options)
+            .map_err(CliError::Usage)?
+            .into_bytes(),
+        OutputFormat::ZplGraphic => qr_code.to_zpl_graphic(options).into_bytes(),
+        OutputFormat::EscPos => qr_code.to_escpos(options).map_err(CliError::Usage)?,
+        OutputFormat::EscPosRaster => qr_code.to_escpos_raster(options),
+        OutputFormat::Html => qr_code.to_html(options, HtmlLayout::Table).into_bytes(),
+        OutputFormat::HtmlGrid => qr_code.to_html(options, HtmlLayout::Grid).into_bytes(),
+        OutputFormat::Matrix => qr_code.to_text(options, dark, light).into_bytes(),
+        OutputFormat::Json => qr_code.to_json(options).into_bytes(),
+    })
+}
+
+fn read_data(source: &DataSource) -> Result<String, CliError> {
+    match source {
+        DataSource::Argument(data) => Ok(data.clone()),
+        DataSource::File(path) => fs::read_to_string(path)
+            .map_err(|err| CliError::InvalidInput(format!("{}: {}", path.display(), err))),
+        DataSource::Stdin => {
+            let mut data = String::new();
+
+            io::stdin()
+                .read_to_string(&mut data)
+                .map_err(|err| CliError::InvalidInput(format!("stdin: {}", err)))?;
+
+            // drop the newline added by echo and most editors
+            if data.ends_with('\n') {
+                data.pop();
+
+                if data.ends_with('\r') {
+                    data.pop();
+                }
+            }
+
+            Ok(data)
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::path::Path;
+
+use crate::cli::error::CliError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Terminal,
+    Png,
+    Svg,
+    Bmp,
+    Pbm,
+    PlainPbm,
+    Pgm,
+    Gif,
+    Eps,
+    PostScript,
+    Pdf,
+    Zpl,
+    ZplGraphic,
+    EscPos,
+    EscPosRaster,
+    Html,
+    HtmlGrid,
+    Matrix,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Result<OutputFormat, CliError> {
+        match name.to_ascii_lowercase().as_str() {
+            "terminal" | "text" | "txt" => Ok(OutputFormat::Terminal),
+            "png" => Ok(OutputFormat::Png),
+            "svg" => Ok(OutputFormat::Svg),
+            "bmp" => Ok(OutputFormat::Bmp),
+            "pbm" => Ok(OutputFormat::Pbm),
+            "pbm-plain" => Ok(OutputFormat::PlainPbm),
+            "pgm" => Ok(OutputFormat::Pgm),
+            "gif" => Ok(OutputFormat::Gif),
+            "eps" => Ok(OutputFormat::Eps),
+            "ps" => Ok(OutputFormat::PostScript),
+            "pdf" => Ok(OutputFormat::Pdf),
+            "zpl" => Ok(OutputFormat::Zpl),
+            "zpl-graphic" => Ok(OutputFormat::ZplGraphic),
+            "escpos" => Ok(OutputFormat::EscPos),
+            "escpos-raster" => Ok(OutputFormat::EscPosRaster),
+            "html" | "htm" => Ok(OutputFormat::Html),
+            "html-grid" => Ok(OutputFormat::HtmlGrid),
+            "matrix" => Ok(OutputFormat::Matrix),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(CliError::Usage(format!(
+                "Unknown output format \"{}\", expected terminal, png, svg, bmp, pbm, pbm-plain, pgm, gif, eps, ps, pdf, zpl, zpl-graphic, escpos, escpos-raster, html, html-grid, matrix or json",
+                name
+            ))),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Terminal => "txt",
+            OutputFormat::Png => "png",
+            OutputFormat::Svg => "svg",
+            OutputFormat::Bmp => "bmp",
+            OutputFormat::Pbm | OutputFormat::PlainPbm => "pbm",
+            OutputFormat::Pgm => "pgm",
+            OutputFormat::Gif => "gif",
+            OutputFormat::Eps => "eps",
+            OutputFormat::PostScript => "ps",
+            OutputFormat::Pdf => "pdf",
+            OutputFormat::Zpl | OutputFormat::ZplGraphic => "zpl",
+            OutputFormat::EscPos | OutputFormat::EscPosRaster => "escpos",
+            OutputFormat::Html | OutputFormat::HtmlGrid => "html",
+            OutputFormat::Matrix => "txt",
+            OutputFormat::Json => "json",
+        }
+    }
+
+    /**
+     * Whether the format is drawn at a physical size with the print options
+     */
+    pub fn is_print(&self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Eps | OutputFormat::PostScript | OutputFormat::Pdf
+        )
+    }
+
+    /**
+     * Guesses the format from the extension of the output file, files without a known extension
+     * get the terminal format
+     */
+    pub fn from_path(path: &Path) -> Result<OutputFormat, CliError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) => OutputFormat::from_name(extension).map_err(|_| {
+                CliError::Usage(format!(
+                    "Cannot tell the output format from \"{}\", use --format",
+                    path.display()
+                ))
+            }),
+            None => Ok(OutputFormat::Terminal),
+        }
+    }
+}
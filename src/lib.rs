@@ -1 +1,8 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod qr_code;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,21 +1,7 @@
-pub mod qr_code;
-
-use crate::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode};
+mod cli;
 
 fn main() {
-    let data = "HELLO WORLD".to_string();
-
-    let qr_code = match QrCode::create(
-        data,
-        EncodingMode::Alphanumeric,
-        ErrorCorrectionLevel::Quartile,
-    ) {
-        Ok(qr_code) => qr_code,
-        Err(err) => {
-            println!("Error: {}", err);
-            std::process::exit(1)
-        }
-    };
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    qr_code.details()
+    std::process::exit(cli::run(args))
 }
@@ -0,0 +1,259 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(all(test, feature = "alloc"))]
+use alloc::{string::ToString, vec};
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+/**
+ * Somewhere the stages of the pipeline append bits to, most significant bit first. `BitBuffer` grows
+ * as needed, `FixedQrCode` writes into a slice of its own buffers.
+ */
+pub trait BitSink {
+    /**
+     * Appends the lowest `len` bits of `value`, most significant bit first
+     */
+    fn append_bits(&mut self, value: u32, len: u8);
+
+    /**
+     * Number of bits appended so far
+     */
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /**
+     * Appends 8 bits for every byte
+     */
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.append_bits(byte as u32, 8);
+        }
+    }
+}
+
+/**
+ * A growable sequence of bits packed eight to a byte, most significant bit first.
+ *
+ * Every stage of the pipeline appends to one of these instead of building strings of '0' and '1',
+ * so the buffer can be handed straight to the error correction stage as bytes.
+ */
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitBuffer {
+    data: Vec<u8>,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl BitBuffer {
+    pub fn new() -> BitBuffer {
+        BitBuffer {
+            data: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /**
+     * Creates an empty buffer with room for at least `bits` bits
+     */
+    pub fn with_capacity(bits: usize) -> BitBuffer {
+        BitBuffer {
+            data: Vec::with_capacity(bits.div_ceil(8)),
+            len: 0,
+        }
+    }
+
+    /**
+     * Appends the lowest `len` bits of `value`, most significant bit first.
+     *
+     * 779 with a length of 11 → 01100001011
+     */
+    pub fn append_bits(&mut self, value: u32, len: u8) {
+        assert!(len <= 32, "Cannot append more than 32 bits at once");
+        debug_assert!(
+            len == 32 || value >> len == 0,
+            "Value {} does not fit in {} bits",
+            value,
+            len
+        );
+
+        // fill the last byte, then whole bytes, at most 8 bits at a time
+        let mut remaining = len;
+        while remaining > 0 {
+            let used = self.len % 8;
+            if used == 0 {
+                self.data.push(0);
+            }
+
+            let take = (8 - used).min(remaining as usize) as u8;
+            let chunk = (value >> (remaining - take)) & ((1 << take) - 1);
+
+            if let Some(last) = self.data.last_mut() {
+                *last |= (chunk << (8 - used as u8 - take)) as u8;
+            }
+
+            self.len += take as usize;
+            remaining -= take;
+        }
+    }
+
+    /**
+     * Appends every bit of `other` to the end of this buffer
+     */
+    pub fn append_buffer(&mut self, other: &BitBuffer) {
+        let whole_bytes = other.len / 8;
+        self.append_bytes(&other.data[..whole_bytes]);
+
+        let remaining = (other.len % 8) as u8;
+        if remaining > 0 {
+            self.append_bits(
+                (other.data[whole_bytes] >> (8 - remaining)) as u32,
+                remaining,
+            );
+        }
+    }
+
+    /**
+     * Appends 8 bits for every byte, copied as they are when the buffer ends on a byte boundary
+     */
+    pub fn append_bytes(&mut self, bytes: &[u8]) {
+        if self.len.is_multiple_of(8) {
+            self.data.extend_from_slice(bytes);
+            self.len += bytes.len() * 8;
+            return;
+        }
+
+        for &byte in bytes {
+            self.append_bits(byte as u32, 8);
+        }
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.data.push(0);
+        }
+
+        if bit {
+            self.data[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+
+        self.len += 1;
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "Bit index {} out of bounds", index);
+        self.data[index / 8] & (0x80 >> (index % 8)) != 0
+    }
+
+    /**
+     * Number of bits in the buffer
+     */
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /**
+     * Removes every bit, keeping the allocated capacity
+     */
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.len = 0;
+    }
+
+    /**
+     * Iterates over the packed bytes, the last byte is padded with 0s if the length is not a multiple of 8
+     */
+    pub fn bytes(&self) -> impl ExactSizeIterator<Item = u8> + '_ {
+        self.data.iter().copied()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /**
+     * Iterates over every bit in the buffer
+     */
+    pub fn bits(&self) -> impl ExactSizeIterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitSink for BitBuffer {
+    fn append_bits(&mut self, value: u32, len: u8) {
+        BitBuffer::append_bits(self, value, len);
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn append_bytes(&mut self, bytes: &[u8]) {
+        BitBuffer::append_bytes(self, bytes);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<&[u8]> for BitBuffer {
+    fn from(bytes: &[u8]) -> BitBuffer {
+        BitBuffer {
+            data: bytes.to_vec(),
+            len: bytes.len() * 8,
+        }
+    }
+}
+
+/**
+ * Formats the buffer as a string of '0' and '1', useful when comparing against the tutorial examples
+ */
+#[cfg(feature = "alloc")]
+impl fmt::Display for BitBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.bits() {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_append_bits() {
+    let mut buffer = BitBuffer::new();
+
+    buffer.append_bits(0b0010, 4);
+    buffer.append_bits(11, 9);
+    buffer.append_bits(779, 11);
+
+    assert_eq!(buffer.len(), 24);
+    assert_eq!(buffer.to_string(), "001000000101101100001011");
+    assert_eq!(
+        buffer.bytes().collect::<Vec<u8>>(),
+        vec![0b00100000, 0b01011011, 0b00001011]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_append_buffer_unaligned() {
+    let mut first = BitBuffer::new();
+    first.append_bits(0b101, 3);
+
+    let mut second = BitBuffer::new();
+    second.append_bits(0b11001, 5);
+    second.append_bits(0xFF, 8);
+
+    first.append_buffer(&second);
+
+    assert_eq!(first.len(), 16);
+    assert_eq!(first.to_string(), "1011100111111111");
+}
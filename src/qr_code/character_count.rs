@@ -1,36 +1,34 @@
-use crate::qr_code::utils::{convert_hex_to_binary, left_pad};
+#[cfg(feature = "std")]
+use crate::qr_code::BitBuffer;
 use crate::qr_code::{EncodingMode, Version};
 
+#[cfg(feature = "std")]
 pub fn create_character_count_indicator(
     data: &str,
     encoding_mode: &EncodingMode,
     version: &Version,
-) -> String {
+) -> BitBuffer {
     let binary_indicator_bit_length = calculate_binary_indicator_bit_length(encoding_mode, version);
 
-    let count = data.len() as i32;
+    let mut bits = BitBuffer::with_capacity(binary_indicator_bit_length as usize);
+    bits.append_bits(data.len() as u32, binary_indicator_bit_length);
 
-    let hex = format!("{:02X?}", count);
-
-    let binary = left_pad(
-        &convert_hex_to_binary(&hex),
-        binary_indicator_bit_length,
-        "0",
-    );
-
-    binary
+    bits
 }
 
-fn calculate_binary_indicator_bit_length(encoding_mode: &EncodingMode, version: &Version) -> i32 {
+pub fn calculate_binary_indicator_bit_length(
+    encoding_mode: &EncodingMode,
+    version: &Version,
+) -> u8 {
     let version = version.version();
 
-    if version >= 1 && version <= 9 {
+    if (1..=9).contains(&version) {
         match encoding_mode {
             EncodingMode::Numeric => 10,
             EncodingMode::Alphanumeric => 9,
             EncodingMode::Byte => 8,
         }
-    } else if version >= 10 && version <= 26 {
+    } else if (10..=26).contains(&version) {
         match encoding_mode {
             EncodingMode::Numeric => 12,
             EncodingMode::Alphanumeric => 11,
@@ -0,0 +1,349 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::qr_code::character_count::calculate_binary_indicator_bit_length;
+use crate::qr_code::error_correction::{
+    code_word_blocks, correct_errors, correctable_code_words_per_block,
+    get_error_correction_code_words_per_block,
+};
+use crate::qr_code::matrix::{
+    data_modules, format_information_bits, format_information_positions, mask_applies,
+    version_information_bits, Matrix,
+};
+use crate::qr_code::{EncodingMode, ErrorCorrectionLevel, Version};
+
+const ALPHANUMERIC_CHARACTERS: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/**
+ * What was read back from the modules of a QR code
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedQrCode {
+    pub data: String,
+    /// the mode of the first segment
+    pub encoding_mode: EncodingMode,
+    pub error_correction_level: ErrorCorrectionLevel,
+    pub version: Version,
+    pub mask: u8,
+    /// how many code words Reed-Solomon had to correct, over all blocks
+    pub corrected_code_words: usize,
+}
+
+/**
+ * Reads the data back from a grid of modules, true is a dark module, such as the rows of a QR code
+ * or `QrCode::to_matrix`. Light rows and columns around the symbol are ignored, the symbol itself
+ * must be upright and one entry per module.
+ *
+ * Wrong code words are corrected as long as every block has no more than the error correction
+ * level can recover.
+ *
+ * based on ISO/IEC 18004 section 12
+ */
+pub fn decode_matrix(modules: &[Vec<bool>]) -> Result<DecodedQrCode, String> {
+    let symbol = crop_to_symbol(modules)?;
+    let size = symbol.len();
+
+    if !(21..=177).contains(&size) || (size - 17) % 4 != 0 {
+        return Err(format!(
+            "A symbol of {} modules is not the size of a QR code version",
+            size
+        ));
+    }
+
+    let version = Version::Normal(((size - 17) / 4) as i16);
+    let module = |x: usize, y: usize| symbol[y][x];
+
+    let (error_correction_level, mask) = read_format_information(&module, size)?;
+    check_version_information(&module, size, &version)?;
+
+    // the positions of the data modules only depend on the function patterns of the version
+    let positions = data_modules(&Matrix::new(&version));
+    let mut code_words = vec![0u8; positions.len() / 8];
+
+    for (i, &(x, y)) in positions.iter().take(code_words.len() * 8).enumerate() {
+        if module(x, y) != mask_applies(mask, x, y) {
+            code_words[i / 8] |= 0x80 >> (i % 8);
+        }
+    }
+
+    let (data, corrected_code_words) =
+        correct_code_words(&code_words, &error_correction_level, &version)?;
+    let (data, encoding_mode) = read_segments(&data, &version)?;
+
+    Ok(DecodedQrCode {
+        data,
+        encoding_mode,
+        error_correction_level,
+        version,
+        mask,
+        corrected_code_words,
+    })
+}
+
+/**
+ * Drops the light rows and columns around the symbol, the finder patterns make its corners dark
+ */
+fn crop_to_symbol(modules: &[Vec<bool>]) -> Result<Vec<&[bool]>, String> {
+    if modules.iter().any(|row| row.len() != modules.len()) {
+        return Err("The modules of a QR code must form a square".to_string());
+    }
+
+    let dark_rows: Vec<usize> = (0..modules.len())
+        .filter(|&y| modules[y].contains(&true))
+        .collect();
+    let dark_columns: Vec<usize> = (0..modules.len())
+        .filter(|&x| modules.iter().any(|row| row[x]))
+        .collect();
+
+    let (Some(&top), Some(&bottom)) = (dark_rows.first(), dark_rows.last()) else {
+        return Err("There are no dark modules".to_string());
+    };
+    let (left, right) = (dark_columns[0], dark_columns[dark_columns.len() - 1]);
+
+    if bottom - top != right - left {
+        return Err("The dark modules do not form a square symbol".to_string());
+    }
+
+    Ok(modules[top..=bottom]
+        .iter()
+        .map(|row| &row[left..=right])
+        .collect())
+}
+
+/**
+ * Picks the error correction level and mask whose format information is closest to either copy,
+ * up to 3 bits can be wrong
+ */
+fn read_format_information(
+    module: &impl Fn(usize, usize) -> bool,
+    size: usize,
+) -> Result<(ErrorCorrectionLevel, u8), String> {
+    let read = |positions: &[(usize, usize)]| {
+        positions
+            .iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, &(x, y))| {
+                bits | ((module(x, y) as u32) << i)
+            })
+    };
+
+    let [first, second] = format_information_positions(size);
+    let copies = [read(&first), read(&second)];
+
+    let levels = [
+        ErrorCorrectionLevel::Low,
+        ErrorCorrectionLevel::Medium,
+        ErrorCorrectionLevel::Quartile,
+        ErrorCorrectionLevel::High,
+    ];
+
+    levels
+        .into_iter()
+        .flat_map(|level| (0..8).map(move |mask| (level, mask)))
+        .map(|(level, mask)| {
+            let expected = format_information_bits(&level, mask);
+            let distance = copies
+                .iter()
+                .map(|copy| (copy ^ expected).count_ones())
+                .min()
+                .unwrap_or(u32::MAX);
+
+            (distance, level, mask)
+        })
+        .min_by_key(|&(distance, _, _)| distance)
+        .filter(|&(distance, _, _)| distance <= 3)
+        .map(|(_, level, mask)| (level, mask))
+        .ok_or_else(|| "The format information cannot be read".to_string())
+}
+
+/**
+ * Versions 7 and up repeat their version next to two finder patterns, one copy with up to 3 wrong
+ * bits has to agree with the size of the symbol
+ */
+fn check_version_information(
+    module: &impl Fn(usize, usize) -> bool,
+    size: usize,
+    version: &Version,
+) -> Result<(), String> {
+    if version.version() < 7 {
+        return Ok(());
+    }
+
+    let expected = version_information_bits(version);
+    let read = |transpose: bool| {
+        (0..18).fold(0u32, |bits, i| {
+            let (a, b) = (size - 11 + i % 3, i / 3);
+            let dark = if transpose {
+                module(b, a)
+            } else {
+                module(a, b)
+            };
+
+            bits | ((dark as u32) << i)
+        })
+    };
+
+    if [read(false), read(true)]
+        .iter()
+        .any(|copy| (copy ^ expected).count_ones() <= 3)
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "The version information does not match the size of {}",
+            version
+        ))
+    }
+}
+
+/**
+ * Deinterleaves the final message into its blocks, corrects each of them and joins their data
+ * code words, returning them with the number of corrected code words
+ */
+fn correct_code_words(
+    code_words: &[u8],
+    error_correction_level: &ErrorCorrectionLevel,
+    version: &Version,
+) -> Result<(Vec<u8>, usize), String> {
+    let block_of = code_word_blocks(error_correction_level, version)?;
+    let error_correction_code_words =
+        get_error_correction_code_words_per_block(error_correction_level, version)
+            .ok_or_else(|| "Version must be between 1 and 40".to_string())?;
+    let correctable = correctable_code_words_per_block(error_correction_level, version)
+        .ok_or_else(|| "Version must be between 1 and 40".to_string())?;
+
+    let block_count = block_of.iter().max().map_or(0, |&block| block + 1);
+    let mut blocks = vec![Vec::new(); block_count];
+
+    for (&code_word, &block) in code_words.iter().zip(&block_of) {
+        blocks[block].push(code_word);
+    }
+
+    let mut data = Vec::with_capacity(code_words.len());
+    let mut corrected = 0;
+
+    for block in &mut blocks {
+        corrected += correct_errors(block, error_correction_code_words, correctable)?;
+        data.extend_from_slice(&block[..block.len() - error_correction_code_words]);
+    }
+
+    Ok((data, corrected))
+}
+
+/**
+ * Reads the segments of the data code words up to the terminator or the end of the capacity,
+ * returning the text and the mode of the first segment
+ */
+fn read_segments(data: &[u8], version: &Version) -> Result<(String, EncodingMode), String> {
+    let mut reader = BitReader { data, position: 0 };
+    let mut bytes = Vec::new();
+    let mut first_mode = None;
+
+    while reader.remaining() >= 4 {
+        let encoding_mode = match reader.read(4) {
+            0b0000 => break,
+            0b0001 => EncodingMode::Numeric,
+            0b0010 => EncodingMode::Alphanumeric,
+            0b0100 => EncodingMode::Byte,
+            indicator => {
+                return Err(format!(
+                    "Mode indicator {:04b} is not supported by the decoder",
+                    indicator
+                ))
+            }
+        };
+        first_mode.get_or_insert(encoding_mode);
+
+        let count_bits = calculate_binary_indicator_bit_length(&encoding_mode, version);
+        let count = reader.read_checked(count_bits as usize)? as usize;
+
+        match encoding_mode {
+            EncodingMode::Numeric => {
+                for group in (0..count).step_by(3) {
+                    let digits = (count - group).min(3);
+                    let bits = [0, 4, 7, 10][digits];
+                    let value = reader.read_checked(bits)?;
+
+                    if value >= 10u32.pow(digits as u32) {
+                        return Err(format!("{} is not a group of {} digits", value, digits));
+                    }
+                    bytes.extend(format!("{:0width$}", value, width = digits).bytes());
+                }
+            }
+            EncodingMode::Alphanumeric => {
+                for pair in (0..count).step_by(2) {
+                    let character = |index: u32| {
+                        ALPHANUMERIC_CHARACTERS
+                            .get(index as usize)
+                            .copied()
+                            .ok_or_else(|| format!("{} is not an alphanumeric character", index))
+                    };
+
+                    if count - pair >= 2 {
+                        let value = reader.read_checked(11)?;
+                        bytes.push(character(value / 45)?);
+                        bytes.push(character(value % 45)?);
+                    } else {
+                        bytes.push(character(reader.read_checked(6)?)?);
+                    }
+                }
+            }
+            EncodingMode::Byte => {
+                for _ in 0..count {
+                    bytes.push(reader.read_checked(8)? as u8);
+                }
+            }
+        }
+    }
+
+    let encoding_mode = first_mode.ok_or_else(|| "The symbol holds no data".to_string())?;
+    let text = String::from_utf8(bytes).map_err(|_| "The data is not valid UTF-8".to_string())?;
+
+    Ok((text, encoding_mode))
+}
+
+/**
+ * Reads bits from the most significant bit of the first byte onwards
+ */
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn remaining(&self) -> usize {
+        self.data.len() * 8 - self.position
+    }
+
+    fn read(&mut self, bits: usize) -> u32 {
+        (0..bits).fold(0, |value, _| {
+            let bit = (self.data[self.position / 8] >> (7 - self.position % 8)) & 1;
+            self.position += 1;
+
+            (value << 1) | bit as u32
+        })
+    }
+
+    fn read_checked(&mut self, bits: usize) -> Result<u32, String> {
+        if bits > self.remaining() {
+            return Err("The data ends in the middle of a segment".to_string());
+        }
+
+        Ok(self.read(bits))
+    }
+}
+
+#[test]
+fn test_read_segments() {
+    // HELLO WORLD as 1-Q
+    let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236];
+
+    assert_eq!(
+        read_segments(&data, &Version::Normal(1)),
+        Ok(("HELLO WORLD".to_string(), EncodingMode::Alphanumeric))
+    );
+}
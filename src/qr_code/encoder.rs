@@ -0,0 +1,208 @@
+use alloc::{format, string::String, string::ToString};
+use core::mem;
+
+use crate::qr_code::character_count::calculate_binary_indicator_bit_length;
+use crate::qr_code::encoding::encode_into;
+use crate::qr_code::error_correction::{correctable_code_words_per_block, ErrorCorrector};
+use crate::qr_code::knockout::{
+    clear_knockout, obscured_code_words_per_block, raise_error_correction_level,
+};
+use crate::qr_code::matrix::{Matrix, MatrixBuilder};
+use crate::qr_code::version::{
+    determine_data_bits_required_for_version, determine_optimal_qr_code_version,
+};
+use crate::qr_code::{
+    pad_data, BitBuffer, EncodingMode, ErrorCorrectionLevel, QrCode, QrCodeOptions, Version,
+};
+
+/**
+ * Creates QR codes while keeping its buffers and lookup tables between them: the bit buffers of
+ * each stage, the Reed-Solomon generators and error correction code words, a matrix with the
+ * function patterns of every version it has seen and the rows and columns the masks are scored on.
+ *
+ * Creating many codes with `encode_into` only allocates when a code needs more room than any
+ * before it.
+ *
+ * ```
+ * use qr_code::qr_code::{Encoder, EncodingMode, ErrorCorrectionLevel, QrCodeOptions};
+ *
+ * let mut encoder = Encoder::new();
+ * let options = QrCodeOptions::default();
+ * let mut qr_code = encoder.encode("0", EncodingMode::Numeric, ErrorCorrectionLevel::Low, &options)?;
+ *
+ * for ticket in 1..100 {
+ *     let data = ticket.to_string();
+ *     encoder.encode_into(&mut qr_code, &data, EncodingMode::Numeric, ErrorCorrectionLevel::Low, &options)?;
+ * }
+ * # Ok::<(), String>(())
+ * ```
+ */
+#[derive(Default)]
+pub struct Encoder {
+    encoded_data: BitBuffer,
+    data_bits: BitBuffer,
+    code_words: BitBuffer,
+    matrix: Matrix,
+    error_corrector: ErrorCorrector,
+    matrix_builder: MatrixBuilder,
+}
+
+impl Encoder {
+    pub fn new() -> Encoder {
+        Encoder::default()
+    }
+
+    /**
+     * Creates a new QR code, the same one `QrCode::create_with_options` creates
+     */
+    pub fn encode(
+        &mut self,
+        data: &str,
+        encoding_mode: EncodingMode,
+        error_correction_level: ErrorCorrectionLevel,
+        options: &QrCodeOptions,
+    ) -> Result<QrCode, String> {
+        let mut qr_code = QrCode {
+            encoding_mode,
+            error_correction_level,
+            version: Version::Normal(1),
+            data: String::new(),
+            encoded_data: BitBuffer::new(),
+            code_words: BitBuffer::new(),
+            mask: 0,
+            knockout: None,
+            matrix: Matrix::default(),
+        };
+
+        self.encode_into(
+            &mut qr_code,
+            data,
+            encoding_mode,
+            error_correction_level,
+            options,
+        )?;
+
+        Ok(qr_code)
+    }
+
+    /**
+     * Replaces the QR code with a new one for the data, reusing the memory of both. The QR code is
+     * left as it was if the data cannot be encoded.
+     */
+    pub fn encode_into(
+        &mut self,
+        qr_code: &mut QrCode,
+        data: &str,
+        encoding_mode: EncodingMode,
+        error_correction_level: ErrorCorrectionLevel,
+        options: &QrCodeOptions,
+    ) -> Result<(), String> {
+        let mut error_correction_level = error_correction_level;
+
+        let (version, mask) = loop {
+            let (version, mask) =
+                self.build(data, encoding_mode, error_correction_level, options)?;
+
+            let Some(knockout) = options.knockout else {
+                break (version, mask);
+            };
+
+            let obscured = obscured_code_words_per_block(
+                &self.matrix,
+                &knockout,
+                &error_correction_level,
+                &version,
+            )?;
+            let correctable = correctable_code_words_per_block(&error_correction_level, &version)
+                .ok_or_else(|| "Version must be between 1 and 40".to_string())?;
+
+            if obscured <= correctable {
+                clear_knockout(&mut self.matrix, &knockout);
+                break (version, mask);
+            }
+
+            match raise_error_correction_level(&error_correction_level) {
+                Some(raised) if options.raise_error_correction => error_correction_level = raised,
+                _ => {
+                    return Err(format!(
+                        "A {}x{} logo area covers {} code words of one block, error correction level {} can only recover {}",
+                        knockout.width, knockout.height, obscured, error_correction_level, correctable
+                    ))
+                }
+            }
+        };
+
+        qr_code.encoding_mode = encoding_mode;
+        qr_code.error_correction_level = error_correction_level;
+        qr_code.version = version;
+        qr_code.data.clear();
+        qr_code.data.push_str(data);
+        qr_code.mask = mask;
+        qr_code.knockout = options.knockout;
+
+        // the buffers of the QR code become the scratch buffers of the next call
+        mem::swap(&mut qr_code.encoded_data, &mut self.encoded_data);
+        mem::swap(&mut qr_code.code_words, &mut self.code_words);
+        mem::swap(&mut qr_code.matrix, &mut self.matrix);
+
+        Ok(())
+    }
+
+    /**
+     * Runs every stage into the buffers of the encoder, returning the version and mask pattern
+     */
+    fn build(
+        &mut self,
+        data: &str,
+        encoding_mode: EncodingMode,
+        error_correction_level: ErrorCorrectionLevel,
+        options: &QrCodeOptions,
+    ) -> Result<(Version, u8), String> {
+        if let Some(mask) = options.mask {
+            if mask > 7 {
+                return Err(format!("Mask pattern {} must be between 0 and 7", mask));
+            }
+        }
+
+        let version = determine_optimal_qr_code_version(
+            &encoding_mode,
+            &error_correction_level,
+            data.len() as i32,
+            options.min_version,
+            options.max_version,
+        )?;
+
+        self.encoded_data.clear();
+        encode_into(data, &encoding_mode, &mut self.encoded_data)?;
+
+        let data_bits_required_for_version =
+            determine_data_bits_required_for_version(&version, &error_correction_level)?;
+
+        self.data_bits.clear();
+        self.data_bits.append_bits(encoding_mode.indicator(), 4);
+        self.data_bits.append_bits(
+            data.len() as u32,
+            calculate_binary_indicator_bit_length(&encoding_mode, &version),
+        );
+        self.data_bits.append_buffer(&self.encoded_data);
+        pad_data(&mut self.data_bits, data_bits_required_for_version)?;
+
+        self.code_words.clear();
+        self.error_corrector.generate_into(
+            &self.data_bits,
+            &error_correction_level,
+            &version,
+            &mut self.code_words,
+        )?;
+
+        let mask = self.matrix_builder.build_into(
+            &mut self.matrix,
+            &self.code_words,
+            &error_correction_level,
+            &version,
+            options.mask,
+        );
+
+        Ok((version, mask))
+    }
+}
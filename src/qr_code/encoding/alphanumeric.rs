@@ -1,5 +1,4 @@
-use crate::qr_code::utils::left_pad;
-use std::collections::HashMap;
+use crate::qr_code::BitSink;
 
 /**
 * HELLO WORLD
@@ -13,7 +12,7 @@ use std::collections::HashMap;
 * HE, LL, O , WO, RL, D
 *
 * Step 2:
-* for each pair of characters get the value of first character from the ALPHANUMERIC_VALUES and
+* for each pair of characters get the value of first character from the alphanumeric table and
 * multiply it by 45. Then add that number to the number representation of the second character.
 *
 * H -> 17
@@ -30,98 +29,52 @@ use std::collections::HashMap;
 * if encoding an odd number of characters, as we are here, take the number representation of the
 * final character and convert it into a 6-bit binary string.
 */
-pub fn alphanumeric_encoding(data: &str) -> Result<String, String> {
-    let alphanumeric_values: HashMap<&str, i32> = [
-        ("0", 0),
-        ("1", 1),
-        ("2", 2),
-        ("3", 3),
-        ("4", 4),
-        ("5", 5),
-        ("6", 6),
-        ("7", 7),
-        ("8", 8),
-        ("9", 9),
-        ("A", 10),
-        ("B", 11),
-        ("C", 12),
-        ("D", 13),
-        ("E", 14),
-        ("F", 15),
-        ("G", 16),
-        ("H", 17),
-        ("I", 18),
-        ("J", 19),
-        ("K", 20),
-        ("L", 21),
-        ("M", 22),
-        ("N", 23),
-        ("O", 24),
-        ("P", 25),
-        ("Q", 26),
-        ("R", 27),
-        ("S", 28),
-        ("T", 29),
-        ("U", 30),
-        ("V", 31),
-        ("W", 32),
-        ("X", 33),
-        ("Y", 34),
-        ("Z", 35),
-        (" ", 36),
-        ("$", 37),
-        ("%", 38),
-        ("*", 39),
-        ("+", 40),
-        ("-", 41),
-        (".", 42),
-        ("/", 43),
-        (":", 44),
-    ]
-    .iter()
-    .cloned()
-    .collect();
+pub fn alphanumeric_encoding(data: &str, bits: &mut impl BitSink) -> Result<(), char> {
+    let value = |character: char| alphanumeric_value(character).ok_or(character);
 
-    let grouped_data = data
-        .chars()
-        .collect::<Vec<char>>()
-        .chunks(2)
-        .map(|c| c.iter().collect::<String>())
-        .collect::<Vec<String>>();
+    let mut characters = data.chars();
 
-    let mut bits: String = String::new();
+    while let Some(first) = characters.next() {
+        match characters.next() {
+            Some(second) => {
+                let total = value(first)? * 45 + value(second)?;
 
-    for group in grouped_data {
-        let first_letter = group.chars().nth(0).unwrap().to_string();
-
-        let first_number = match alphanumeric_values.get(&first_letter.as_str()) {
-            Some(number) => number * 45,
-            None => return Err(format!("Failed to find alphanumeric character \"{}\" in alphanumeric_values, consider using a different encoding mode", first_letter)),
-        };
-
-        let second_letter = group.chars().nth(1);
-
-        match second_letter {
-            Some(second_letter) => {
-                let second_number = match alphanumeric_values.get(&second_letter.to_string().as_str()) {
-                    Some(number) => number.to_owned(),
-                    None => return Err(format!("Failed to find alphanumeric character \"{}\" in alphanumeric_values, consider using a different encoding mode", second_letter)),
-                };
+                bits.append_bits(total, 11);
+            }
+            None => bits.append_bits(value(first)?, 6),
+        }
+    }
 
-                let total = first_number + second_number;
+    Ok(())
+}
 
-                let value = left_pad(&format!("{:b}", total), 11, "0");
+/**
+ * The value of a character in the alphanumeric table: digits are 0-9, uppercase letters 10-35, then
+ * space $ % * + - . / : are 36-44
+ */
+pub fn alphanumeric_value(character: char) -> Option<u32> {
+    match character {
+        '0'..='9' => Some(character as u32 - '0' as u32),
+        'A'..='Z' => Some(character as u32 - 'A' as u32 + 10),
+        ' ' => Some(36),
+        '$' => Some(37),
+        '%' => Some(38),
+        '*' => Some(39),
+        '+' => Some(40),
+        '-' => Some(41),
+        '.' => Some(42),
+        '/' => Some(43),
+        ':' => Some(44),
+        _ => None,
+    }
+}
 
-                bits = bits + &value;
-            }
-            None => {
-                // triggers on the last group when encoding an odd number of characters
-                let value = left_pad(&format!("{:b}", first_number / 45), 6, "0");
+#[test]
+fn test_alphanumeric_value() {
+    let table = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
 
-                bits = bits + &value;
-            }
-        }
+    for (value, character) in table.chars().enumerate() {
+        assert_eq!(alphanumeric_value(character), Some(value as u32));
     }
-
-    Ok(bits)
+    assert_eq!(alphanumeric_value('a'), None);
 }
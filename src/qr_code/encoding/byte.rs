@@ -1,4 +1,4 @@
-use crate::qr_code::utils::convert_hex_to_binary;
+use crate::qr_code::BitSink;
 
 /**
 * Hello, World!
@@ -8,23 +8,11 @@ use crate::qr_code::utils::convert_hex_to_binary;
 * ISO 8859-1 is preferred
 *
 * Step 2:
-* convert each character in the string into hex bytes
+* take each byte of the string
 *
 * Step 3:
-* convert each byte into an 8-bit binary string, pad to the left with zeros if neeeded
+* append each byte as an 8-bit binary value
 */
-pub fn byte_encoding(data: &str) -> Result<String, String> {
-    let mut bits: String = String::new();
-
-    for char in data.chars() {
-        let string = char.to_string();
-
-        let byte = format!("{:02X?}", string.as_bytes());
-
-        let binary_bits = convert_hex_to_binary(&byte);
-
-        bits = bits + &binary_bits;
-    }
-
-    Ok(bits)
+pub fn byte_encoding(data: &str, bits: &mut impl BitSink) {
+    bits.append_bytes(data.as_bytes());
 }
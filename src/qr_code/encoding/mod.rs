@@ -5,12 +5,44 @@ mod numeric;
 use crate::qr_code::encoding::alphanumeric::alphanumeric_encoding;
 use crate::qr_code::encoding::byte::byte_encoding;
 use crate::qr_code::encoding::numeric::numeric_encoding;
-use crate::qr_code::EncodingMode;
+#[cfg(feature = "alloc")]
+use crate::qr_code::BitBuffer;
+use crate::qr_code::{BitSink, EncodingMode};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
 
-pub fn encode(data: &str, mode: &EncodingMode) -> Result<String, String> {
+#[cfg(feature = "alloc")]
+pub fn encode(data: &str, mode: &EncodingMode) -> Result<BitBuffer, String> {
+    let mut bits = BitBuffer::new();
+    encode_into(data, mode, &mut bits)?;
+
+    Ok(bits)
+}
+
+/**
+ * Appends the encoded data to the end of `bits`, which may hold part of the data if it fails
+ */
+#[cfg(feature = "alloc")]
+pub fn encode_into(data: &str, mode: &EncodingMode, bits: &mut BitBuffer) -> Result<(), String> {
+    encode_bits(data, mode, bits).map_err(|invalid| match mode {
+        EncodingMode::Numeric => format!(
+            "Failed to encode \"{}\" as a digit, consider using a different encoding mode",
+            invalid
+        ),
+        _ => format!("Failed to find alphanumeric character \"{}\" in the alphanumeric table, consider using a different encoding mode", invalid),
+    })
+}
+
+/**
+ * Appends the encoded data to any bit sink, returning the first character the mode cannot encode
+ */
+pub fn encode_bits(data: &str, mode: &EncodingMode, bits: &mut impl BitSink) -> Result<(), char> {
     match mode {
-        EncodingMode::Numeric => numeric_encoding(data),
-        EncodingMode::Alphanumeric => alphanumeric_encoding(data),
-        EncodingMode::Byte => byte_encoding(data),
+        EncodingMode::Numeric => numeric_encoding(data, bits),
+        EncodingMode::Alphanumeric => alphanumeric_encoding(data, bits),
+        EncodingMode::Byte => {
+            byte_encoding(data, bits);
+            Ok(())
+        }
     }
 }
@@ -1,3 +1,5 @@
+use crate::qr_code::BitSink;
+
 /**
 * 8675309
 *
@@ -8,35 +10,34 @@
 * 867 530 9
 *
 * Step 2:
-* treat each group as a three digit number (or fewer than 3 if the final group is 1 or 2  digits long)
-* if a group starts with 0, it should be interpreted as a 2 digit number and should be converted to 7 binary bits
-* if there are 2 zeros at the start of a group it should be interpreted as a 1 digit number and converted in to 4 binary bits
-* conditions above also apply to to the last group if it is 1 or 2 digits long
+* treat each group as a number and convert it into binary, the width depends on the number of digits
+* in the group rather than its value, so leading zeros are kept
+*   - 3 digits -> 10 bits
+*   - 2 digits -> 7 bits
+*   - 1 digit -> 4 bits
 *
 * 867 -> 1101100011
 * 530 -> 1000010010
 * 9 -> 1001
 */
-pub fn numeric_encoding(data: &str) -> Result<String, String> {
-    let grouped_data = data
-        .chars()
-        .collect::<Vec<char>>()
-        .chunks(3)
-        .map(|c| c.iter().collect::<String>())
-        .collect::<Vec<String>>();
+pub fn numeric_encoding(data: &str, bits: &mut impl BitSink) -> Result<(), char> {
+    if let Some(invalid) = data.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(invalid);
+    }
 
-    let mut bits: String = String::new();
+    for group in data.as_bytes().chunks(3) {
+        let number = group
+            .iter()
+            .fold(0, |number, digit| number * 10 + (digit - b'0') as u32);
 
-    for group in grouped_data {
-        let number = match group.parse::<i32>() {
-            Ok(number) => number,
-            Err(err) => return Err(err.to_string()),
+        let width = match group.len() {
+            3 => 10,
+            2 => 7,
+            _ => 4,
         };
 
-        let value = format!("{:b}", number);
-
-        bits = bits + &value
+        bits.append_bits(number, width);
     }
 
-    Ok(bits)
+    Ok(())
 }
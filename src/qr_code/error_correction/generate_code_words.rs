@@ -1,18 +1,6 @@
 use crate::qr_code::{ErrorCorrectionLevel, Version};
-
-pub struct ErrorCorrectionGroups {
-    pub group_one: Vec<Vec<String>>,
-    pub group_two: Vec<Vec<String>>,
-}
-
-impl ErrorCorrectionGroups {
-    pub fn new() -> ErrorCorrectionGroups {
-        ErrorCorrectionGroups {
-            group_one: Vec::new(),
-            group_two: Vec::new(),
-        }
-    }
-}
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
 
 pub struct GroupConfig {
     pub blocks_in_group_one: i32,
@@ -35,40 +23,78 @@ impl GroupConfig {
             code_words_in_group_two_blocks,
         }
     }
+
+    /**
+     * The number of data code words in each block, group one first
+     */
+    pub fn block_lengths(&self) -> impl Iterator<Item = usize> + Clone {
+        core::iter::repeat_n(
+            self.code_words_in_group_one_blocks as usize,
+            self.blocks_in_group_one as usize,
+        )
+        .chain(core::iter::repeat_n(
+            self.code_words_in_group_two_blocks as usize,
+            self.blocks_in_group_two as usize,
+        ))
+    }
 }
 
-pub fn generate_code_words(
-    data_bits: String,
+/**
+ * The number of error correction code words generated for every block, this is the same for both
+ * groups. None for versions outside 1 to 40.
+ *
+ * Based on https://www.thonky.com/qr-code-tutorial/error-correction-table
+ */
+pub fn get_error_correction_code_words_per_block(
     error_correction_level: &ErrorCorrectionLevel,
     version: &Version,
-) -> Result<ErrorCorrectionGroups, String> {
-    let byte_blocks = data_bits_to_byte_blocks(data_bits)?;
-    let error_correction_group_config = get_group_layout(&error_correction_level, &version);
-    let error_correction_groups = ErrorCorrectionGroups::new();
-
-    Ok(error_correction_groups)
-}
-
-fn data_bits_to_byte_blocks(data_bits: String) -> Result<Vec<String>, String> {
-    if data_bits.len() % 8 != 0 {
-        return Err("Failed to split bits into bytes".to_string());
-    }
+) -> Option<usize> {
+    let code_words_per_block: [usize; 40] = match error_correction_level {
+        ErrorCorrectionLevel::Low => [
+            7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28,
+            30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+        ],
+        ErrorCorrectionLevel::Medium => [
+            10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28,
+            28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+        ],
+        ErrorCorrectionLevel::Quartile => [
+            13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30,
+            30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+        ],
+        ErrorCorrectionLevel::High => [
+            17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24,
+            30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+        ],
+    };
 
-    let chunks: Vec<String> = (0..data_bits.len())
-        .step_by(8)
-        .map(|i| data_bits[i..i + 8].to_string())
-        .collect();
+    let index = (version.version() as usize).checked_sub(1)?;
 
-    Ok(chunks)
+    code_words_per_block.get(index).copied()
 }
 
-fn get_group_layout(
+#[cfg(feature = "alloc")]
+pub fn get_group_layout(
     error_correction_level: &ErrorCorrectionLevel,
     version: &Version,
 ) -> Result<GroupConfig, String> {
     if version.version() > 40 || version.version() < 1 {
         return Err("Version must be between 1 and 40".to_string());
     }
+
+    group_layout(error_correction_level, version).ok_or_else(|| "Failed to find config".to_string())
+}
+
+/**
+ * The blocks of each group and the data code words in each of their blocks, none for versions
+ * outside 1 to 40
+ *
+ * Based on https://www.thonky.com/qr-code-tutorial/error-correction-table
+ */
+pub fn group_layout(
+    error_correction_level: &ErrorCorrectionLevel,
+    version: &Version,
+) -> Option<GroupConfig> {
     let group_configs = match error_correction_level {
         ErrorCorrectionLevel::Low => [
             (1, 19, 0, 0),
@@ -240,10 +266,43 @@ fn get_group_layout(
         ],
     };
 
-    let group_config = group_configs.get(version.version() as usize);
+    let index = (version.version() as usize).checked_sub(1)?;
 
-    match group_config {
-        Some(config) => return Ok(GroupConfig::new(config.0, config.1, config.2, config.3)),
-        None => return Err("Failed to find config".to_string()),
-    }
+    group_configs
+        .get(index)
+        .map(|config| GroupConfig::new(config.0, config.1, config.2, config.3))
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_get_group_layout() {
+    // each version reads its own row of the table, the first and last included
+    let layout = |level, version| {
+        let layout = get_group_layout(&level, &Version::Normal(version)).unwrap();
+        (
+            layout.blocks_in_group_one,
+            layout.code_words_in_group_one_blocks,
+            layout.blocks_in_group_two,
+            layout.code_words_in_group_two_blocks,
+        )
+    };
+
+    assert_eq!(layout(ErrorCorrectionLevel::Low, 1), (1, 19, 0, 0));
+    assert_eq!(layout(ErrorCorrectionLevel::Quartile, 5), (2, 15, 2, 16));
+    assert_eq!(layout(ErrorCorrectionLevel::High, 40), (20, 15, 61, 16));
+
+    assert!(get_group_layout(&ErrorCorrectionLevel::Low, &Version::Normal(0)).is_err());
+    assert!(get_group_layout(&ErrorCorrectionLevel::Low, &Version::Normal(41)).is_err());
+}
+
+#[test]
+fn test_get_error_correction_code_words_per_block() {
+    let code_words = |level, version| {
+        get_error_correction_code_words_per_block(&level, &Version::Normal(version))
+    };
+
+    assert_eq!(code_words(ErrorCorrectionLevel::Medium, 1), Some(10));
+    assert_eq!(code_words(ErrorCorrectionLevel::High, 40), Some(30));
+    assert_eq!(code_words(ErrorCorrectionLevel::Low, 0), None);
+    assert_eq!(code_words(ErrorCorrectionLevel::Low, 41), None);
 }
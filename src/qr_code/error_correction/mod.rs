@@ -1,14 +1,289 @@
 mod generate_code_words;
+mod reed_solomon;
 
-use generate_code_words::generate_code_words;
+#[cfg(feature = "alloc")]
+pub use generate_code_words::get_group_layout;
+pub use generate_code_words::{
+    get_error_correction_code_words_per_block, group_layout, GroupConfig,
+};
+#[cfg(feature = "alloc")]
+pub use reed_solomon::correct_errors;
+pub use reed_solomon::{ReedSolomon, MAX_DEGREE};
 
-use crate::qr_code::{ErrorCorrectionLevel, Version};
+#[cfg(feature = "alloc")]
+use crate::qr_code::{BitBuffer, ErrorCorrectionLevel, Version};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, string::ToString, vec::Vec};
 
+/**
+ * Generates the error correction code words and interleaves them with the data code words to
+ * produce the final message that is placed in the matrix.
+ */
+#[cfg(feature = "alloc")]
 pub fn generate_error_correction(
-    data_bits: String,
+    data_bits: &BitBuffer,
     error_correction_level: &ErrorCorrectionLevel,
     version: &Version,
-) -> Result<String, String> {
-    let code_words = generate_code_words(data_bits, &error_correction_level, &version)?;
-    Ok("this is fine".to_string())
+) -> Result<BitBuffer, String> {
+    let mut message = BitBuffer::new();
+    ErrorCorrector::new().generate_into(
+        data_bits,
+        error_correction_level,
+        version,
+        &mut message,
+    )?;
+
+    Ok(message)
+}
+
+/**
+ * Generates final messages like generate_error_correction, keeping the Reed-Solomon generator of
+ * every number of error correction code words it has seen and the buffer the message is written to
+ */
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub struct ErrorCorrector {
+    generators: Vec<Option<ReedSolomon>>,
+    message: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl ErrorCorrector {
+    pub fn new() -> ErrorCorrector {
+        ErrorCorrector::default()
+    }
+
+    /**
+     * Appends the final message for the data code words to `message`
+     */
+    pub fn generate_into(
+        &mut self,
+        data_bits: &BitBuffer,
+        error_correction_level: &ErrorCorrectionLevel,
+        version: &Version,
+        message: &mut BitBuffer,
+    ) -> Result<(), String> {
+        if !data_bits.len().is_multiple_of(8) {
+            return Err("Failed to split bits into bytes".to_string());
+        }
+
+        let layout = get_group_layout(error_correction_level, version)?;
+        let error_correction_code_words =
+            get_error_correction_code_words_per_block(error_correction_level, version)
+                .ok_or_else(|| "Version must be between 1 and 40".to_string())?;
+
+        let data = data_bits.as_bytes();
+        let expected_code_words: usize = layout.block_lengths().sum();
+        if data.len() != expected_code_words {
+            return Err(format!(
+                "Expected {} data code words but found {}",
+                expected_code_words,
+                data.len()
+            ));
+        }
+
+        if self.generators.len() <= error_correction_code_words {
+            self.generators
+                .resize_with(error_correction_code_words + 1, || None);
+        }
+        let reed_solomon = self.generators[error_correction_code_words]
+            .get_or_insert_with(|| ReedSolomon::new(error_correction_code_words));
+
+        self.message.clear();
+        self.message.resize(
+            data.len() + layout.block_lengths().count() * error_correction_code_words,
+            0,
+        );
+        write_final_message(data, &layout, reed_solomon, &mut self.message);
+        message.append_bytes(&self.message);
+
+        Ok(())
+    }
+}
+
+/**
+ * Writes the final message: the first data code word from each block, then the second, and so on;
+ * followed by the error correction code words in the same order. The message must be as long as
+ * the data and error correction code words together.
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/structure-final-message
+ */
+pub fn write_final_message(
+    data: &[u8],
+    layout: &GroupConfig,
+    reed_solomon: &ReedSolomon,
+    message: &mut [u8],
+) {
+    let error_correction_code_words = reed_solomon.degree();
+    let block_count = layout.block_lengths().count();
+    let longest_data_block = layout.block_lengths().max().unwrap_or(0);
+
+    let mut position = 0;
+    for i in 0..longest_data_block {
+        let mut start = 0;
+
+        for len in layout.block_lengths() {
+            if i < len {
+                message[position] = data[start + i];
+                position += 1;
+            }
+            start += len;
+        }
+    }
+
+    let mut start = 0;
+    for (block, len) in layout.block_lengths().enumerate() {
+        let mut error_correction = [0; MAX_DEGREE];
+        let error_correction = &mut error_correction[..error_correction_code_words];
+        reed_solomon.remainder_into(&data[start..start + len], error_correction);
+        start += len;
+
+        for (i, &code_word) in error_correction.iter().enumerate() {
+            message[data.len() + i * block_count + block] = code_word;
+        }
+    }
+}
+
+/**
+ * The block each code word of the final message comes from, in the order write_final_message
+ * writes them
+ */
+#[cfg(feature = "alloc")]
+pub fn code_word_blocks(
+    error_correction_level: &ErrorCorrectionLevel,
+    version: &Version,
+) -> Result<Vec<usize>, String> {
+    let layout = get_group_layout(error_correction_level, version)?;
+    let error_correction_code_words =
+        get_error_correction_code_words_per_block(error_correction_level, version)
+            .ok_or_else(|| "Version must be between 1 and 40".to_string())?;
+
+    let data_lens: Vec<usize> = layout.block_lengths().collect();
+
+    let longest_data_block = data_lens.iter().copied().max().unwrap_or(0);
+    let mut blocks = Vec::new();
+
+    for i in 0..longest_data_block {
+        for (block, &len) in data_lens.iter().enumerate() {
+            if i < len {
+                blocks.push(block);
+            }
+        }
+    }
+
+    for _ in 0..error_correction_code_words {
+        blocks.extend(0..data_lens.len());
+    }
+
+    Ok(blocks)
+}
+
+/**
+ * How many code words in each block can be wrong and still be corrected. Reed-Solomon corrects
+ * half as many errors as there are error correction code words, less the code words the smallest
+ * versions set aside to avoid decoding to the wrong message. None for versions outside 1 to 40.
+ *
+ * based on ISO/IEC 18004 table 9
+ */
+#[cfg(feature = "alloc")]
+pub fn correctable_code_words_per_block(
+    error_correction_level: &ErrorCorrectionLevel,
+    version: &Version,
+) -> Option<usize> {
+    let error_correction_code_words =
+        get_error_correction_code_words_per_block(error_correction_level, version)?;
+
+    let misdecode_protection = match (version.version(), error_correction_level) {
+        (1, ErrorCorrectionLevel::Low) => 3,
+        (1, ErrorCorrectionLevel::Medium) | (2, ErrorCorrectionLevel::Low) => 2,
+        (1, _) | (3, ErrorCorrectionLevel::Low) => 1,
+        _ => 0,
+    };
+
+    Some((error_correction_code_words - misdecode_protection) / 2)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_code_word_blocks() {
+    // 5-Q has two blocks of 15 data code words and two of 16, with 18 error correction code words
+    let blocks = code_word_blocks(&ErrorCorrectionLevel::Quartile, &Version::Normal(5)).unwrap();
+
+    assert_eq!(blocks.len(), 134);
+    assert_eq!(&blocks[..4], &[0, 1, 2, 3]);
+    assert_eq!(&blocks[60..64], &[2, 3, 0, 1]);
+    assert_eq!(blocks.iter().filter(|&&block| block == 3).count(), 16 + 18);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_correctable_code_words_per_block() {
+    assert_eq!(
+        correctable_code_words_per_block(&ErrorCorrectionLevel::Low, &Version::Normal(1)),
+        Some(2)
+    );
+    assert_eq!(
+        correctable_code_words_per_block(&ErrorCorrectionLevel::High, &Version::Normal(5)),
+        Some(11)
+    );
+
+    for version in [0, 41] {
+        assert_eq!(
+            correctable_code_words_per_block(&ErrorCorrectionLevel::Low, &Version::Normal(version)),
+            None
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_generate_error_correction_code_words() {
+    // HELLO WORLD as a 1-M code from https://www.thonky.com/qr-code-tutorial/error-correction-coding
+    let data: &[u8] = &[
+        32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+    ];
+
+    let message = generate_error_correction(
+        &BitBuffer::from(data),
+        &ErrorCorrectionLevel::Medium,
+        &Version::Normal(1),
+    );
+
+    assert!(message.is_ok(), "Failed to generate code words");
+
+    let message = message.unwrap();
+
+    assert_eq!(&message.as_bytes()[..16], data);
+    assert_eq!(
+        &message.as_bytes()[16..],
+        &[196, 35, 39, 119, 235, 215, 231, 226, 93, 23],
+        "Error correction code words did not match expected result"
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_generate_error_correction() {
+    // 5-Q splits 62 data code words into blocks of 15, 15, 16 and 16 with 18 error correction
+    // code words each
+    let data: Vec<u8> = (0..62).collect();
+    let version = Version::Normal(5);
+    let message = generate_error_correction(
+        &BitBuffer::from(data.as_slice()),
+        &ErrorCorrectionLevel::Quartile,
+        &version,
+    )
+    .unwrap();
+    let message = message.as_bytes();
+
+    assert_eq!(message.len(), 62 + 4 * 18);
+    assert_eq!(&message[..8], &[0, 15, 30, 46, 1, 16, 31, 47]);
+    assert_eq!(&message[56..62], &[14, 29, 44, 60, 45, 61]);
+
+    let reed_solomon = ReedSolomon::new(18);
+    let blocks = [&data[..15], &data[15..30], &data[30..46], &data[46..]];
+    for (block, block_data) in blocks.iter().enumerate() {
+        let error_correction: Vec<u8> = message[62 + block..].iter().step_by(4).copied().collect();
+        assert_eq!(error_correction, reed_solomon.remainder(block_data));
+    }
 }
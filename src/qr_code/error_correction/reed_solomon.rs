@@ -0,0 +1,313 @@
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+
+/**
+ * The most error correction code words any block has
+ */
+pub const MAX_DEGREE: usize = 30;
+
+/**
+ * Reed-Solomon error correction over GF(256) using the QR code polynomial x^8 + x^4 + x^3 + x^2 + 1
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/error-correction-coding
+ */
+pub struct GaloisField {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+impl GaloisField {
+    pub fn new() -> GaloisField {
+        let mut exp = [0; 512];
+        let mut log = [0; 256];
+
+        let mut value: u16 = 1;
+        for (i, entry) in exp.iter_mut().take(255).enumerate() {
+            *entry = value as u8;
+            log[value as usize] = i as u8;
+
+            value <<= 1;
+            if value > 255 {
+                value ^= 0x11D;
+            }
+        }
+
+        // duplicate the table so multiplication never has to reduce the exponent modulo 255
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+
+        GaloisField { exp, log }
+    }
+
+    pub fn multiply(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+
+        self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn divide(&self, a: u8, b: u8) -> u8 {
+        assert!(b != 0, "Division by zero in GF(256)");
+
+        if a == 0 {
+            return 0;
+        }
+
+        self.exp[self.log[a as usize] as usize + 255 - self.log[b as usize] as usize]
+    }
+
+    /**
+     * α raised to the power, α is 2
+     */
+    #[cfg(feature = "alloc")]
+    pub fn power(&self, exponent: usize) -> u8 {
+        self.exp[exponent % 255]
+    }
+
+    /**
+     * Evaluates a polynomial with its coefficients from the lowest power up
+     */
+    #[cfg(feature = "alloc")]
+    fn evaluate(&self, polynomial: &[u8], x: u8) -> u8 {
+        polynomial.iter().rev().fold(0, |result, &coefficient| {
+            self.multiply(result, x) ^ coefficient
+        })
+    }
+}
+
+pub struct ReedSolomon {
+    field: GaloisField,
+    generator: [u8; MAX_DEGREE],
+    degree: usize,
+}
+
+impl ReedSolomon {
+    /**
+     * Builds the generator polynomial (x - α^0)(x - α^1)...(x - α^(degree - 1)), the degree is at
+     * most 30
+     */
+    pub fn new(degree: usize) -> ReedSolomon {
+        assert!(
+            (1..=MAX_DEGREE).contains(&degree),
+            "A block has 1 to {} error correction code words",
+            MAX_DEGREE
+        );
+        let field = GaloisField::new();
+
+        // coefficients from the highest power down, the leading 1 is implied
+        let mut generator = [0; MAX_DEGREE];
+        generator[degree - 1] = 1;
+
+        let mut root = 1;
+        for _ in 0..degree {
+            for i in 0..degree {
+                generator[i] = field.multiply(generator[i], root);
+                if i + 1 < degree {
+                    generator[i] ^= generator[i + 1];
+                }
+            }
+            root = field.multiply(root, 2);
+        }
+
+        ReedSolomon {
+            field,
+            generator,
+            degree,
+        }
+    }
+
+    /**
+     * The number of error correction code words it generates
+     */
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /**
+     * Divides the message polynomial by the generator polynomial, the remainder is the error correction code words
+     */
+    #[cfg(all(test, feature = "alloc"))]
+    pub fn remainder(&self, data: &[u8]) -> Vec<u8> {
+        let mut remainder = vec![0; self.degree];
+        self.remainder_into(data, &mut remainder);
+
+        remainder
+    }
+
+    /**
+     * Writes the remainder into a slice as long as the degree of the generator polynomial
+     */
+    pub fn remainder_into(&self, data: &[u8], remainder: &mut [u8]) {
+        let degree = self.degree;
+        remainder.fill(0);
+
+        for byte in data {
+            let factor = byte ^ remainder[0];
+            remainder.copy_within(1.., 0);
+            remainder[degree - 1] = 0;
+
+            if factor == 0 {
+                continue;
+            }
+
+            // multiplying by the factor is adding its logarithm
+            let factor_log = self.field.log[factor as usize] as usize;
+
+            for (coefficient, &generator) in remainder.iter_mut().zip(&self.generator[..degree]) {
+                if generator != 0 {
+                    *coefficient ^=
+                        self.field.exp[self.field.log[generator as usize] as usize + factor_log];
+                }
+            }
+        }
+    }
+}
+
+/**
+ * Corrects up to `max_errors` wrong code words in a block of data followed by its error correction
+ * code words, returning how many were corrected.
+ *
+ * The syndromes are the block evaluated at the roots of the generator polynomial, the
+ * Berlekamp-Massey algorithm finds the error locator polynomial from them, a Chien search finds its
+ * roots (the positions of the errors) and the Forney algorithm the value of each error.
+ *
+ * based on https://en.wikiversity.org/wiki/Reed%E2%80%93Solomon_codes_for_coders
+ */
+#[cfg(feature = "alloc")]
+pub fn correct_errors(
+    block: &mut [u8],
+    error_correction_code_words: usize,
+    max_errors: usize,
+) -> Result<usize, String> {
+    let field = GaloisField::new();
+    let length = block.len();
+
+    // the block is a polynomial with its first code word as the highest power
+    let mut ascending = block.to_vec();
+    ascending.reverse();
+
+    let syndromes: Vec<u8> = (0..error_correction_code_words)
+        .map(|i| field.evaluate(&ascending, field.power(i)))
+        .collect();
+
+    if syndromes.iter().all(|&syndrome| syndrome == 0) {
+        return Ok(0);
+    }
+
+    // Berlekamp-Massey, both polynomials from the lowest power up
+    let mut locator = vec![1u8];
+    let mut previous = vec![1u8];
+    let mut errors = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = 1u8;
+
+    for n in 0..error_correction_code_words {
+        let discrepancy = (1..=errors.min(locator.len() - 1)).fold(syndromes[n], |sum, i| {
+            sum ^ field.multiply(locator[i], syndromes[n - i])
+        });
+
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let scale = field.divide(discrepancy, previous_discrepancy);
+        let mut next = locator.clone();
+        next.resize(next.len().max(previous.len() + shift), 0);
+        for (i, &coefficient) in previous.iter().enumerate() {
+            next[i + shift] ^= field.multiply(scale, coefficient);
+        }
+
+        if 2 * errors <= n {
+            previous = core::mem::replace(&mut locator, next);
+            errors = n + 1 - errors;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            locator = next;
+            shift += 1;
+        }
+    }
+
+    if errors > max_errors {
+        return Err(format!(
+            "{} code words of a block are wrong, at most {} can be corrected",
+            errors, max_errors
+        ));
+    }
+
+    // the error evaluator polynomial, the syndromes times the locator up to x^(code words)
+    let mut evaluator = vec![0u8; error_correction_code_words];
+    for (i, &syndrome) in syndromes.iter().enumerate() {
+        for (j, &coefficient) in locator.iter().enumerate() {
+            if i + j < error_correction_code_words {
+                evaluator[i + j] ^= field.multiply(syndrome, coefficient);
+            }
+        }
+    }
+
+    // the formal derivative keeps the odd powers, lowered by one
+    let derivative: Vec<u8> = locator
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &coefficient)| if i % 2 == 1 { coefficient } else { 0 })
+        .collect();
+
+    let mut corrected = 0;
+
+    for power in 0..length {
+        let inverse = field.power(255 - power % 255);
+
+        if field.evaluate(&locator, inverse) != 0 {
+            continue;
+        }
+
+        let denominator = field.evaluate(&derivative, inverse);
+        if denominator == 0 {
+            return Err("The errors in a block cannot be located".to_string());
+        }
+
+        let magnitude = field.multiply(
+            field.power(power),
+            field.divide(field.evaluate(&evaluator, inverse), denominator),
+        );
+        block[length - 1 - power] ^= magnitude;
+        corrected += 1;
+    }
+
+    if corrected != errors {
+        return Err("The errors in a block cannot be located".to_string());
+    }
+
+    Ok(corrected)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_correct_errors() {
+    // HELLO WORLD as 1-M, 16 data code words and 10 error correction code words
+    let data = [
+        32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+    ];
+    let mut block = data.to_vec();
+    block.extend(ReedSolomon::new(10).remainder(&data));
+    let original = block.clone();
+
+    assert_eq!(correct_errors(&mut block, 10, 4), Ok(0));
+
+    block[0] ^= 0xFF;
+    block[7] = 0;
+    block[20] ^= 1;
+    block[25] ^= 0x80;
+    assert_eq!(correct_errors(&mut block, 10, 4), Ok(4));
+    assert_eq!(block, original);
+
+    for i in [1, 3, 5, 9, 11] {
+        block[i] ^= 0x55;
+    }
+    assert!(correct_errors(&mut block, 10, 4).is_err());
+}
@@ -0,0 +1,322 @@
+use core::fmt;
+
+use crate::qr_code::character_count::calculate_binary_indicator_bit_length;
+use crate::qr_code::encoding::encode_bits;
+use crate::qr_code::error_correction::{
+    get_error_correction_code_words_per_block, group_layout, write_final_message, ReedSolomon,
+};
+use crate::qr_code::matrix::{
+    apply_mask, draw_format_information, draw_version, for_each_data_module, mask_applies,
+    penalty_of, Modules,
+};
+use crate::qr_code::version::{data_code_words, smallest_version};
+use crate::qr_code::{
+    pad_bits, BitSink, EncodingMode, ErrorCorrectionLevel, QrCodeOptions, Version,
+};
+
+/**
+ * The number of bytes a `FixedQrCode` needs for each of its buffers to hold every version up to
+ * `max_version`, one bit per module
+ */
+pub const fn fixed_buffer_len(max_version: usize) -> usize {
+    let size = max_version * 4 + 17;
+    (size * size).div_ceil(8)
+}
+
+/**
+ * Why a `FixedQrCode` could not be created, the same failures `QrCode` reports as messages
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixedError {
+    /// the first character the encoding mode cannot encode
+    InvalidCharacter(char),
+    /// no version in the range, and within the capacity of the buffers, holds the data
+    DataTooLong,
+    InvalidVersionRange(i16, i16),
+    InvalidMask(u8),
+    /// clearing a logo area needs the list of blocks each code word belongs to, which allocates
+    KnockoutNotSupported,
+}
+
+impl fmt::Display for FixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixedError::InvalidCharacter(character) => write!(
+                f,
+                "Failed to encode \"{}\", consider using a different encoding mode",
+                character
+            ),
+            FixedError::DataTooLong => write!(f, "Data is too large, no version found"),
+            FixedError::InvalidVersionRange(min_version, max_version) => write!(
+                f,
+                "Version range {} to {} must be within 1 to 40",
+                min_version, max_version
+            ),
+            FixedError::InvalidMask(mask) => {
+                write!(f, "Mask pattern {} must be between 0 and 7", mask)
+            }
+            FixedError::KnockoutNotSupported => {
+                write!(f, "A logo area needs the alloc feature")
+            }
+        }
+    }
+}
+
+/**
+ * A QR code that never allocates, for targets without a heap. Its modules are packed into two
+ * buffers of `N` bytes, so it holds every version up to the largest one `fixed_buffer_len` fits in
+ * `N`. Creating one also needs `N` bytes of stack for the final message.
+ *
+ * ```
+ * use qr_code::qr_code::{fixed_buffer_len, EncodingMode, ErrorCorrectionLevel, FixedQrCode};
+ *
+ * const LEN: usize = fixed_buffer_len(5);
+ *
+ * let qr_code = FixedQrCode::<LEN>::create("HELLO WORLD", EncodingMode::Alphanumeric, ErrorCorrectionLevel::Quartile)
+ *     .unwrap();
+ * assert_eq!(qr_code.width(), 21);
+ * ```
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixedQrCode<const N: usize> {
+    pub encoding_mode: EncodingMode,
+    pub error_correction_level: ErrorCorrectionLevel,
+    pub version: Version,
+    pub mask: u8,
+    size: usize,
+    modules: [u8; N],
+    function_modules: [u8; N],
+}
+
+impl<const N: usize> FixedQrCode<N> {
+    pub fn create(
+        data: &str,
+        encoding_mode: EncodingMode,
+        error_correction_level: ErrorCorrectionLevel,
+    ) -> Result<FixedQrCode<N>, FixedError> {
+        FixedQrCode::create_with_options(
+            data,
+            encoding_mode,
+            error_correction_level,
+            &QrCodeOptions::default(),
+        )
+    }
+
+    /**
+     * Creates the same QR code `QrCode::create_with_options` does, a logo area is not supported
+     */
+    pub fn create_with_options(
+        data: &str,
+        encoding_mode: EncodingMode,
+        error_correction_level: ErrorCorrectionLevel,
+        options: &QrCodeOptions,
+    ) -> Result<FixedQrCode<N>, FixedError> {
+        if options.knockout.is_some() {
+            return Err(FixedError::KnockoutNotSupported);
+        }
+        if let Some(mask) = options.mask {
+            if mask > 7 {
+                return Err(FixedError::InvalidMask(mask));
+            }
+        }
+
+        let (min_version, max_version) = (options.min_version, options.max_version);
+        if min_version < 1 || max_version > 40 || min_version > max_version {
+            return Err(FixedError::InvalidVersionRange(min_version, max_version));
+        }
+
+        let max_version = (1..=max_version)
+            .rev()
+            .find(|&version| fixed_buffer_len(version as usize) <= N)
+            .ok_or(FixedError::DataTooLong)?;
+        if min_version > max_version {
+            return Err(FixedError::DataTooLong);
+        }
+
+        let version = smallest_version(
+            &encoding_mode,
+            &error_correction_level,
+            data.len() as i32,
+            min_version,
+            max_version,
+        )
+        .ok_or(FixedError::DataTooLong)?;
+
+        let mut qr_code = FixedQrCode {
+            encoding_mode,
+            error_correction_level,
+            version,
+            mask: 0,
+            size: version.size() as usize,
+            modules: [0; N],
+            function_modules: [0; N],
+        };
+
+        // the data code words are built in the module buffer, which is cleared once the final
+        // message is written
+        let data_len = data_code_words(&version, &error_correction_level).unwrap_or(0);
+        let mut bits = BitSlice {
+            bytes: &mut qr_code.modules[..data_len],
+            len: 0,
+        };
+        bits.append_bits(encoding_mode.indicator(), 4);
+        bits.append_bits(
+            data.len() as u32,
+            calculate_binary_indicator_bit_length(&encoding_mode, &version),
+        );
+        encode_bits(data, &encoding_mode, &mut bits).map_err(FixedError::InvalidCharacter)?;
+        pad_bits(&mut bits, data_len * 8);
+
+        let layout =
+            group_layout(&error_correction_level, &version).ok_or(FixedError::DataTooLong)?;
+        let error_correction_code_words =
+            get_error_correction_code_words_per_block(&error_correction_level, &version)
+                .ok_or(FixedError::DataTooLong)?;
+        let reed_solomon = ReedSolomon::new(error_correction_code_words);
+        let message_len = data_len + layout.block_lengths().count() * reed_solomon.degree();
+        let mut message = [0; N];
+        write_final_message(
+            &qr_code.modules[..data_len],
+            &layout,
+            &reed_solomon,
+            &mut message[..message_len],
+        );
+
+        qr_code.modules = [0; N];
+        draw_version(&mut qr_code, &version);
+        qr_code.place_message(&message[..message_len]);
+
+        let mask = match options.mask {
+            Some(mask) => mask,
+            None => qr_code.lowest_penalty_mask(),
+        };
+
+        apply_mask(&mut qr_code, mask);
+        draw_format_information(&mut qr_code, &error_correction_level, mask);
+        qr_code.mask = mask;
+
+        Ok(qr_code)
+    }
+
+    /**
+     * The number of modules along each side, without a quiet zone
+     */
+    pub fn width(&self) -> usize {
+        self.size
+    }
+
+    /**
+     * Whether the module at column x and row y is dark
+     */
+    pub fn get_module(&self, x: usize, y: usize) -> bool {
+        self.get(x, y)
+    }
+
+    /**
+     * Whether the module at column x and row y belongs to a function pattern or the format or
+     * version information
+     */
+    pub fn is_function_module(&self, x: usize, y: usize) -> bool {
+        self.is_function(x, y)
+    }
+
+    /**
+     * Places the message in the data modules, the remainder bits after it are left light
+     */
+    fn place_message(&mut self, message: &[u8]) {
+        let size = self.size;
+        let function_modules = &self.function_modules;
+        let modules = &mut self.modules;
+        let mut bit = 0;
+
+        for_each_data_module(
+            size,
+            |x, y| get_bit(function_modules, y * size + x),
+            |x, y| {
+                if let Some(&code_word) = message.get(bit / 8) {
+                    set_bit(modules, y * size + x, (code_word >> (7 - bit % 8)) & 1 == 1);
+                }
+                bit += 1;
+            },
+        );
+    }
+
+    /**
+     * Scores each mask pattern with its format information drawn, the mask is applied while the
+     * modules are read instead of to a copy
+     */
+    fn lowest_penalty_mask(&mut self) -> u8 {
+        let error_correction_level = self.error_correction_level;
+        let mut lowest = (0, i32::MAX);
+
+        for mask in 0..8 {
+            draw_format_information(self, &error_correction_level, mask);
+
+            let penalty = penalty_of(self.size, |x, y| {
+                self.get(x, y) ^ (!self.is_function(x, y) && mask_applies(mask, x, y))
+            });
+            if penalty < lowest.1 {
+                lowest = (mask, penalty);
+            }
+        }
+
+        lowest.0
+    }
+}
+
+impl<const N: usize> Modules for FixedQrCode<N> {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        get_bit(&self.modules, y * self.size + x)
+    }
+
+    fn is_function(&self, x: usize, y: usize) -> bool {
+        get_bit(&self.function_modules, y * self.size + x)
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        set_bit(&mut self.modules, y * self.size + x, dark);
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.set(x, y, dark);
+        set_bit(&mut self.function_modules, y * self.size + x, true);
+    }
+}
+
+fn get_bit(bytes: &[u8], index: usize) -> bool {
+    (bytes[index / 8] >> (7 - index % 8)) & 1 == 1
+}
+
+fn set_bit(bytes: &mut [u8], index: usize, value: bool) {
+    let mask = 1 << (7 - index % 8);
+    if value {
+        bytes[index / 8] |= mask;
+    } else {
+        bytes[index / 8] &= !mask;
+    }
+}
+
+/**
+ * Appends bits to a zeroed slice, which must be long enough for all of them
+ */
+struct BitSlice<'a> {
+    bytes: &'a mut [u8],
+    len: usize,
+}
+
+impl BitSink for BitSlice<'_> {
+    fn append_bits(&mut self, value: u32, len: u8) {
+        for i in (0..len).rev() {
+            set_bit(self.bytes, self.len, (value >> i) & 1 == 1);
+            self.len += 1;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(feature = "alloc")]
+use crate::qr_code::error_correction::code_word_blocks;
+#[cfg(feature = "alloc")]
+use crate::qr_code::matrix::{data_modules, Matrix};
+#[cfg(feature = "alloc")]
+use crate::qr_code::{ErrorCorrectionLevel, Version};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, string::ToString, vec};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KnockoutShape {
+    Rectangle,
+    /// an ellipse when the width and height differ
+    Circle,
+}
+
+/**
+ * An area in the middle of the symbol where the data modules are cleared to make room for a logo.
+ *
+ * The cleared modules are read as errors by scanners, so the area can only be as large as the error
+ * correction level can recover. Alignment patterns inside the area are kept and drawn on top of the
+ * logo, and the area can never reach the finder, timing or format information modules.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Knockout {
+    /// width of the area in modules
+    pub width: usize,
+    /// height of the area in modules
+    pub height: usize,
+    pub shape: KnockoutShape,
+}
+
+impl Knockout {
+    pub fn new(width: usize, height: usize) -> Knockout {
+        Knockout {
+            width,
+            height,
+            shape: KnockoutShape::Rectangle,
+        }
+    }
+
+    pub fn shape(mut self, shape: KnockoutShape) -> Knockout {
+        self.shape = shape;
+        self
+    }
+
+    /**
+     * The left, top, width and height of the area in modules, centred on a symbol with `size`
+     * modules on each side
+     */
+    pub fn bounds(&self, size: usize) -> (f64, f64, f64, f64) {
+        let width = self.width as f64;
+        let height = self.height as f64;
+
+        (
+            (size as f64 - width) / 2.0,
+            (size as f64 - height) / 2.0,
+            width,
+            height,
+        )
+    }
+
+    /**
+     * Whether any part of the module at column x and row y is inside the area
+     */
+    pub fn covers(&self, size: usize, x: usize, y: usize) -> bool {
+        let center = size as f64 / 2.0;
+        let radius_x = self.width as f64 / 2.0;
+        let radius_y = self.height as f64 / 2.0;
+
+        // the point of the module closest to the centre
+        let dx = (center.clamp(x as f64, x as f64 + 1.0) - center).abs();
+        let dy = (center.clamp(y as f64, y as f64 + 1.0) - center).abs();
+
+        match self.shape {
+            KnockoutShape::Rectangle => dx < radius_x && dy < radius_y,
+            KnockoutShape::Circle => {
+                let (x, y) = (dx / radius_x, dy / radius_y);
+                x * x + y * y < 1.0
+            }
+        }
+    }
+}
+
+/**
+ * Checks the area fits in the symbol and counts the code words it covers in the block that loses
+ * the most, the whole code word is lost when any of its modules is covered
+ */
+#[cfg(feature = "alloc")]
+pub fn obscured_code_words_per_block(
+    matrix: &Matrix,
+    knockout: &Knockout,
+    error_correction_level: &ErrorCorrectionLevel,
+    version: &Version,
+) -> Result<usize, String> {
+    let size = matrix.size();
+    let (left, top, width, height) = knockout.bounds(size);
+
+    if knockout.width == 0 || knockout.height == 0 {
+        return Err("The logo area must be at least 1 module wide and high".to_string());
+    }
+
+    // the finder patterns, separators, timing patterns and format and version information all
+    // lie within 9 modules of the edges
+    let limit = size as f64 - 8.0;
+    if left < 9.0 || top < 9.0 || left + width > limit || top + height > limit {
+        return Err(format!(
+            "A {}x{} logo area would cover the finder patterns of a {} code",
+            knockout.width, knockout.height, version
+        ));
+    }
+
+    let blocks = code_word_blocks(error_correction_level, version)?;
+    let mut obscured = vec![false; blocks.len()];
+
+    for (bit, (x, y)) in data_modules(matrix).into_iter().enumerate() {
+        if knockout.covers(size, x, y) {
+            // modules after the last code word are remainder bits
+            if let Some(code_word) = obscured.get_mut(bit / 8) {
+                *code_word = true;
+            }
+        }
+    }
+
+    let mut obscured_per_block = vec![0; blocks.iter().max().map_or(0, |last| last + 1)];
+
+    for (code_word, &block) in blocks.iter().enumerate() {
+        if obscured[code_word] {
+            obscured_per_block[block] += 1;
+        }
+    }
+
+    Ok(obscured_per_block.into_iter().max().unwrap_or(0))
+}
+
+/**
+ * Clears the data modules under the area, function modules are left as they are
+ */
+#[cfg(feature = "alloc")]
+pub fn clear_knockout(matrix: &mut Matrix, knockout: &Knockout) {
+    let size = matrix.size();
+
+    for (x, y) in data_modules(matrix) {
+        if knockout.covers(size, x, y) {
+            matrix.set(x, y, false);
+        }
+    }
+}
+
+/**
+ * The next error correction level up, which recovers more code words
+ */
+#[cfg(feature = "alloc")]
+pub fn raise_error_correction_level(
+    error_correction_level: &ErrorCorrectionLevel,
+) -> Option<ErrorCorrectionLevel> {
+    match error_correction_level {
+        ErrorCorrectionLevel::Low => Some(ErrorCorrectionLevel::Medium),
+        ErrorCorrectionLevel::Medium => Some(ErrorCorrectionLevel::Quartile),
+        ErrorCorrectionLevel::Quartile => Some(ErrorCorrectionLevel::High),
+        ErrorCorrectionLevel::High => None,
+    }
+}
+
+#[test]
+fn test_knockout_covers() {
+    let square = Knockout::new(3, 3);
+    let circle = Knockout::new(7, 7).shape(KnockoutShape::Circle);
+
+    // a version 1 symbol is 21 modules wide, the centre module is (10, 10)
+    assert!(square.covers(21, 9, 9));
+    assert!(square.covers(21, 11, 11));
+    assert!(!square.covers(21, 12, 10));
+
+    assert!(circle.covers(21, 7, 10));
+    assert!(!circle.covers(21, 7, 7));
+    assert!(Knockout::new(7, 7).covers(21, 7, 7));
+}
@@ -0,0 +1,179 @@
+use core::ops::Deref;
+
+use crate::qr_code::matrix::Modules;
+use crate::qr_code::Version;
+
+/**
+ * Draws the finder patterns, separators, timing patterns, alignment patterns and dark module, and
+ * reserves the format information area.
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/module-placement-matrix
+ */
+pub fn draw_function_patterns(matrix: &mut impl Modules, version: &Version) {
+    let size = matrix.size();
+
+    // timing patterns, drawn first so the finder patterns and separators overwrite their ends
+    for i in 0..size {
+        matrix.set_function(6, i, i % 2 == 0);
+        matrix.set_function(i, 6, i % 2 == 0);
+    }
+
+    draw_finder_pattern(matrix, 3, 3);
+    draw_finder_pattern(matrix, size - 4, 3);
+    draw_finder_pattern(matrix, 3, size - 4);
+
+    let positions = alignment_pattern_positions(version);
+    let last = positions.len().saturating_sub(1);
+
+    for (i, &x) in positions.iter().enumerate() {
+        for (j, &y) in positions.iter().enumerate() {
+            // skip the three corners occupied by finder patterns
+            let overlaps_finder = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+
+            if !overlaps_finder {
+                draw_alignment_pattern(matrix, x, y);
+            }
+        }
+    }
+
+    reserve_format_area(matrix);
+
+    // the dark module is always next to the bottom left separator
+    matrix.set_function(8, size - 8, true);
+}
+
+/**
+ * Draws a 7x7 finder pattern centered on (x, y) along with the light separator around it
+ */
+fn draw_finder_pattern(matrix: &mut impl Modules, x: usize, y: usize) {
+    let size = matrix.size() as isize;
+
+    for dy in -4..=4_isize {
+        for dx in -4..=4_isize {
+            let module_x = x as isize + dx;
+            let module_y = y as isize + dy;
+
+            if module_x < 0 || module_y < 0 || module_x >= size || module_y >= size {
+                continue;
+            }
+
+            // distance from the center decides which ring the module is in
+            let distance = dx.abs().max(dy.abs());
+
+            matrix.set_function(
+                module_x as usize,
+                module_y as usize,
+                distance != 2 && distance != 4,
+            );
+        }
+    }
+}
+
+/**
+ * Draws a 5x5 alignment pattern centered on (x, y)
+ */
+fn draw_alignment_pattern(matrix: &mut impl Modules, x: usize, y: usize) {
+    for dy in -2..=2_isize {
+        for dx in -2..=2_isize {
+            let distance = dx.abs().max(dy.abs());
+
+            matrix.set_function(
+                (x as isize + dx) as usize,
+                (y as isize + dy) as usize,
+                distance != 1,
+            );
+        }
+    }
+}
+
+/**
+ * Marks the modules around the finder patterns that will hold the format information
+ */
+fn reserve_format_area(matrix: &mut impl Modules) {
+    let size = matrix.size();
+
+    for i in 0..9 {
+        if i != 6 {
+            matrix.set_function(8, i, false);
+            matrix.set_function(i, 8, false);
+        }
+    }
+
+    for i in 0..8 {
+        matrix.set_function(size - 1 - i, 8, false);
+        matrix.set_function(8, size - 1 - i, false);
+    }
+}
+
+/**
+ * The row and column coordinates of the alignment pattern centers, every combination of two
+ * coordinates is a pattern unless it overlaps a finder pattern.
+ *
+ * Version 1 has no alignment patterns, the first coordinate is always 6 and the rest are evenly
+ * spaced back from the bottom right corner.
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/alignment-pattern-locations
+ */
+pub fn alignment_pattern_positions(version: &Version) -> AlignmentPositions {
+    let version = version.version() as usize;
+    let mut positions = AlignmentPositions {
+        positions: [6; 7],
+        len: 0,
+    };
+
+    if version == 1 {
+        return positions;
+    }
+
+    let count = version / 7 + 2;
+    let size = version * 4 + 17;
+
+    // version 32 is the only version where the spacing formula does not hold
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+
+    // the first is always 6, the rest count back from the last
+    for i in 1..count {
+        positions.positions[i] = size - 7 - (count - 1 - i) * step;
+    }
+    positions.len = count;
+
+    positions
+}
+
+/**
+ * Up to 7 alignment pattern coordinates, version 40 has the most
+ */
+pub struct AlignmentPositions {
+    positions: [usize; 7],
+    len: usize,
+}
+
+impl Deref for AlignmentPositions {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        &self.positions[..self.len]
+    }
+}
+
+#[test]
+fn test_alignment_pattern_positions() {
+    assert!(alignment_pattern_positions(&Version::Normal(1)).is_empty());
+    assert_eq!(*alignment_pattern_positions(&Version::Normal(2)), [6, 18]);
+    assert_eq!(
+        *alignment_pattern_positions(&Version::Normal(7)),
+        [6, 22, 38]
+    );
+    assert_eq!(
+        *alignment_pattern_positions(&Version::Normal(32)),
+        [6, 34, 60, 86, 112, 138]
+    );
+    assert_eq!(
+        *alignment_pattern_positions(&Version::Normal(40)),
+        [6, 30, 58, 86, 114, 142, 170]
+    );
+}
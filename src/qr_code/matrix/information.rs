@@ -0,0 +1,135 @@
+use crate::qr_code::matrix::Modules;
+use crate::qr_code::{ErrorCorrectionLevel, Version};
+#[cfg(all(test, feature = "alloc"))]
+use alloc::format;
+
+/**
+ * The 15 format information bits: 2 error correction level bits, 3 mask pattern bits and 10 error
+ * correction bits, XORed with 101010000010010 so the result is never all zeros.
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/format-version-information
+ */
+pub fn format_information_bits(error_correction_level: &ErrorCorrectionLevel, mask: u8) -> u32 {
+    let level_bits = match error_correction_level {
+        ErrorCorrectionLevel::Low => 0b01,
+        ErrorCorrectionLevel::Medium => 0b00,
+        ErrorCorrectionLevel::Quartile => 0b11,
+        ErrorCorrectionLevel::High => 0b10,
+    };
+
+    let data = (level_bits << 3) | mask as u32;
+
+    // remainder of dividing by the generator polynomial 10100110111
+    let mut remainder = data;
+    for _ in 0..10 {
+        remainder = (remainder << 1) ^ ((remainder >> 9) * 0b10100110111);
+    }
+
+    ((data << 10) | remainder) ^ 0b101010000010010
+}
+
+/**
+ * The 18 version information bits: 6 version bits followed by 12 error correction bits, only
+ * versions 7 and up include them.
+ */
+pub fn version_information_bits(version: &Version) -> u32 {
+    let data = version.version() as u32;
+
+    // remainder of dividing by the generator polynomial 1111100100101
+    let mut remainder = data;
+    for _ in 0..12 {
+        remainder = (remainder << 1) ^ ((remainder >> 11) * 0b1111100100101);
+    }
+
+    (data << 12) | remainder
+}
+
+/**
+ * Where both copies of the format information go, bit i of each copy at index i
+ */
+pub fn format_information_positions(size: usize) -> [[(usize, usize); 15]; 2] {
+    let mut first = [(0, 0); 15];
+    let mut second = [(0, 0); 15];
+
+    // first copy, around the top left finder pattern
+    for (i, position) in first.iter_mut().enumerate() {
+        *position = match i {
+            0..=5 => (8, i),
+            6 => (8, 7),
+            7 => (8, 8),
+            8 => (7, 8),
+            _ => (14 - i, 8),
+        };
+    }
+
+    // second copy, split between the top right and bottom left finder patterns
+    for (i, position) in second.iter_mut().enumerate() {
+        *position = if i < 8 {
+            (size - 1 - i, 8)
+        } else {
+            (8, size - 15 + i)
+        };
+    }
+
+    [first, second]
+}
+
+/**
+ * Draws both copies of the format information, bit 0 is the least significant bit
+ */
+pub fn draw_format_information(
+    matrix: &mut impl Modules,
+    error_correction_level: &ErrorCorrectionLevel,
+    mask: u8,
+) {
+    let bits = format_information_bits(error_correction_level, mask);
+
+    for copy in format_information_positions(matrix.size()) {
+        for (i, (x, y)) in copy.into_iter().enumerate() {
+            matrix.set_function(x, y, (bits >> i) & 1 == 1);
+        }
+    }
+}
+
+/**
+ * Draws both 6x3 copies of the version information next to the top right and bottom left finder patterns
+ */
+pub fn draw_version_information(matrix: &mut impl Modules, version: &Version) {
+    if version.version() < 7 {
+        return;
+    }
+
+    let bits = version_information_bits(version);
+    let size = matrix.size();
+
+    for i in 0..18 {
+        let dark = (bits >> i) & 1 == 1;
+        let a = size - 11 + i % 3;
+        let b = i / 3;
+
+        matrix.set_function(a, b, dark);
+        matrix.set_function(b, a, dark);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_format_information_bits() {
+    // L with mask pattern 4 from https://www.thonky.com/qr-code-tutorial/format-version-information
+    assert_eq!(
+        format!(
+            "{:015b}",
+            format_information_bits(&ErrorCorrectionLevel::Low, 4)
+        ),
+        "110011000101111"
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_version_information_bits() {
+    assert_eq!(
+        format!("{:018b}", version_information_bits(&Version::Normal(7))),
+        "000111110010010100"
+    );
+}
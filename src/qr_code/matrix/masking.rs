@@ -0,0 +1,468 @@
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::qr_code::matrix::Modules;
+#[cfg(feature = "alloc")]
+use crate::qr_code::matrix::{format_information_bits, format_information_positions, Matrix};
+#[cfg(feature = "alloc")]
+use crate::qr_code::ErrorCorrectionLevel;
+#[cfg(all(test, feature = "alloc"))]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/**
+ * Whether the mask pattern flips the module at column x and row y
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/mask-patterns
+ */
+pub fn mask_applies(mask: u8, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (y + x).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (y + x).is_multiple_of(3),
+        4 => (y / 2 + x / 3).is_multiple_of(2),
+        5 => (y * x) % 2 + (y * x) % 3 == 0,
+        6 => ((y * x) % 2 + (y * x) % 3).is_multiple_of(2),
+        7 => ((y + x) % 2 + (y * x) % 3).is_multiple_of(2),
+        _ => panic!("Mask pattern must be between 0 and 7"),
+    }
+}
+
+/**
+ * Where the mask pattern applies in the top left 6 columns and 12 rows, every mask pattern repeats
+ * after that
+ */
+fn mask_tile(mask: u8) -> [[bool; 6]; 12] {
+    let mut tile = [[false; 6]; 12];
+
+    for (y, row) in tile.iter_mut().enumerate() {
+        for (x, applies) in row.iter_mut().enumerate() {
+            *applies = mask_applies(mask, x, y);
+        }
+    }
+
+    tile
+}
+
+/**
+ * Flips every data module the mask pattern applies to, function modules are never masked
+ */
+pub fn apply_mask(matrix: &mut impl Modules, mask: u8) {
+    let size = matrix.size();
+    let tile = mask_tile(mask);
+
+    for y in 0..size {
+        let tile_row = &tile[y % 12];
+
+        for x in 0..size {
+            if tile_row[x % 6] && !matrix.is_function(x, y) {
+                let dark = matrix.get(x, y);
+                matrix.set(x, y, !dark);
+            }
+        }
+    }
+}
+
+/**
+ * The rows and columns score_masks packs the matrix into, kept between calls so scoring the masks of
+ * many codes does not allocate them again
+ */
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub struct MaskScratch {
+    unmasked: Lines,
+    function: Lines,
+    masked: Lines,
+    data_rows: Vec<Line>,
+    data_columns: Vec<Line>,
+}
+
+/**
+ * The penalty score of every mask pattern for a matrix with the message placed but no mask or format
+ * information, indexed by mask pattern.
+ *
+ * Each mask is applied to the rows and columns as bits instead of to a copy of the matrix, only the
+ * format information modules have to be set one by one.
+ */
+#[cfg(feature = "alloc")]
+pub fn score_masks(
+    matrix: &Matrix,
+    error_correction_level: &ErrorCorrectionLevel,
+    scratch: &mut MaskScratch,
+) -> [i32; 8] {
+    let size = matrix.size();
+    let MaskScratch {
+        unmasked,
+        function,
+        masked,
+        data_rows,
+        data_columns,
+    } = scratch;
+
+    unmasked.fill(size, &matrix.modules);
+    function.fill(size, &matrix.function_modules);
+    let format_positions = format_information_positions(size);
+
+    let all = Line::first(size);
+    data_rows.clear();
+    data_rows.extend(function.rows.iter().map(|&row| !row & all));
+    data_columns.clear();
+    data_columns.extend(function.columns.iter().map(|&column| !column & all));
+
+    let mut penalties = [0; 8];
+
+    for (mask, score) in penalties.iter_mut().enumerate() {
+        let mask = mask as u8;
+        let tile = mask_tile(mask);
+
+        let row_patterns: [Line; 12] =
+            core::array::from_fn(|y| Line::repeating(Line::new(6, |x| tile[y][x]).0[0], 6) & all);
+        let column_patterns: [Line; 6] =
+            core::array::from_fn(|x| Line::repeating(Line::new(12, |y| tile[y][x]).0[0], 12) & all);
+
+        masked.copy_from(unmasked);
+        for (y, row) in masked.rows.iter_mut().enumerate() {
+            *row = *row ^ (row_patterns[y % 12] & data_rows[y]);
+        }
+        for (x, column) in masked.columns.iter_mut().enumerate() {
+            *column = *column ^ (column_patterns[x % 6] & data_columns[x]);
+        }
+
+        let bits = format_information_bits(error_correction_level, mask);
+        for copy in format_positions {
+            for (i, (x, y)) in copy.into_iter().enumerate() {
+                masked.set(x, y, (bits >> i) & 1 == 1);
+            }
+        }
+
+        *score = penalty(
+            size,
+            masked.rows.iter().copied(),
+            masked.columns.iter().copied(),
+        );
+    }
+
+    penalties
+}
+
+/**
+ * The rows and columns of a matrix as bits, so the penalty rules score 64 modules at a time
+ */
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct Lines {
+    size: usize,
+    rows: Vec<Line>,
+    columns: Vec<Line>,
+}
+
+#[cfg(feature = "alloc")]
+impl Lines {
+    /**
+     * Packs modules stored row by row, the columns are filled from the set bits of each row
+     */
+    fn fill(&mut self, size: usize, modules: &[bool]) {
+        self.size = size;
+        self.rows.clear();
+        self.rows
+            .extend(modules.chunks(size).map(Line::from_modules));
+        self.columns.clear();
+        self.columns.resize(size, Line::default());
+
+        for (y, row) in self.rows.iter().enumerate() {
+            for (i, &word) in row.0.iter().enumerate() {
+                let mut word = word;
+
+                while word != 0 {
+                    let x = i * 64 + word.trailing_zeros() as usize;
+                    self.columns[x].0[y / 64] |= 1 << (y % 64);
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+
+    fn copy_from(&mut self, other: &Lines) {
+        self.size = other.size;
+        self.rows.clone_from(&other.rows);
+        self.columns.clone_from(&other.columns);
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        self.rows[y].set(x, dark);
+        self.columns[x].set(y, dark);
+    }
+}
+
+/**
+ * The penalty score of modules read one at a time, for modules that are not kept in a Matrix. The
+ * mask with the lowest score is used.
+ */
+pub fn penalty_of(size: usize, dark: impl Fn(usize, usize) -> bool) -> i32 {
+    penalty(
+        size,
+        (0..size).map(|y| Line::new(size, |x| dark(x, y))),
+        (0..size).map(|x| Line::new(size, |y| dark(x, y))),
+    )
+}
+
+/**
+ * Scores the rows and columns using the four penalty rules
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/data-masking
+ */
+fn penalty(
+    size: usize,
+    rows: impl Iterator<Item = Line>,
+    columns: impl Iterator<Item = Line>,
+) -> i32 {
+    let mut penalty = 0;
+    let mut dark_modules = 0;
+    let mut previous: Option<Line> = None;
+
+    for row in rows {
+        // rule 1: five or more modules of the same color in a row or column
+        // rule 3: patterns that look like finder patterns
+        penalty += line_run_penalty(row, size) + line_finder_like_penalty(row, size);
+
+        // rule 2: 2x2 blocks of the same color
+        if let Some(top) = previous {
+            let blocks = !(top ^ top.shift_right(1))
+                & !(row ^ row.shift_right(1))
+                & !(top ^ row)
+                & Line::first(size - 1);
+
+            penalty += 3 * blocks.count_ones() as i32;
+        }
+
+        dark_modules += row.count_ones() as usize;
+        previous = Some(row);
+    }
+
+    for column in columns {
+        penalty += line_run_penalty(column, size) + line_finder_like_penalty(column, size);
+    }
+
+    // rule 4: how far the ratio of dark modules is from 50%
+    let percent = (dark_modules * 100 / (size * size)) as i32;
+    let previous_multiple = percent - percent % 5;
+    let next_multiple = previous_multiple + 5;
+
+    penalty += ((previous_multiple - 50).abs() / 5).min((next_multiple - 50).abs() / 5) * 10;
+
+    penalty
+}
+
+/**
+ * A run of 5 or more modules of the same color scores its length less 2
+ */
+fn line_run_penalty(line: Line, len: usize) -> i32 {
+    if len < 5 {
+        return 0;
+    }
+
+    // bit i is set when module i is the same color as module i + 1
+    let same = !(line ^ line.shift_right(1)) & Line::first(len - 1);
+
+    // bit i is set when modules i to i + 4 are the same color, a run of n sets n - 4 bits in a row
+    let windows = same & same.shift_right(1) & same.shift_right(2) & same.shift_right(3);
+    let runs = windows & !windows.shift_left_one();
+
+    windows.count_ones() as i32 + 2 * runs.count_ones() as i32
+}
+
+/**
+ * Each 1:1:3:1:1 finder-like pattern with 4 light modules on one side scores 40
+ */
+fn line_finder_like_penalty(line: Line, len: usize) -> i32 {
+    if len < 11 {
+        return 0;
+    }
+
+    let shifted: [Line; 7] = core::array::from_fn(|i| line.shift_right(i));
+
+    // bit i is set when modules i to i + 6 are dark, light, dark, dark, dark, light, dark
+    let core =
+        shifted[0] & !shifted[1] & shifted[2] & shifted[3] & shifted[4] & !shifted[5] & shifted[6];
+
+    // bit i is set when modules i to i + 3 are light
+    let light = !(shifted[0] | shifted[1] | shifted[2] | shifted[3]);
+
+    let starts = Line::first(len - 10);
+    let forward = core & light.shift_right(7) & starts;
+    let backward = light & core.shift_right(4) & starts;
+
+    40 * (forward.count_ones() + backward.count_ones()) as i32
+}
+
+/**
+ * The modules of a row or column as bits, bit i is module i. 192 bits hold the 177 modules of the
+ * largest version.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Line([u64; 3]);
+
+impl Line {
+    fn new(len: usize, dark: impl Fn(usize) -> bool) -> Line {
+        let mut line = Line::default();
+        for i in 0..len {
+            line.0[i / 64] |= (dark(i) as u64) << (i % 64);
+        }
+
+        line
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_modules(modules: &[bool]) -> Line {
+        let mut line = Line::default();
+
+        for (word, chunk) in line.0.iter_mut().zip(modules.chunks(64)) {
+            *word = chunk
+                .iter()
+                .enumerate()
+                .fold(0, |word, (i, &dark)| word | (dark as u64) << i);
+        }
+
+        line
+    }
+
+    /**
+     * The lowest `period` bits of `unit` over and over, the caller cuts it to length
+     */
+    #[cfg(feature = "alloc")]
+    fn repeating(unit: u64, period: usize) -> Line {
+        let mut line = Line::default();
+
+        for (i, word) in line.0.iter_mut().enumerate() {
+            // the unit rotated so it continues where the previous word left off
+            let phase = i * 64 % period;
+            let rotated = (unit >> phase | unit << (period - phase)) & ((1 << period) - 1);
+
+            for start in (0..64).step_by(period) {
+                *word |= rotated << start;
+            }
+        }
+
+        line
+    }
+
+    /**
+     * The first `len` bits set
+     */
+    fn first(len: usize) -> Line {
+        let word = |start: usize| match len.saturating_sub(start) {
+            0 => 0,
+            bits if bits >= 64 => u64::MAX,
+            bits => (1 << bits) - 1,
+        };
+
+        Line([word(0), word(64), word(128)])
+    }
+
+    #[cfg(feature = "alloc")]
+    fn set(&mut self, i: usize, bit: bool) {
+        let word = &mut self.0[i / 64];
+        let mask = 1 << (i % 64);
+
+        if bit {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    /**
+     * Moves bit i + n to bit i, n must be less than 64
+     */
+    fn shift_right(self, n: usize) -> Line {
+        if n == 0 {
+            return self;
+        }
+
+        let [a, b, c] = self.0;
+        Line([a >> n | b << (64 - n), b >> n | c << (64 - n), c >> n])
+    }
+
+    /**
+     * Moves bit i to bit i + 1
+     */
+    fn shift_left_one(self) -> Line {
+        let [a, b, c] = self.0;
+        Line([a << 1, b << 1 | a >> 63, c << 1 | b >> 63])
+    }
+
+    fn count_ones(self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+}
+
+impl BitAnd for Line {
+    type Output = Line;
+
+    fn bitand(self, other: Line) -> Line {
+        Line([
+            self.0[0] & other.0[0],
+            self.0[1] & other.0[1],
+            self.0[2] & other.0[2],
+        ])
+    }
+}
+
+impl BitOr for Line {
+    type Output = Line;
+
+    fn bitor(self, other: Line) -> Line {
+        Line([
+            self.0[0] | other.0[0],
+            self.0[1] | other.0[1],
+            self.0[2] | other.0[2],
+        ])
+    }
+}
+
+impl BitXor for Line {
+    type Output = Line;
+
+    fn bitxor(self, other: Line) -> Line {
+        Line([
+            self.0[0] ^ other.0[0],
+            self.0[1] ^ other.0[1],
+            self.0[2] ^ other.0[2],
+        ])
+    }
+}
+
+impl Not for Line {
+    type Output = Line;
+
+    fn not(self) -> Line {
+        Line([!self.0[0], !self.0[1], !self.0[2]])
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_line_run_penalty() {
+    let mut line = vec![true; 7];
+    line.extend(vec![false; 4]);
+    line.extend(vec![true; 5]);
+
+    // a run of 7 scores 5 and a run of 5 scores 3
+    assert_eq!(
+        line_run_penalty(Line::new(line.len(), |i| line[i]), line.len()),
+        8
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_line_finder_like_penalty() {
+    let line: Vec<bool> = "0000101110100001011101".chars().map(|c| c == '1').collect();
+
+    // the light modules between the two patterns count for both of them
+    assert_eq!(
+        line_finder_like_penalty(Line::new(line.len(), |i| line[i]), line.len()),
+        120
+    );
+}
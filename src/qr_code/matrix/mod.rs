@@ -0,0 +1,297 @@
+mod function_patterns;
+mod information;
+mod masking;
+mod placement;
+
+#[cfg(feature = "alloc")]
+use masking::score_masks;
+#[cfg(feature = "alloc")]
+use placement::place_code_words;
+
+pub use function_patterns::draw_function_patterns;
+pub use information::{draw_format_information, draw_version_information};
+#[cfg(feature = "alloc")]
+pub use information::{
+    format_information_bits, format_information_positions, version_information_bits,
+};
+#[cfg(feature = "alloc")]
+pub use masking::MaskScratch;
+pub use masking::{apply_mask, mask_applies, penalty_of};
+#[cfg(feature = "alloc")]
+pub use placement::data_modules;
+pub use placement::for_each_data_module;
+
+use crate::qr_code::Version;
+#[cfg(feature = "alloc")]
+use crate::qr_code::{BitBuffer, ErrorCorrectionLevel};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/**
+ * Anything the function patterns, format and version information and masks can be drawn on, true
+ * is a dark module. `Matrix` keeps a bool per module, `FixedQrCode` packs them into its own
+ * buffers.
+ */
+pub trait Modules {
+    /**
+     * The number of modules along each side
+     */
+    fn size(&self) -> usize;
+
+    fn get(&self, x: usize, y: usize) -> bool;
+
+    fn is_function(&self, x: usize, y: usize) -> bool;
+
+    fn set(&mut self, x: usize, y: usize, dark: bool);
+
+    /**
+     * Sets a module and marks it as part of a function pattern so data is never placed there
+     */
+    fn set_function(&mut self, x: usize, y: usize, dark: bool);
+}
+
+/**
+ * Draws everything but the format information, which depends on the mask pattern, on modules
+ * that are all light and not part of a function pattern
+ */
+pub fn draw_version(modules: &mut impl Modules, version: &Version) {
+    draw_function_patterns(modules, version);
+    draw_version_information(modules, version);
+}
+
+/**
+ * The grid of modules that makes up a QR code, true is a dark module.
+ *
+ * Modules are stored row by row, x is the column and y is the row with (0, 0) in the top left corner.
+ */
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Matrix {
+    size: usize,
+    modules: Vec<bool>,
+    function_modules: Vec<bool>,
+}
+
+#[cfg(feature = "alloc")]
+impl Matrix {
+    /**
+     * Creates a matrix for the version with all function patterns and the version information
+     * drawn, and the format information area reserved
+     */
+    pub fn new(version: &Version) -> Matrix {
+        let size = version.size() as usize;
+
+        let mut matrix = Matrix {
+            size,
+            modules: vec![false; size * size],
+            function_modules: vec![false; size * size],
+        };
+
+        draw_version(&mut matrix, version);
+
+        matrix
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    pub fn is_function(&self, x: usize, y: usize) -> bool {
+        self.function_modules[y * self.size + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+    }
+
+    /**
+     * Sets a module and marks it as part of a function pattern so data is never placed there
+     */
+    pub fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.set(x, y, dark);
+        self.function_modules[y * self.size + x] = true;
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[bool]> {
+        self.modules.chunks(self.size)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Modules for Matrix {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        Matrix::get(self, x, y)
+    }
+
+    fn is_function(&self, x: usize, y: usize) -> bool {
+        Matrix::is_function(self, x, y)
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        Matrix::set(self, x, y, dark);
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        Matrix::set_function(self, x, y, dark);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Clone for Matrix {
+    fn clone(&self) -> Matrix {
+        Matrix {
+            size: self.size,
+            modules: self.modules.clone(),
+            function_modules: self.function_modules.clone(),
+        }
+    }
+
+    /**
+     * Copies into the modules already allocated, a matrix of the same version never reallocates
+     */
+    fn clone_from(&mut self, source: &Matrix) {
+        self.size = source.size;
+        self.modules.clone_from(&source.modules);
+        self.function_modules.clone_from(&source.function_modules);
+    }
+}
+
+/**
+ * Places the final message in the matrix and applies the mask pattern with the lowest penalty score,
+ * or the given mask pattern if there is one.
+ *
+ * Returns the matrix and the mask pattern that was used.
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/module-placement-matrix
+ */
+#[cfg(feature = "alloc")]
+pub fn build_matrix(
+    code_words: &BitBuffer,
+    error_correction_level: &ErrorCorrectionLevel,
+    version: &Version,
+    mask: Option<u8>,
+) -> (Matrix, u8) {
+    let mut matrix = Matrix::new(version);
+    let mask = place_and_mask(
+        &mut matrix,
+        code_words,
+        error_correction_level,
+        mask,
+        &mut MaskScratch::default(),
+    );
+
+    (matrix, mask)
+}
+
+/**
+ * Builds matrices like build_matrix into an existing matrix, keeping the function patterns of every
+ * version it has seen and the buffers the masks are scored with
+ */
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub struct MatrixBuilder {
+    templates: Vec<Option<Matrix>>,
+    scratch: MaskScratch,
+}
+
+#[cfg(feature = "alloc")]
+impl MatrixBuilder {
+    /**
+     * Overwrites the matrix with the final message and mask pattern, returning the mask pattern
+     * that was used
+     */
+    pub fn build_into(
+        &mut self,
+        matrix: &mut Matrix,
+        code_words: &BitBuffer,
+        error_correction_level: &ErrorCorrectionLevel,
+        version: &Version,
+        mask: Option<u8>,
+    ) -> u8 {
+        let index = version.version() as usize;
+        if self.templates.len() <= index {
+            self.templates.resize(index + 1, None);
+        }
+
+        let template = self.templates[index].get_or_insert_with(|| Matrix::new(version));
+        matrix.clone_from(template);
+
+        place_and_mask(
+            matrix,
+            code_words,
+            error_correction_level,
+            mask,
+            &mut self.scratch,
+        )
+    }
+}
+
+/**
+ * Places the message in a matrix with only the function patterns drawn and applies the mask
+ * pattern, scoring every mask pattern when none is given
+ */
+#[cfg(feature = "alloc")]
+fn place_and_mask(
+    matrix: &mut Matrix,
+    code_words: &BitBuffer,
+    error_correction_level: &ErrorCorrectionLevel,
+    mask: Option<u8>,
+    scratch: &mut MaskScratch,
+) -> u8 {
+    place_code_words(matrix, code_words);
+
+    let mask = mask.unwrap_or_else(|| {
+        let penalties = score_masks(matrix, error_correction_level, scratch);
+        let mut mask = 0;
+        for (candidate, &penalty) in penalties.iter().enumerate() {
+            if penalty < penalties[mask as usize] {
+                mask = candidate as u8;
+            }
+        }
+
+        mask
+    });
+
+    apply_mask(matrix, mask);
+    draw_format_information(matrix, error_correction_level, mask);
+
+    mask
+}
+
+/**
+ * The penalty score of every mask pattern for the message, indexed by mask pattern
+ */
+#[cfg(feature = "std")]
+pub fn mask_penalties(
+    code_words: &BitBuffer,
+    error_correction_level: &ErrorCorrectionLevel,
+    version: &Version,
+) -> [i32; 8] {
+    let mut matrix = Matrix::new(version);
+    place_code_words(&mut matrix, code_words);
+
+    score_masks(&matrix, error_correction_level, &mut MaskScratch::default())
+}
+
+/**
+ * The number of modules left over after the message is placed, these remainder bits are always light
+ */
+#[cfg(feature = "std")]
+pub fn remainder_bits(code_words: &BitBuffer, version: &Version) -> usize {
+    let matrix = Matrix::new(version);
+    let data_modules = matrix
+        .function_modules
+        .iter()
+        .filter(|&&function| !function)
+        .count();
+
+    data_modules - code_words.len()
+}
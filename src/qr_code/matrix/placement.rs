@@ -0,0 +1,90 @@
+#[cfg(feature = "alloc")]
+use crate::qr_code::matrix::Matrix;
+#[cfg(feature = "alloc")]
+use crate::qr_code::BitBuffer;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/**
+ * Places the bits of the final message in the matrix in the order given by data_modules.
+ *
+ * Any modules left over after the message are the remainder bits and stay light.
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/module-placement-matrix
+ */
+#[cfg(feature = "alloc")]
+pub fn place_code_words(matrix: &mut Matrix, code_words: &BitBuffer) {
+    let size = matrix.size;
+    let modules = &mut matrix.modules;
+    let function_modules = &matrix.function_modules;
+    let mut bits = code_words.bits();
+
+    for_each_data_module(
+        size,
+        |x, y| function_modules[y * size + x],
+        |x, y| {
+            modules[y * size + x] = bits.next().unwrap_or(false);
+        },
+    );
+}
+
+/**
+ * The position of every module that is not part of a function pattern in the order the message is
+ * placed, starting in the bottom right corner and moving in two module wide columns that zigzag up
+ * and down, skipping the vertical timing pattern.
+ *
+ * The nth position holds bit n of the final message.
+ */
+#[cfg(feature = "alloc")]
+pub fn data_modules(matrix: &Matrix) -> Vec<(usize, usize)> {
+    let mut positions = Vec::with_capacity(matrix.size * matrix.size);
+
+    for_each_data_module(
+        matrix.size,
+        |x, y| matrix.is_function(x, y),
+        |x, y| {
+            positions.push((x, y));
+        },
+    );
+
+    positions
+}
+
+/**
+ * Calls `place` with each position in the order of data_modules, without collecting them
+ */
+pub fn for_each_data_module(
+    size: usize,
+    is_function: impl Fn(usize, usize) -> bool,
+    mut place: impl FnMut(usize, usize),
+) {
+    let mut right = size - 1;
+    loop {
+        // the vertical timing pattern is skipped entirely
+        if right == 6 {
+            right = 5;
+        }
+
+        let upward = (right + 1) & 2 == 0;
+
+        for vertical in 0..size {
+            let y = if upward {
+                size - 1 - vertical
+            } else {
+                vertical
+            };
+
+            for x in [right, right - 1] {
+                if !is_function(x, y) {
+                    place(x, y);
+                }
+            }
+        }
+
+        if right < 2 {
+            break;
+        }
+
+        right -= 2;
+    }
+}
@@ -1,15 +1,57 @@
+use core::fmt;
+
+mod bit_buffer;
 mod character_count;
+#[cfg(feature = "alloc")]
+mod decode;
+#[cfg(feature = "alloc")]
+mod encoder;
 pub mod encoding;
 mod error_correction;
-mod utils;
+mod fixed;
+mod knockout;
+mod matrix;
+#[cfg(feature = "std")]
+pub mod payload;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+mod report;
 mod version;
 
-use character_count::create_character_count_indicator;
-use encoding::encode;
-use error_correction::generate_error_correction;
-use utils::right_pad;
-use version::{determine_data_bits_required_for_version, determine_optimal_qr_code_version};
+#[cfg(feature = "alloc")]
+pub use bit_buffer::BitBuffer;
+pub use bit_buffer::BitSink;
+#[cfg(feature = "alloc")]
+pub use decode::{decode_matrix, DecodedQrCode};
+#[cfg(feature = "alloc")]
+pub use encoder::Encoder;
+pub use error_correction::GroupConfig;
+pub use fixed::{fixed_buffer_len, FixedError, FixedQrCode};
+pub use knockout::{Knockout, KnockoutShape};
+#[cfg(feature = "std")]
+pub use report::{BlockReport, EncodingReport};
+
+/**
+ * The stages of creating a code, public only for benches/encode.rs. Not part of the API, they can
+ * change or go away at any time.
+ */
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub mod stages {
+    pub use super::error_correction::generate_error_correction;
+    pub use super::matrix::build_matrix;
+}
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "alloc")]
+use matrix::Matrix;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EncodingMode {
     Numeric,
     Alphanumeric,
@@ -17,25 +59,56 @@ pub enum EncodingMode {
 }
 
 impl EncodingMode {
-    pub fn to_bits(&self) -> String {
+    #[cfg(feature = "alloc")]
+    pub fn to_bits(&self) -> BitBuffer {
+        let mut bits = BitBuffer::with_capacity(4);
+        bits.append_bits(self.indicator(), 4);
+        bits
+    }
+
+    /**
+     * The 4 bit mode indicator that starts every segment
+     */
+    pub fn indicator(&self) -> u32 {
         match self {
-            EncodingMode::Numeric => "0001",
-            EncodingMode::Alphanumeric => "0010",
-            EncodingMode::Byte => "0100",
+            EncodingMode::Numeric => 0b0001,
+            EncodingMode::Alphanumeric => 0b0010,
+            EncodingMode::Byte => 0b0100,
         }
-        .to_string()
     }
 
-    pub fn to_string(&self) -> String {
-        match self {
+    /**
+     * Picks the most compact encoding mode that can represent every character in the data
+     */
+    pub fn detect(data: &str) -> EncodingMode {
+        if data.chars().all(|c| c.is_ascii_digit()) {
+            EncodingMode::Numeric
+        } else if data.chars().all(is_alphanumeric_character) {
+            EncodingMode::Alphanumeric
+        } else {
+            EncodingMode::Byte
+        }
+    }
+}
+
+/**
+ * Alphanumeric mode can only encode digits, uppercase letters and a handful of symbols
+ */
+fn is_alphanumeric_character(c: char) -> bool {
+    c.is_ascii_digit() || c.is_ascii_uppercase() || " $%*+-./:".contains(c)
+}
+
+impl fmt::Display for EncodingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
             EncodingMode::Numeric => "Numeric",
             EncodingMode::Alphanumeric => "Alphanumeric",
             EncodingMode::Byte => "Byte",
-        }
-        .to_string()
+        })
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ErrorCorrectionLevel {
     Low,
     Medium,
@@ -43,18 +116,18 @@ pub enum ErrorCorrectionLevel {
     High,
 }
 
-impl ErrorCorrectionLevel {
-    pub fn to_string(&self) -> String {
-        match self {
+impl fmt::Display for ErrorCorrectionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
             ErrorCorrectionLevel::Low => "Low",
             ErrorCorrectionLevel::Medium => "Medium",
             ErrorCorrectionLevel::Quartile => "Quartile",
             ErrorCorrectionLevel::High => "High",
-        }
-        .to_string()
+        })
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Version {
     Normal(i16),
 }
@@ -68,158 +141,249 @@ impl Version {
 
     pub fn version(&self) -> i16 {
         match self {
-            Version::Normal(version) => version.to_owned(),
+            Version::Normal(version) => *version,
         }
     }
+}
 
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Version::Normal(version) => format!("Version {}", version),
+            Version::Normal(version) => write!(f, "Version {}", version),
+        }
+    }
+}
+
+/**
+ * Optional settings for creating a QR code, the defaults allow any version and pick the mask pattern
+ * with the lowest penalty score
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QrCodeOptions {
+    pub min_version: i16,
+    pub max_version: i16,
+    pub mask: Option<u8>,
+    /// an area in the middle that is cleared for a logo
+    pub knockout: Option<Knockout>,
+    /// raise the error correction level until it can recover the knockout, instead of failing
+    pub raise_error_correction: bool,
+}
+
+impl Default for QrCodeOptions {
+    fn default() -> QrCodeOptions {
+        QrCodeOptions {
+            min_version: 1,
+            max_version: 40,
+            mask: None,
+            knockout: None,
+            raise_error_correction: false,
         }
     }
 }
 
+#[cfg(feature = "alloc")]
 pub struct QrCode {
     pub encoding_mode: EncodingMode,
     pub error_correction_level: ErrorCorrectionLevel,
     pub version: Version,
     pub data: String,
-    pub encoded_data: String,
+    pub encoded_data: BitBuffer,
+    pub code_words: BitBuffer,
+    pub mask: u8,
+    pub knockout: Option<Knockout>,
+    matrix: Matrix,
 }
 
+#[cfg(feature = "alloc")]
 impl QrCode {
     pub fn create(
         data: String,
         encoding_mode: EncodingMode,
         error_correction_level: ErrorCorrectionLevel,
     ) -> Result<QrCode, String> {
-        let version = match determine_optimal_qr_code_version(
-            &encoding_mode,
-            &error_correction_level,
-            data.len() as i32,
-        ) {
-            Ok(version) => version,
-            Err(err) => return Err(err),
-        };
-
-        let encoded_data = match encode(&data, &encoding_mode) {
-            Ok(encoded_data) => encoded_data,
-            Err(err) => return Err(err),
-        };
-
-        let character_count_indicator =
-            create_character_count_indicator(&data, &encoding_mode, &version);
-
-        let data_bits_required_for_version =
-            match determine_data_bits_required_for_version(&version, &error_correction_level) {
-                Ok(data_bits_required_for_version) => data_bits_required_for_version,
-                Err(err) => return Err(err),
-            };
-
-        let constructed_data = match construct_data(
-            &encoding_mode.to_bits(),
-            &character_count_indicator,
-            &encoded_data,
-            data_bits_required_for_version,
-        ) {
-            Ok(construct_data) => construct_data,
-            Err(err) => return Err(err),
-        };
-
-        let constructed_data =
-            match generate_error_correction(constructed_data, &error_correction_level, &version) {
-                Ok(constructed_data) => constructed_data,
-                Err(err) => return Err(err),
-            };
-
-        Ok(QrCode {
+        QrCode::create_with_options(
+            data,
             encoding_mode,
             error_correction_level,
-            version,
-            data,
-            encoded_data,
-        })
+            &QrCodeOptions::default(),
+        )
+    }
+
+    pub fn create_with_options(
+        data: String,
+        encoding_mode: EncodingMode,
+        error_correction_level: ErrorCorrectionLevel,
+        options: &QrCodeOptions,
+    ) -> Result<QrCode, String> {
+        Encoder::new().encode(&data, encoding_mode, error_correction_level, options)
+    }
+
+    /**
+     * The number of modules along each side, not including the quiet zone
+     */
+    pub fn width(&self) -> usize {
+        self.matrix.size()
+    }
+
+    /**
+     * Whether the module at column x and row y is dark, coordinates outside of the symbol are
+     * light as they fall in the quiet zone
+     */
+    pub fn get_module(&self, x: usize, y: usize) -> bool {
+        x < self.width() && y < self.width() && self.matrix.get(x, y)
+    }
+
+    /**
+     * Whether the module at column x and row y belongs to a finder, separator, timing or alignment
+     * pattern, the dark module, or the format and version information rather than the data
+     */
+    pub fn is_function_module(&self, x: usize, y: usize) -> bool {
+        x < self.width() && y < self.width() && self.matrix.is_function(x, y)
+    }
+
+    /**
+     * Iterates over the rows of the symbol from top to bottom, not including the quiet zone
+     */
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[bool]> {
+        self.matrix.rows()
+    }
+
+    /**
+     * Copies the modules into rows, surrounded by `quiet_zone` light modules on each side.
+     *
+     * The specification requires a quiet zone of 4 modules, pass 0 to get only the symbol.
+     */
+    pub fn to_matrix(&self, quiet_zone: usize) -> Vec<Vec<bool>> {
+        let width = self.width() + quiet_zone * 2;
+
+        (0..width)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        x >= quiet_zone
+                            && y >= quiet_zone
+                            && self.get_module(x - quiet_zone, y - quiet_zone)
+                    })
+                    .collect()
+            })
+            .collect()
     }
 
     /**
      * Prints information about the qr code
      */
+    #[cfg(feature = "std")]
     pub fn details(&self) {
-        println!("---- {} QR Code ----\n", self.version.to_string());
-        println!("Encoding Mode: {}", self.encoding_mode.to_string());
-        println!(
-            "Error Correction Level: {}",
-            self.error_correction_level.to_string()
-        );
+        println!("---- {} QR Code ----\n", self.version);
+        println!("Encoding Mode: {}", self.encoding_mode);
+        println!("Error Correction Level: {}", self.error_correction_level);
+        println!("Mask Pattern: {}", self.mask);
     }
 }
 
+/**
+ * Joins the mode indicator, character count indicator and encoded data, then adds the terminator
+ * and pad bytes until the data fills the capacity of the version.
+ *
+ * based on https://www.thonky.com/qr-code-tutorial/data-encoding
+ */
+#[cfg(feature = "std")]
 fn construct_data(
-    encoding_mode_bits: &str,
-    character_count_indicator_bits: &str,
-    encoded_data_bits: &str,
+    encoding_mode_bits: &BitBuffer,
+    character_count_indicator_bits: &BitBuffer,
+    encoded_data_bits: &BitBuffer,
     data_bits_required_for_version: i32,
-) -> Result<String, String> {
-    let mut bits = format!(
-        "{}{}{}",
-        encoding_mode_bits, character_count_indicator_bits, encoded_data_bits
-    );
+) -> Result<BitBuffer, String> {
+    let mut bits = BitBuffer::with_capacity(data_bits_required_for_version as usize);
+    bits.append_buffer(encoding_mode_bits);
+    bits.append_buffer(character_count_indicator_bits);
+    bits.append_buffer(encoded_data_bits);
 
-    if bits.len() > data_bits_required_for_version as usize {
+    pad_data(&mut bits, data_bits_required_for_version)?;
+
+    Ok(bits)
+}
+
+/**
+ * Adds the terminator and pad bytes after the data until it fills the capacity of the version
+ */
+#[cfg(feature = "alloc")]
+fn pad_data(bits: &mut BitBuffer, data_bits_required_for_version: i32) -> Result<(), String> {
+    let required_len = data_bits_required_for_version as usize;
+
+    if bits.len() > required_len {
         return Err("Data bits are longer than what the QR Code can hold".to_string());
     }
 
-    // adding terminator bits
-    if bits.len() + 4 != data_bits_required_for_version as usize {
-        bits += "0000";
+    pad_bits(bits, required_len);
 
-        if bits.len() == data_bits_required_for_version as usize {
-            return Ok(bits);
-        }
+    Ok(())
+}
 
-        // making multiple of 8;
-        let len = bits.len() % 8;
-        let dif = 8 - len;
+/**
+ * Appends the padding of Padding::new, the bits must already fit in `required_len`
+ */
+fn pad_bits(bits: &mut impl BitSink, required_len: usize) {
+    let padding = Padding::new(bits.len(), required_len);
 
-        bits = right_pad(&bits, (dif + bits.len()) as i32, "0");
-        if bits.len() == data_bits_required_for_version as usize {
-            return Ok(bits);
-        }
+    bits.append_bits(0, padding.terminator_bits as u8);
+    bits.append_bits(0, padding.alignment_bits as u8);
 
-        // TODO: improve this: required_len - current_len = x; x/8 = bytes_to_pad
-        let pad_string_odd = "11101100";
-        let pad_string_even = "00010001";
+    for byte in padding.pad_bytes() {
+        bits.append_bits(byte as u32, 8);
+    }
+}
 
-        while bits.len() != data_bits_required_for_version as usize {
-            bits += pad_string_odd;
-            if bits.len() == data_bits_required_for_version as usize {
-                return Ok(bits);
-            }
+/**
+ * What construct_data adds after the encoded data to fill the capacity of the version
+ */
+struct Padding {
+    /// up to 4 zero bits marking the end of the data
+    pub terminator_bits: usize,
+    /// zero bits making the length a multiple of 8
+    pub alignment_bits: usize,
+    /// how many pad bytes fill the remaining capacity
+    pub pad_byte_count: usize,
+}
 
-            bits += pad_string_even;
+impl Padding {
+    fn new(data_len: usize, required_len: usize) -> Padding {
+        let terminator_bits = (required_len - data_len).min(4);
+        let alignment_bits = (8 - (data_len + terminator_bits) % 8) % 8;
+        let pad_byte_count = (required_len - data_len - terminator_bits - alignment_bits) / 8;
 
-            if bits.len() == data_bits_required_for_version as usize {
-                return Ok(bits);
-            }
-        }
-    } else {
-        while bits.len() != data_bits_required_for_version as usize {
-            bits += "0";
+        Padding {
+            terminator_bits,
+            alignment_bits,
+            pad_byte_count,
         }
     }
-    Ok(bits)
+
+    /**
+     * Alternating 11101100 and 00010001 bytes
+     */
+    fn pad_bytes(&self) -> impl Iterator<Item = u8> {
+        [0b11101100, 0b00010001]
+            .into_iter()
+            .cycle()
+            .take(self.pad_byte_count)
+    }
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn test_data_construct() {
-    let encoding_mode_bits = "0010";
-    let character_count_indicator_bits = "000001011";
-    let encoded_data_bits = "0110000101101111000110100010111001011011100010011010100001101";
+    let encoding_mode_bits = EncodingMode::Alphanumeric.to_bits();
+
+    let mut character_count_indicator_bits = BitBuffer::new();
+    character_count_indicator_bits.append_bits(11, 9);
+
+    let encoded_data_bits = encoding::encode("HELLO WORLD", &EncodingMode::Alphanumeric).unwrap();
 
     let constructed_data = construct_data(
-        encoding_mode_bits,
-        character_count_indicator_bits,
-        encoded_data_bits,
+        &encoding_mode_bits,
+        &character_count_indicator_bits,
+        &encoded_data_bits,
         104,
     );
 
@@ -228,7 +392,7 @@ fn test_data_construct() {
     let constructed_data = constructed_data.unwrap();
 
     assert_eq!(
-        constructed_data, "00100000010110110000101101111000110100010111001011011100010011010100001101000000111011000001000111101100",
+        constructed_data.to_string(), "00100000010110110000101101111000110100010111001011011100010011010100001101000000111011000001000111101100",
         "Construced data did not match expected result"
     )
 }
@@ -0,0 +1,201 @@
+use crate::qr_code::payload::contact::is_valid_date;
+use crate::qr_code::payload::{content_lines, escape_text, Payload};
+
+/**
+ * When an event starts or ends, either a whole day or a time of day in UTC or the local time of
+ * whoever scans the code
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventTime {
+    Date {
+        year: u16,
+        month: u8,
+        day: u8,
+    },
+    DateTime {
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        utc: bool,
+    },
+}
+
+impl EventTime {
+    pub fn date(year: u16, month: u8, day: u8) -> EventTime {
+        EventTime::Date { year, month, day }
+    }
+
+    pub fn utc(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> EventTime {
+        EventTime::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            utc: true,
+        }
+    }
+
+    pub fn local(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> EventTime {
+        EventTime::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            utc: false,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let valid = match *self {
+            EventTime::Date { year, month, day } => is_valid_date(year, month, day),
+            EventTime::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                ..
+            } => is_valid_date(year, month, day) && hour < 24 && minute < 60 && second < 60,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("{} is not a valid date and time", self.value()))
+        }
+    }
+
+    /**
+     * The property name with parameters and the value, dates need VALUE=DATE
+     */
+    fn property(&self, name: &str) -> String {
+        match self {
+            EventTime::Date { .. } => format!("{};VALUE=DATE:{}", name, self.value()),
+            EventTime::DateTime { .. } => format!("{}:{}", name, self.value()),
+        }
+    }
+
+    fn value(&self) -> String {
+        match *self {
+            EventTime::Date { year, month, day } => format!("{:04}{:02}{:02}", year, month, day),
+            EventTime::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                utc,
+            } => format!(
+                "{:04}{:02}{:02}T{:02}{:02}{:02}{}",
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                if utc { "Z" } else { "" }
+            ),
+        }
+    }
+}
+
+/**
+ * An event that calendar apps can add, written as a single iCalendar VEVENT which is what scanner
+ * apps expect rather than a complete VCALENDAR
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc5545#section-3.6.1
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub start: EventTime,
+    pub end: Option<EventTime>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+}
+
+impl CalendarEvent {
+    pub fn new(summary: &str, start: EventTime) -> CalendarEvent {
+        CalendarEvent {
+            summary: summary.to_string(),
+            start,
+            end: None,
+            location: None,
+            description: None,
+        }
+    }
+
+    pub fn end(mut self, end: EventTime) -> CalendarEvent {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn location(mut self, location: &str) -> CalendarEvent {
+        self.location = Some(location.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> CalendarEvent {
+        self.description = Some(description.to_string());
+        self
+    }
+}
+
+impl Payload for CalendarEvent {
+    fn to_payload_string(&self) -> Result<String, String> {
+        self.start.validate()?;
+
+        if let Some(end) = &self.end {
+            end.validate()?;
+
+            let same_kind = matches!(
+                (&self.start, end),
+                (EventTime::Date { .. }, EventTime::Date { .. })
+                    | (EventTime::DateTime { .. }, EventTime::DateTime { .. })
+            );
+
+            if !same_kind {
+                return Err(
+                    "The start and end of an event must both be dates or both be date times"
+                        .to_string(),
+                );
+            }
+
+            // the fixed width values sort the same way as the times they represent
+            if end.value() < self.start.value() {
+                return Err("An event cannot end before it starts".to_string());
+            }
+        }
+
+        let mut lines = vec![
+            "BEGIN:VEVENT".to_string(),
+            format!("SUMMARY:{}", escape_text(&self.summary)),
+            self.start.property("DTSTART"),
+        ];
+
+        if let Some(end) = &self.end {
+            lines.push(end.property("DTEND"));
+        }
+
+        if let Some(location) = &self.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+
+        if let Some(description) = &self.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+
+        lines.push("END:VEVENT".to_string());
+
+        Ok(content_lines(&lines))
+    }
+}
@@ -0,0 +1,536 @@
+use crate::qr_code::payload::{
+    content_lines, escape_text, escape_with_backslash, split_escaped, strip_prefix_ignore_case,
+    unescape_backslash, unescape_text, unfold_lines, Payload,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhoneType {
+    Cell,
+    Home,
+    Work,
+    Fax,
+    Other,
+}
+
+impl PhoneType {
+    fn vcard_type(&self) -> Option<&'static str> {
+        match self {
+            PhoneType::Cell => Some("cell"),
+            PhoneType::Home => Some("home"),
+            PhoneType::Work => Some("work"),
+            PhoneType::Fax => Some("fax"),
+            PhoneType::Other => None,
+        }
+    }
+
+    /**
+     * Finds the first type this crate knows about in a TYPE parameter such as "voice,cell"
+     */
+    fn from_vcard_types(types: &[String]) -> PhoneType {
+        types
+            .iter()
+            .find_map(|name| match name.to_ascii_lowercase().as_str() {
+                "cell" => Some(PhoneType::Cell),
+                "home" => Some(PhoneType::Home),
+                "work" => Some(PhoneType::Work),
+                "fax" => Some(PhoneType::Fax),
+                _ => None,
+            })
+            .unwrap_or(PhoneType::Other)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+    pub region: String,
+    pub postal_code: String,
+    pub country: String,
+}
+
+/**
+ * The details shared by the vCard and MeCard formats
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Contact {
+    pub first_name: String,
+    pub last_name: String,
+    pub nickname: Option<String>,
+    pub organization: Option<String>,
+    pub title: Option<String>,
+    pub phones: Vec<(PhoneType, String)>,
+    pub emails: Vec<String>,
+    pub url: Option<String>,
+    pub address: Option<Address>,
+    /// year, month and day
+    pub birthday: Option<(u16, u8, u8)>,
+    pub note: Option<String>,
+}
+
+impl Contact {
+    pub fn new(first_name: &str, last_name: &str) -> Contact {
+        Contact {
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            ..Contact::default()
+        }
+    }
+
+    pub fn nickname(mut self, nickname: &str) -> Contact {
+        self.nickname = Some(nickname.to_string());
+        self
+    }
+
+    pub fn organization(mut self, organization: &str) -> Contact {
+        self.organization = Some(organization.to_string());
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Contact {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn phone(mut self, phone_type: PhoneType, number: &str) -> Contact {
+        self.phones.push((phone_type, number.to_string()));
+        self
+    }
+
+    pub fn email(mut self, email: &str) -> Contact {
+        self.emails.push(email.to_string());
+        self
+    }
+
+    pub fn url(mut self, url: &str) -> Contact {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    pub fn address(mut self, address: Address) -> Contact {
+        self.address = Some(address);
+        self
+    }
+
+    pub fn birthday(mut self, year: u16, month: u8, day: u8) -> Contact {
+        self.birthday = Some((year, month, day));
+        self
+    }
+
+    pub fn note(mut self, note: &str) -> Contact {
+        self.note = Some(note.to_string());
+        self
+    }
+
+    fn full_name(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+            .trim()
+            .to_string()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.full_name().is_empty() {
+            return Err("A contact needs a first or last name".to_string());
+        }
+
+        if let Some((year, month, day)) = self.birthday {
+            if !is_valid_date(year, month, day) {
+                return Err(format!(
+                    "Birthday {:04}-{:02}-{:02} is not a valid date",
+                    year, month, day
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VCardVersion {
+    V3,
+    V4,
+}
+
+/**
+ * A contact card in the vCard format read by most address books
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc2426 and https://www.rfc-editor.org/rfc/rfc6350
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VCard {
+    pub contact: Contact,
+    pub version: VCardVersion,
+}
+
+impl VCard {
+    pub fn new(contact: Contact, version: VCardVersion) -> VCard {
+        VCard { contact, version }
+    }
+
+    /**
+     * Reads the properties this crate can build from a vCard, anything else is ignored. Version 2.1
+     * cards are read as 3.0 as the properties are mostly the same.
+     */
+    pub fn parse(text: &str) -> Result<VCard, String> {
+        let lines = unfold_lines(text.trim());
+
+        if !lines
+            .first()
+            .is_some_and(|line| line.eq_ignore_ascii_case("BEGIN:VCARD"))
+        {
+            return Err("vCards must start with BEGIN:VCARD".to_string());
+        }
+
+        let mut contact = Contact::default();
+        let mut version = VCardVersion::V3;
+        let mut formatted_name = None;
+
+        for line in &lines[1..] {
+            let Some((name, types, value)) = split_content_line(line) else {
+                continue;
+            };
+
+            let components = || -> Vec<String> {
+                split_escaped(value, ';')
+                    .into_iter()
+                    .map(unescape_text)
+                    .collect()
+            };
+
+            match name.as_str() {
+                "VERSION" if value == "4.0" => version = VCardVersion::V4,
+                "N" => {
+                    let components = components();
+                    contact.last_name = components.first().cloned().unwrap_or_default();
+                    contact.first_name = components.get(1).cloned().unwrap_or_default();
+                }
+                "FN" => formatted_name = Some(unescape_text(value)),
+                "NICKNAME" => contact.nickname = Some(unescape_text(value)),
+                "ORG" => contact.organization = Some(components().join(", ")),
+                "TITLE" => contact.title = Some(unescape_text(value)),
+                "TEL" => {
+                    let number = unescape_text(value);
+                    let number = strip_prefix_ignore_case(&number, "tel:").unwrap_or(&number);
+
+                    contact
+                        .phones
+                        .push((PhoneType::from_vcard_types(&types), number.to_string()));
+                }
+                "EMAIL" => contact.emails.push(unescape_text(value)),
+                "URL" => contact.url = Some(unescape_text(value)),
+                "ADR" => {
+                    let components = components();
+                    let component = |i: usize| components.get(i).cloned().unwrap_or_default();
+
+                    contact.address = Some(Address {
+                        street: component(2),
+                        city: component(3),
+                        region: component(4),
+                        postal_code: component(5),
+                        country: component(6),
+                    });
+                }
+                "BDAY" => contact.birthday = parse_date(value),
+                "NOTE" => contact.note = Some(unescape_text(value)),
+                "END" => break,
+                _ => {}
+            }
+        }
+
+        // cards without a structured name still have a formatted name
+        if contact.full_name().is_empty() {
+            contact.first_name = formatted_name.unwrap_or_default();
+        }
+
+        Ok(VCard { contact, version })
+    }
+}
+
+impl Payload for VCard {
+    fn to_payload_string(&self) -> Result<String, String> {
+        let contact = &self.contact;
+        contact.validate()?;
+
+        let v4 = self.version == VCardVersion::V4;
+        let mut lines = vec!["BEGIN:VCARD".to_string()];
+
+        lines.push(if v4 { "VERSION:4.0" } else { "VERSION:3.0" }.to_string());
+        lines.push(format!(
+            "N:{};{};;;",
+            escape_text(&contact.last_name),
+            escape_text(&contact.first_name)
+        ));
+        lines.push(format!("FN:{}", escape_text(&contact.full_name())));
+
+        if let Some(nickname) = &contact.nickname {
+            lines.push(format!("NICKNAME:{}", escape_text(nickname)));
+        }
+
+        if let Some(organization) = &contact.organization {
+            lines.push(format!("ORG:{}", escape_text(organization)));
+        }
+
+        if let Some(title) = &contact.title {
+            lines.push(format!("TITLE:{}", escape_text(title)));
+        }
+
+        for (phone_type, number) in &contact.phones {
+            let number = escape_text(number);
+
+            lines.push(match (v4, phone_type.vcard_type()) {
+                (true, Some(name)) => format!("TEL;VALUE=uri;TYPE={}:tel:{}", name, number),
+                (true, None) => format!("TEL;VALUE=uri:tel:{}", number),
+                (false, Some(name)) => {
+                    format!("TEL;TYPE={}:{}", name.to_ascii_uppercase(), number)
+                }
+                (false, None) => format!("TEL:{}", number),
+            });
+        }
+
+        for email in &contact.emails {
+            lines.push(if v4 {
+                format!("EMAIL:{}", escape_text(email))
+            } else {
+                format!("EMAIL;TYPE=INTERNET:{}", escape_text(email))
+            });
+        }
+
+        if let Some(url) = &contact.url {
+            lines.push(format!("URL:{}", escape_text(url)));
+        }
+
+        if let Some(address) = &contact.address {
+            lines.push(format!(
+                "ADR:;;{};{};{};{};{}",
+                escape_text(&address.street),
+                escape_text(&address.city),
+                escape_text(&address.region),
+                escape_text(&address.postal_code),
+                escape_text(&address.country)
+            ));
+        }
+
+        if let Some((year, month, day)) = contact.birthday {
+            lines.push(if v4 {
+                format!("BDAY:{:04}{:02}{:02}", year, month, day)
+            } else {
+                format!("BDAY:{:04}-{:02}-{:02}", year, month, day)
+            });
+        }
+
+        if let Some(note) = &contact.note {
+            lines.push(format!("NOTE:{}", escape_text(note)));
+        }
+
+        lines.push("END:VCARD".to_string());
+
+        Ok(content_lines(&lines))
+    }
+}
+
+/**
+ * A contact card in the compact MeCard format, it holds fewer fields than a vCard but produces
+ * smaller codes
+ *
+ * MECARD:N:Doe,Jane;TEL:+15550100;EMAIL:jane@example.com;;
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MeCard {
+    pub contact: Contact,
+}
+
+impl MeCard {
+    pub fn new(contact: Contact) -> MeCard {
+        MeCard { contact }
+    }
+
+    /**
+     * Reads a MeCard, a name without a comma is taken as the first name
+     */
+    pub fn parse(text: &str) -> Result<MeCard, String> {
+        let fields = strip_prefix_ignore_case(text.trim(), "MECARD:")
+            .ok_or_else(|| "MeCards must start with MECARD:".to_string())?;
+
+        let mut contact = Contact::default();
+
+        for field in split_escaped(fields, ';') {
+            let Some((key, value)) = field.split_once(':') else {
+                continue;
+            };
+
+            let components: Vec<String> = split_escaped(value, ',')
+                .into_iter()
+                .map(unescape_backslash)
+                .collect();
+            let value = unescape_backslash(value);
+
+            match key.to_ascii_uppercase().as_str() {
+                "N" => match &components[..] {
+                    [last_name, first_name, ..] => {
+                        contact.last_name = last_name.clone();
+                        contact.first_name = first_name.clone();
+                    }
+                    _ => contact.first_name = value,
+                },
+                "NICKNAME" => contact.nickname = Some(value),
+                "ORG" => contact.organization = Some(value),
+                "TEL" => contact.phones.push((PhoneType::Other, value)),
+                "EMAIL" => contact.emails.push(value),
+                "URL" => contact.url = Some(value),
+                "ADR" => {
+                    let component = |i: usize| components.get(i).cloned().unwrap_or_default();
+
+                    contact.address = Some(Address {
+                        street: component(2),
+                        city: component(3),
+                        region: component(4),
+                        postal_code: component(5),
+                        country: component(6),
+                    });
+                }
+                "BDAY" => contact.birthday = parse_date(&value),
+                "NOTE" => contact.note = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(MeCard { contact })
+    }
+}
+
+impl Payload for MeCard {
+    fn to_payload_string(&self) -> Result<String, String> {
+        let contact = &self.contact;
+        contact.validate()?;
+
+        let escape = |value: &str| escape_with_backslash(value, &[';', ',', ':', '"']);
+        let mut payload = String::from("MECARD:");
+
+        if contact.first_name.is_empty() || contact.last_name.is_empty() {
+            payload += &format!("N:{};", escape(&contact.full_name()));
+        } else {
+            payload += &format!(
+                "N:{},{};",
+                escape(&contact.last_name),
+                escape(&contact.first_name)
+            );
+        }
+
+        if let Some(nickname) = &contact.nickname {
+            payload += &format!("NICKNAME:{};", escape(nickname));
+        }
+
+        if let Some(organization) = &contact.organization {
+            payload += &format!("ORG:{};", escape(organization));
+        }
+
+        for (_, number) in &contact.phones {
+            payload += &format!("TEL:{};", escape(number));
+        }
+
+        for email in &contact.emails {
+            payload += &format!("EMAIL:{};", escape(email));
+        }
+
+        if let Some(url) = &contact.url {
+            payload += &format!("URL:{};", escape(url));
+        }
+
+        if let Some(address) = &contact.address {
+            payload += &format!(
+                "ADR:,,{},{},{},{},{};",
+                escape(&address.street),
+                escape(&address.city),
+                escape(&address.region),
+                escape(&address.postal_code),
+                escape(&address.country)
+            );
+        }
+
+        if let Some((year, month, day)) = contact.birthday {
+            payload += &format!("BDAY:{:04}{:02}{:02};", year, month, day);
+        }
+
+        if let Some(note) = &contact.note {
+            payload += &format!("NOTE:{};", escape(note));
+        }
+
+        payload += ";";
+
+        Ok(payload)
+    }
+}
+
+/**
+ * Splits a content line such as item1.TEL;TYPE=work,voice:+15550100 into the property name without
+ * its group, the TYPE parameter values and the value. vCard 2.1 types without TYPE= are included.
+ */
+fn split_content_line(line: &str) -> Option<(String, Vec<String>, &str)> {
+    let mut in_quotes = false;
+    let separator = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (name_and_parameters, value) = (&line[..separator], &line[separator + 1..]);
+    let mut parameters = name_and_parameters.split(';');
+
+    let name = parameters.next()?;
+    let name = name.rsplit('.').next().unwrap_or(name).to_ascii_uppercase();
+
+    let types = parameters
+        .flat_map(|parameter| match parameter.split_once('=') {
+            Some((key, values)) if key.eq_ignore_ascii_case("TYPE") => values
+                .trim_matches('"')
+                .split(',')
+                .map(|value| value.to_string())
+                .collect(),
+            Some(_) => Vec::new(),
+            None => vec![parameter.to_string()],
+        })
+        .collect();
+
+    Some((name, types, value))
+}
+
+/**
+ * Reads YYYYMMDD or YYYY-MM-DD, ignoring a time after the date
+ */
+fn parse_date(value: &str) -> Option<(u16, u8, u8)> {
+    let date: String = value
+        .split('T')
+        .next()?
+        .chars()
+        .filter(|c| *c != '-')
+        .collect();
+
+    if date.len() != 8 || !date.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let year = date[..4].parse().ok()?;
+    let month = date[4..6].parse().ok()?;
+    let day = date[6..].parse().ok()?;
+
+    is_valid_date(year, month, day).then_some((year, month, day))
+}
+
+pub fn is_valid_date(year: u16, month: u8, day: u8) -> bool {
+    let leap_year =
+        (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400);
+
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap_year => 29,
+        2 => 28,
+        _ => return false,
+    };
+
+    day >= 1 && day <= days_in_month
+}
@@ -0,0 +1,90 @@
+use crate::qr_code::payload::{strip_prefix_ignore_case, Payload};
+
+/**
+ * A point on the map, opened by map apps
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc5870
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeoLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: Option<f64>,
+}
+
+impl GeoLocation {
+    pub fn new(latitude: f64, longitude: f64) -> GeoLocation {
+        GeoLocation {
+            latitude,
+            longitude,
+            altitude: None,
+        }
+    }
+
+    /**
+     * Altitude in meters
+     */
+    pub fn altitude(mut self, altitude: f64) -> GeoLocation {
+        self.altitude = Some(altitude);
+        self
+    }
+
+    /**
+     * Reads the coordinates from a geo: URI, parameters such as the uncertainty and queries added
+     * by map apps are ignored
+     */
+    pub fn parse(text: &str) -> Result<GeoLocation, String> {
+        let coordinates = strip_prefix_ignore_case(text, "geo:")
+            .ok_or_else(|| "Locations must start with geo:".to_string())?;
+
+        let coordinates = coordinates
+            .split([';', '?'])
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(|coordinate| {
+                coordinate
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| format!("{} is not a valid coordinate", coordinate))
+            })
+            .collect::<Result<Vec<f64>, String>>()?;
+
+        match coordinates[..] {
+            [latitude, longitude] => Ok(GeoLocation::new(latitude, longitude)),
+            [latitude, longitude, altitude] => {
+                Ok(GeoLocation::new(latitude, longitude).altitude(altitude))
+            }
+            _ => Err(format!("{} does not have 2 or 3 coordinates", text)),
+        }
+    }
+}
+
+impl Payload for GeoLocation {
+    fn to_payload_string(&self) -> Result<String, String> {
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(format!(
+                "Latitude {} must be between -90 and 90",
+                self.latitude
+            ));
+        }
+
+        if !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(format!(
+                "Longitude {} must be between -180 and 180",
+                self.longitude
+            ));
+        }
+
+        match self.altitude {
+            Some(altitude) if !altitude.is_finite() => {
+                Err(format!("Altitude {} must be a finite number", altitude))
+            }
+            Some(altitude) => Ok(format!(
+                "geo:{},{},{}",
+                self.latitude, self.longitude, altitude
+            )),
+            None => Ok(format!("geo:{},{}", self.latitude, self.longitude)),
+        }
+    }
+}
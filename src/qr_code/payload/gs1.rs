@@ -0,0 +1,297 @@
+/**
+ * The group separator that ends a variable length element string, it stands in for FNC1 in the
+ * scanned text
+ */
+const GROUP_SEPARATOR: char = '\u{1d}';
+
+/**
+ * Symbology identifiers that scanners put in front of GS1 data, ]Q3 for QR codes
+ */
+const SYMBOLOGY_IDENTIFIERS: [&str; 4] = ["]Q3", "]C1", "]d2", "]e0"];
+
+/**
+ * The data titles of common application identifiers, 4 digit measurement identifiers are looked up
+ * by their first 3 digits
+ */
+const DATA_TITLES: [(&str, &str); 33] = [
+    ("00", "SSCC"),
+    ("01", "GTIN"),
+    ("02", "CONTENT"),
+    ("10", "BATCH/LOT"),
+    ("11", "PROD DATE"),
+    ("12", "DUE DATE"),
+    ("13", "PACK DATE"),
+    ("15", "BEST BEFORE or BEST BY"),
+    ("16", "SELL BY"),
+    ("17", "USE BY or EXPIRY"),
+    ("20", "VARIANT"),
+    ("21", "SERIAL"),
+    ("22", "CPV"),
+    ("240", "ADDITIONAL ID"),
+    ("241", "CUST. PART No."),
+    ("250", "SECONDARY SERIAL"),
+    ("30", "VAR. COUNT"),
+    ("310", "NET WEIGHT (kg)"),
+    ("320", "NET WEIGHT (lb)"),
+    ("330", "GROSS WEIGHT (kg)"),
+    ("37", "COUNT"),
+    ("400", "ORDER NUMBER"),
+    ("401", "GINC"),
+    ("402", "GSIN"),
+    ("410", "SHIP TO LOC"),
+    ("411", "BILL TO"),
+    ("412", "PURCHASE FROM"),
+    ("413", "SHIP FOR LOC"),
+    ("414", "LOC No."),
+    ("420", "SHIP TO POST"),
+    ("422", "ORIGIN"),
+    ("8004", "GIAI"),
+    ("8018", "GSRN - PROVIDER"),
+];
+
+/**
+ * One application identifier and its data, such as (01) 09501101530003
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gs1Element {
+    pub application_identifier: String,
+    pub value: String,
+}
+
+impl Gs1Element {
+    /**
+     * The short name GS1 gives the application identifier, such as SSCC for 00
+     */
+    pub fn data_title(&self) -> Option<&'static str> {
+        let ai = self.application_identifier.as_str();
+        let measurement = ai.len() == 4
+            && ai[..2]
+                .parse::<u8>()
+                .is_ok_and(|prefix| (31..=36).contains(&prefix));
+
+        DATA_TITLES
+            .iter()
+            .find(|(title_ai, _)| *title_ai == ai || (measurement && ai.starts_with(title_ai)))
+            .map(|(_, title)| *title)
+    }
+}
+
+/**
+ * The application identifiers and data in a GS1 QR code, as found on shipping labels and product
+ * packaging
+ *
+ * based on the GS1 General Specifications https://www.gs1.org/genspecs
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gs1ElementString {
+    pub elements: Vec<Gs1Element>,
+}
+
+impl Gs1ElementString {
+    /**
+     * Reads the scanned text with a symbology identifier such as ]Q3 or a leading group separator,
+     * or the human readable form with application identifiers in brackets
+     *
+     * ]Q3010950110153000310AB-123 or (01)09501101530003(10)AB-123
+     */
+    pub fn parse(text: &str) -> Result<Gs1ElementString, String> {
+        let elements = if text.starts_with('(') {
+            parse_bracketed(text)?
+        } else {
+            let data = SYMBOLOGY_IDENTIFIERS
+                .iter()
+                .find_map(|identifier| text.strip_prefix(identifier))
+                .or_else(|| text.strip_prefix(GROUP_SEPARATOR))
+                .ok_or_else(|| "GS1 element strings must start with ]Q3 or FNC1".to_string())?;
+
+            parse_element_string(data)?
+        };
+
+        if elements.is_empty() {
+            return Err("The GS1 element string is empty".to_string());
+        }
+
+        for element in &elements {
+            validate_element(element)?;
+        }
+
+        Ok(Gs1ElementString { elements })
+    }
+
+    /**
+     * The data of the first element with the application identifier, get("00") is the SSCC
+     */
+    pub fn get(&self, application_identifier: &str) -> Option<&str> {
+        self.elements
+            .iter()
+            .find(|element| element.application_identifier == application_identifier)
+            .map(|element| element.value.as_str())
+    }
+}
+
+/**
+ * Whether the text starts with a symbology identifier, a group separator or a known application
+ * identifier in brackets, so "(555) 0100" is not mistaken for GS1 data
+ */
+pub fn is_gs1_element_string(text: &str) -> bool {
+    if let Some(bracketed) = text.strip_prefix('(') {
+        return bracketed.split_once(')').is_some_and(|(ai, _)| {
+            ai.chars().all(|c| c.is_ascii_digit())
+                && application_identifier_len(ai.get(..2).unwrap_or("")) == Some(ai.len())
+        });
+    }
+
+    SYMBOLOGY_IDENTIFIERS
+        .iter()
+        .any(|identifier| text.starts_with(identifier))
+        || text.starts_with(GROUP_SEPARATOR)
+}
+
+/**
+ * How many digits the application identifier starting with these two digits has
+ */
+fn application_identifier_len(prefix: &str) -> Option<usize> {
+    if prefix.len() != 2 || !prefix.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    match prefix.parse::<u8>().ok()? {
+        0..=4 | 10..=22 | 30 | 37 | 90..=99 => Some(2),
+        23..=25 | 40..=42 | 71 => Some(3),
+        31..=36 | 39 | 43 | 70 | 72 | 80..=82 => Some(4),
+        _ => None,
+    }
+}
+
+/**
+ * Element strings with a predefined length do not need a separator after them, these lengths are
+ * fixed by the specification and do not change with new application identifiers
+ */
+fn predefined_len(prefix: &str) -> Option<usize> {
+    match prefix.parse::<u8>().ok()? {
+        0 => Some(18),
+        1..=3 => Some(14),
+        4 => Some(16),
+        11..=19 | 31..=36 => Some(6),
+        20 => Some(2),
+        41 => Some(13),
+        _ => None,
+    }
+}
+
+fn parse_element_string(data: &str) -> Result<Vec<Gs1Element>, String> {
+    let mut elements = Vec::new();
+    let mut rest = data;
+
+    while !rest.is_empty() {
+        let prefix = rest.get(..2).unwrap_or(rest);
+        let ai_len = application_identifier_len(prefix)
+            .ok_or_else(|| format!("Unknown GS1 application identifier at {}", rest))?;
+
+        let application_identifier = rest
+            .get(..ai_len)
+            .filter(|ai| ai.chars().all(|c| c.is_ascii_digit()))
+            .ok_or_else(|| format!("Incomplete GS1 application identifier {}", rest))?;
+        rest = &rest[ai_len..];
+
+        let value_len = match predefined_len(prefix) {
+            Some(len) => len.min(rest.len()),
+            None => rest.find(GROUP_SEPARATOR).unwrap_or(rest.len()),
+        };
+
+        let value = rest
+            .get(..value_len)
+            .ok_or_else(|| format!("Invalid data for GS1 application identifier {}", prefix))?;
+
+        elements.push(Gs1Element {
+            application_identifier: application_identifier.to_string(),
+            value: value.to_string(),
+        });
+
+        rest = rest[value_len..].trim_start_matches(GROUP_SEPARATOR);
+    }
+
+    Ok(elements)
+}
+
+fn parse_bracketed(text: &str) -> Result<Vec<Gs1Element>, String> {
+    text[1..]
+        .split('(')
+        .map(|element| {
+            let (application_identifier, value) = element
+                .split_once(')')
+                .ok_or_else(|| format!("Missing ) after GS1 application identifier {}", element))?;
+
+            let known = application_identifier.chars().all(|c| c.is_ascii_digit())
+                && application_identifier_len(application_identifier.get(..2).unwrap_or(""))
+                    == Some(application_identifier.len());
+
+            if !known {
+                return Err(format!(
+                    "Unknown GS1 application identifier {}",
+                    application_identifier
+                ));
+            }
+
+            Ok(Gs1Element {
+                application_identifier: application_identifier.to_string(),
+                value: value.to_string(),
+            })
+        })
+        .collect()
+}
+
+/**
+ * Checks the length of predefined length elements and the check digit of GS1 keys
+ */
+fn validate_element(element: &Gs1Element) -> Result<(), String> {
+    let ai = element.application_identifier.as_str();
+    let value = element.value.as_str();
+
+    if let Some(len) = predefined_len(&ai[..2]) {
+        if value.len() != len || !value.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("({}) must be {} digits, found {}", ai, len, value));
+        }
+    }
+
+    let has_check_digit = matches!(
+        ai,
+        "00" | "01" | "02" | "410" | "411" | "412" | "413" | "414"
+    );
+
+    if has_check_digit && !has_valid_check_digit(value) {
+        return Err(format!("({}) {} has an invalid check digit", ai, value));
+    }
+
+    if value.is_empty() || value.len() > 90 {
+        return Err(format!("({}) must have between 1 and 90 characters", ai));
+    }
+
+    Ok(())
+}
+
+/**
+ * The last digit of a GS1 key makes the sum of the digits, weighted 3 and 1 from the right, a
+ * multiple of 10
+ */
+fn has_valid_check_digit(digits: &str) -> bool {
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .enumerate()
+        .map(|(i, c)| c.to_digit(10).unwrap_or(0) * if i % 2 == 1 { 3 } else { 1 })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+#[test]
+fn test_parse_element_string() {
+    let element_string =
+        Gs1ElementString::parse("]Q3010950110153000317140704\u{1d}10AB-123\u{1d}21XYZ").unwrap();
+
+    assert_eq!(element_string.get("01"), Some("09501101530003"));
+    assert_eq!(element_string.get("17"), Some("140704"));
+    assert_eq!(element_string.get("10"), Some("AB-123"));
+    assert_eq!(element_string.get("21"), Some("XYZ"));
+}
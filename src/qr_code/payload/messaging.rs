@@ -0,0 +1,280 @@
+use crate::qr_code::payload::{percent_decode, percent_encode, strip_prefix_ignore_case, Payload};
+
+/**
+ * A phone number to call
+ *
+ * tel:+1-555-0100
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc3966
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Telephone {
+    pub number: String,
+}
+
+impl Telephone {
+    pub fn new(number: &str) -> Telephone {
+        Telephone {
+            number: number.to_string(),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Telephone, String> {
+        let number = strip_prefix_ignore_case(text, "tel:")
+            .ok_or_else(|| "Phone numbers must start with tel:".to_string())?;
+
+        Ok(Telephone::new(&percent_decode(number)?))
+    }
+}
+
+impl Payload for Telephone {
+    fn to_payload_string(&self) -> Result<String, String> {
+        Ok(format!("tel:{}", normalize_phone_number(&self.number)?))
+    }
+}
+
+/**
+ * A text message ready to send, most scanner apps open the messaging app with the number and
+ * message filled in
+ *
+ * SMSTO:+15550100:Hello
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sms {
+    pub number: String,
+    pub message: Option<String>,
+}
+
+impl Sms {
+    pub fn new(number: &str) -> Sms {
+        Sms {
+            number: number.to_string(),
+            message: None,
+        }
+    }
+
+    pub fn message(mut self, message: &str) -> Sms {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /**
+     * Reads SMSTO:number:message as well as the sms:number?body=message URI
+     *
+     * based on https://www.rfc-editor.org/rfc/rfc5724
+     */
+    pub fn parse(text: &str) -> Result<Sms, String> {
+        if let Some(fields) = strip_prefix_ignore_case(text, "SMSTO:") {
+            let (number, message) = fields.split_once(':').unwrap_or((fields, ""));
+            let sms = Sms::new(number);
+
+            return Ok(match message {
+                "" => sms,
+                message => sms.message(message),
+            });
+        }
+
+        let fields = strip_prefix_ignore_case(text, "sms:")
+            .ok_or_else(|| "Text messages must start with SMSTO: or sms:".to_string())?;
+        let (number, query) = fields.split_once('?').unwrap_or((fields, ""));
+        let mut sms = Sms::new(&percent_decode(number)?);
+
+        for (key, value) in query.split('&').filter_map(|field| field.split_once('=')) {
+            if key.eq_ignore_ascii_case("body") {
+                sms.message = Some(percent_decode(value)?);
+            }
+        }
+
+        Ok(sms)
+    }
+}
+
+impl Payload for Sms {
+    fn to_payload_string(&self) -> Result<String, String> {
+        let number = normalize_phone_number(&self.number)?;
+
+        // the message is everything after the second colon so it needs no escaping
+        match &self.message {
+            Some(message) => Ok(format!("SMSTO:{}:{}", number, message)),
+            None => Ok(format!("SMSTO:{}:", number)),
+        }
+    }
+}
+
+/**
+ * An email ready to send
+ *
+ * mailto:someone@example.com?subject=Hello%20there
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc6068
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Email {
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub bcc: Vec<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+}
+
+impl Email {
+    pub fn new(to: &str) -> Email {
+        Email {
+            to: vec![to.to_string()],
+            cc: Vec::new(),
+            bcc: Vec::new(),
+            subject: None,
+            body: None,
+        }
+    }
+
+    pub fn to(mut self, address: &str) -> Email {
+        self.to.push(address.to_string());
+        self
+    }
+
+    pub fn cc(mut self, address: &str) -> Email {
+        self.cc.push(address.to_string());
+        self
+    }
+
+    pub fn bcc(mut self, address: &str) -> Email {
+        self.bcc.push(address.to_string());
+        self
+    }
+
+    pub fn subject(mut self, subject: &str) -> Email {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    pub fn body(mut self, body: &str) -> Email {
+        self.body = Some(body.to_string());
+        self
+    }
+
+    /**
+     * Reads a mailto: URI, line breaks in the body become \n
+     */
+    pub fn parse(text: &str) -> Result<Email, String> {
+        let fields = strip_prefix_ignore_case(text, "mailto:")
+            .ok_or_else(|| "Emails must start with mailto:".to_string())?;
+        let (to, query) = fields.split_once('?').unwrap_or((fields, ""));
+
+        let mut email = Email {
+            to: decode_addresses(to)?,
+            cc: Vec::new(),
+            bcc: Vec::new(),
+            subject: None,
+            body: None,
+        };
+
+        for (key, value) in query.split('&').filter_map(|field| field.split_once('=')) {
+            match key.to_ascii_lowercase().as_str() {
+                "to" => email.to.extend(decode_addresses(value)?),
+                "cc" => email.cc.extend(decode_addresses(value)?),
+                "bcc" => email.bcc.extend(decode_addresses(value)?),
+                "subject" => email.subject = Some(percent_decode(value)?),
+                "body" => email.body = Some(percent_decode(value)?.replace("\r\n", "\n")),
+                _ => {}
+            }
+        }
+
+        Ok(email)
+    }
+}
+
+impl Payload for Email {
+    fn to_payload_string(&self) -> Result<String, String> {
+        let mut payload = format!("mailto:{}", encode_addresses(&self.to)?);
+        let mut fields: Vec<String> = Vec::new();
+
+        if !self.cc.is_empty() {
+            fields.push(format!("cc={}", encode_addresses(&self.cc)?));
+        }
+
+        if !self.bcc.is_empty() {
+            fields.push(format!("bcc={}", encode_addresses(&self.bcc)?));
+        }
+
+        if let Some(subject) = &self.subject {
+            fields.push(format!("subject={}", percent_encode(subject)));
+        }
+
+        if let Some(body) = &self.body {
+            // line breaks in the body must be CRLF
+            let body = body.replace("\r\n", "\n").replace('\n', "\r\n");
+            fields.push(format!("body={}", percent_encode(&body)));
+        }
+
+        if !fields.is_empty() {
+            payload += "?";
+            payload += &fields.join("&");
+        }
+
+        Ok(payload)
+    }
+}
+
+fn encode_addresses(addresses: &[String]) -> Result<String, String> {
+    let encoded = addresses
+        .iter()
+        .map(|address| encode_address(address))
+        .collect::<Result<Vec<String>, String>>()?;
+
+    Ok(encoded.join(","))
+}
+
+fn decode_addresses(addresses: &str) -> Result<Vec<String>, String> {
+    addresses
+        .split(',')
+        .filter(|address| !address.is_empty())
+        .map(percent_decode)
+        .collect()
+}
+
+/**
+ * Percent encodes an address, leaving the characters that are common in addresses and allowed by
+ * the mailto scheme as they are
+ */
+fn encode_address(address: &str) -> Result<String, String> {
+    let address = address.trim();
+
+    match address.split_once('@') {
+        Some((local, domain))
+            if !local.is_empty() && !domain.is_empty() && !domain.contains('@') => {}
+        _ => return Err(format!("\"{}\" is not an email address", address)),
+    }
+
+    let mut encoded = String::with_capacity(address.len());
+
+    for c in address.chars() {
+        match c {
+            '@' | '+' | '!' | '$' | '\'' | '*' => encoded.push(c),
+            _ => encoded += &percent_encode(&c.to_string()),
+        }
+    }
+
+    Ok(encoded)
+}
+
+/**
+ * Removes spaces and checks the number only contains digits and the visual separators - . ( ),
+ * with an optional leading +
+ */
+fn normalize_phone_number(number: &str) -> Result<String, String> {
+    let number: String = number.chars().filter(|c| !c.is_whitespace()).collect();
+
+    let digits = number.strip_prefix('+').unwrap_or(&number);
+
+    let valid = digits.chars().any(|c| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || "-.()".contains(c));
+
+    if !valid {
+        return Err(format!("\"{}\" is not a phone number", number));
+    }
+
+    Ok(number)
+}
@@ -0,0 +1,277 @@
+mod calendar;
+mod contact;
+mod geo;
+mod gs1;
+mod messaging;
+mod parse;
+mod payment;
+mod wifi;
+
+pub use calendar::{CalendarEvent, EventTime};
+pub use contact::{Address, Contact, MeCard, PhoneType, VCard, VCardVersion};
+pub use geo::GeoLocation;
+pub use gs1::{Gs1Element, Gs1ElementString};
+pub use messaging::{Email, Sms, Telephone};
+pub use parse::{parse_payload, ParsedPayload};
+pub use payment::{
+    MerchantAccount, MerchantPayment, SepaCreditTransfer, SwissAddress, SwissCurrency, SwissQrBill,
+    SwissReference,
+};
+pub use wifi::{WifiNetwork, WifiSecurity};
+
+use crate::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode};
+
+/**
+ * Structured content that scanner apps recognise, such as a WiFi network or a contact card
+ */
+pub trait Payload {
+    /**
+     * Builds the text stored in the QR code, with every field escaped for the format
+     */
+    fn to_payload_string(&self) -> Result<String, String>;
+
+    /**
+     * The most compact encoding mode for the payload text
+     */
+    fn encoding_mode(&self) -> Result<EncodingMode, String> {
+        Ok(EncodingMode::detect(&self.to_payload_string()?))
+    }
+
+    fn to_qr_code(&self, error_correction_level: ErrorCorrectionLevel) -> Result<QrCode, String> {
+        QrCode::create(
+            self.to_payload_string()?,
+            self.encoding_mode()?,
+            error_correction_level,
+        )
+    }
+}
+
+/**
+ * Puts a backslash in front of every special character and the backslash itself
+ */
+fn escape_with_backslash(value: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if c == '\\' || special.contains(&c) {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/**
+ * Escapes a TEXT value for vCard and iCalendar, line breaks become \n
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc6350#section-3.4
+ */
+fn escape_text(value: &str) -> String {
+    escape_with_backslash(&value.replace("\r\n", "\n"), &[',', ';']).replace('\n', "\\n")
+}
+
+/**
+ * Splits content lines longer than 75 octets by inserting a line break followed by a space, never
+ * in the middle of a multi-byte character
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc5545#section-3.1
+ */
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut line_len = 0;
+
+    for c in line.chars() {
+        if line_len + c.len_utf8() > 75 {
+            folded += "\r\n ";
+            line_len = 1;
+        }
+
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+
+    folded
+}
+
+/**
+ * Joins content lines with the CRLF line endings vCard and iCalendar require
+ */
+fn content_lines(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+}
+
+/**
+ * Percent encodes everything except unreserved characters
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc3986#section-2.3
+ */
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded += &format!("%{:02X}", byte),
+        }
+    }
+
+    encoded
+}
+
+/**
+ * Splits on every separator that is not escaped with a backslash, the escapes are kept so each part
+ * can be unescaped for its own format
+ */
+fn split_escaped(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == separator {
+            parts.push(&value[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+
+    parts.push(&value[start..]);
+    parts
+}
+
+/**
+ * Removes the backslashes added by escape_with_backslash
+ */
+fn unescape_backslash(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+/**
+ * Reverses escape_text, \n and \N become line breaks
+ */
+fn unescape_text(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n' | 'N')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some(escaped)) => {
+                unescaped.push(escaped);
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+/**
+ * Splits text into content lines, joining folded lines back together
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc6350#section-3.2
+ */
+fn unfold_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => *last += continuation,
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+/**
+ * Reverses percent_encode, failing on a % that is not followed by two hex digits or bytes that are
+ * not UTF-8
+ */
+fn percent_decode(value: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+
+    while let Some(byte) = input.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+
+        let hex = [input.next(), input.next()];
+        let decoded = match hex {
+            [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match decoded {
+            Some(decoded) => bytes.push(decoded),
+            None => return Err(format!("Invalid percent encoding in {}", value)),
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| format!("{} does not decode to UTF-8 text", value))
+}
+
+/**
+ * Removes a prefix such as a URI scheme, ignoring the case of the prefix
+ */
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    match value.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&value[prefix.len()..]),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_fold_line() {
+    let line = format!("NOTE:{}", "é".repeat(40));
+    let folded = fold_line(&line);
+
+    assert!(folded
+        .split("\r\n")
+        .all(|folded_line| folded_line.len() <= 75));
+    assert_eq!(folded.replace("\r\n ", ""), line);
+}
+
+#[test]
+fn test_unescape() {
+    let escaped = escape_with_backslash("a;b\\c:d", &[';', ':']);
+    let fields = format!("{};e", escaped);
+
+    assert_eq!(split_escaped(&fields, ';'), vec![escaped.as_str(), "e"]);
+    assert_eq!(unescape_backslash(&escaped), "a;b\\c:d");
+    assert_eq!(
+        unescape_text(&escape_text("one, two\nthree")),
+        "one, two\nthree"
+    );
+    assert_eq!(percent_decode(&percent_encode("a b&ü")).unwrap(), "a b&ü");
+    assert!(percent_decode("%G1").is_err());
+}
@@ -0,0 +1,71 @@
+use crate::qr_code::payload::gs1::is_gs1_element_string;
+use crate::qr_code::payload::{
+    strip_prefix_ignore_case, Email, GeoLocation, Gs1ElementString, MeCard, MerchantPayment,
+    SepaCreditTransfer, Sms, Telephone, VCard, WifiNetwork,
+};
+
+/**
+ * What the text in a scanned QR code means
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParsedPayload {
+    Wifi(WifiNetwork),
+    VCard(VCard),
+    MeCard(MeCard),
+    Url(String),
+    Geo(GeoLocation),
+    Sms(Sms),
+    Telephone(Telephone),
+    Email(Email),
+    SepaCreditTransfer(SepaCreditTransfer),
+    MerchantPayment(MerchantPayment),
+    Gs1(Gs1ElementString),
+    Text(String),
+}
+
+/**
+ * Detects the format of decoded text from its prefix and reads it into the matching payload.
+ *
+ * Text that starts like a known format but cannot be read is an error, anything else is returned
+ * as plain text.
+ */
+pub fn parse_payload(text: &str) -> Result<ParsedPayload, String> {
+    let starts_with = |prefix: &str| strip_prefix_ignore_case(text, prefix).is_some();
+
+    if starts_with("WIFI:") {
+        WifiNetwork::parse(text).map(ParsedPayload::Wifi)
+    } else if starts_with("BEGIN:VCARD") {
+        VCard::parse(text).map(ParsedPayload::VCard)
+    } else if starts_with("MECARD:") {
+        MeCard::parse(text).map(ParsedPayload::MeCard)
+    } else if starts_with("http://") || starts_with("https://") {
+        Ok(ParsedPayload::Url(text.to_string()))
+    } else if starts_with("geo:") {
+        GeoLocation::parse(text).map(ParsedPayload::Geo)
+    } else if starts_with("SMSTO:") || starts_with("sms:") {
+        Sms::parse(text).map(ParsedPayload::Sms)
+    } else if starts_with("tel:") {
+        Telephone::parse(text).map(ParsedPayload::Telephone)
+    } else if starts_with("mailto:") {
+        Email::parse(text).map(ParsedPayload::Email)
+    } else if text.starts_with("BCD\n") || text.starts_with("BCD\r\n") {
+        SepaCreditTransfer::parse(text).map(ParsedPayload::SepaCreditTransfer)
+    } else if text.starts_with("000201") && is_emvco_crc_field(text) {
+        MerchantPayment::parse(text).map(ParsedPayload::MerchantPayment)
+    } else if is_gs1_element_string(text) {
+        Gs1ElementString::parse(text).map(ParsedPayload::Gs1)
+    } else {
+        Ok(ParsedPayload::Text(text.to_string()))
+    }
+}
+
+/**
+ * EMVCo merchant codes end with the CRC field 6304 and 4 hex digits, numeric text that happens to
+ * start with 000201 does not
+ */
+fn is_emvco_crc_field(text: &str) -> bool {
+    text.len()
+        .checked_sub(8)
+        .and_then(|start| text.get(start..))
+        .is_some_and(|crc| crc.starts_with("6304"))
+}
@@ -0,0 +1,369 @@
+use crate::qr_code::payload::payment::{format_amount, parse_amount, validate_field};
+use crate::qr_code::payload::Payload;
+
+/**
+ * How a payment network identifies the merchant, a template of sub fields where field 00 is the
+ * globally unique identifier of the network
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerchantAccount {
+    /// 26 to 51, each network is assigned an id by EMVCo or the local scheme
+    pub id: u8,
+    pub globally_unique_identifier: String,
+    pub fields: Vec<(u8, String)>,
+}
+
+impl MerchantAccount {
+    pub fn new(id: u8, globally_unique_identifier: &str) -> MerchantAccount {
+        MerchantAccount {
+            id,
+            globally_unique_identifier: globally_unique_identifier.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /**
+     * Adds a network specific sub field, such as the merchant id
+     */
+    pub fn field(mut self, id: u8, value: &str) -> MerchantAccount {
+        self.fields.push((id, value.to_string()));
+        self
+    }
+}
+
+/**
+ * A merchant presented QR code that payment apps of the listed networks can pay
+ *
+ * based on EMV QR Code Specification for Payment Systems, Merchant-Presented Mode
+ * https://www.emvco.com/emv-technologies/qr-codes/
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerchantPayment {
+    pub accounts: Vec<MerchantAccount>,
+    pub merchant_category_code: String,
+    /// ISO 4217 numeric currency code, 978 for euro
+    pub currency: String,
+    /// ISO 3166-1 two letter country code
+    pub country: String,
+    pub merchant_name: String,
+    pub merchant_city: String,
+    pub postal_code: Option<String>,
+    pub amount: Option<String>,
+    pub bill_number: Option<String>,
+    pub reference_label: Option<String>,
+}
+
+impl MerchantPayment {
+    pub fn new(
+        merchant_name: &str,
+        merchant_city: &str,
+        country: &str,
+        merchant_category_code: &str,
+        currency: &str,
+    ) -> MerchantPayment {
+        MerchantPayment {
+            accounts: Vec::new(),
+            merchant_category_code: merchant_category_code.to_string(),
+            currency: currency.to_string(),
+            country: country.to_string(),
+            merchant_name: merchant_name.to_string(),
+            merchant_city: merchant_city.to_string(),
+            postal_code: None,
+            amount: None,
+            bill_number: None,
+            reference_label: None,
+        }
+    }
+
+    pub fn account(mut self, account: MerchantAccount) -> MerchantPayment {
+        self.accounts.push(account);
+        self
+    }
+
+    pub fn postal_code(mut self, postal_code: &str) -> MerchantPayment {
+        self.postal_code = Some(postal_code.to_string());
+        self
+    }
+
+    /**
+     * A fixed amount makes the code dynamic, meant to be used for a single payment
+     */
+    pub fn amount(mut self, amount: &str) -> MerchantPayment {
+        self.amount = Some(amount.to_string());
+        self
+    }
+
+    pub fn bill_number(mut self, bill_number: &str) -> MerchantPayment {
+        self.bill_number = Some(bill_number.to_string());
+        self
+    }
+
+    pub fn reference_label(mut self, reference_label: &str) -> MerchantPayment {
+        self.reference_label = Some(reference_label.to_string());
+        self
+    }
+
+    /**
+     * Reads a scanned merchant code after checking its CRC, fields this crate does not build such
+     * as tips and card network ids 02 to 25 are ignored
+     */
+    pub fn parse(text: &str) -> Result<MerchantPayment, String> {
+        let (checked, crc) = text
+            .len()
+            .checked_sub(4)
+            .filter(|&split| text.is_char_boundary(split))
+            .map(|split| text.split_at(split))
+            .ok_or_else(|| "The merchant payment is too short".to_string())?;
+
+        if !text.starts_with("000201") || !checked.ends_with("6304") {
+            return Err("Merchant payments must start with 000201 and end with a CRC".to_string());
+        }
+
+        if u16::from_str_radix(crc, 16).ok() != Some(crc16_ccitt(checked.as_bytes())) {
+            return Err(format!("CRC {} does not match the merchant payment", crc));
+        }
+
+        let mut payment = MerchantPayment::new("", "", "", "", "");
+
+        for (id, value) in parse_tlv(text)? {
+            match id {
+                26..=51 => {
+                    let mut account = MerchantAccount::new(id, "");
+
+                    for (field_id, field_value) in parse_tlv(&value)? {
+                        match field_id {
+                            0 => account.globally_unique_identifier = field_value,
+                            _ => account.fields.push((field_id, field_value)),
+                        }
+                    }
+
+                    payment.accounts.push(account);
+                }
+                52 => payment.merchant_category_code = value,
+                53 => payment.currency = value,
+                54 => payment.amount = Some(value),
+                58 => payment.country = value,
+                59 => payment.merchant_name = value,
+                60 => payment.merchant_city = value,
+                61 => payment.postal_code = Some(value),
+                62 => {
+                    for (field_id, field_value) in parse_tlv(&value)? {
+                        match field_id {
+                            1 => payment.bill_number = Some(field_value),
+                            5 => payment.reference_label = Some(field_value),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(payment)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.accounts.is_empty() {
+            return Err("A merchant payment needs at least one merchant account".to_string());
+        }
+
+        for account in &self.accounts {
+            if !(26..=51).contains(&account.id) {
+                return Err(format!(
+                    "Merchant account id {} must be between 26 and 51",
+                    account.id
+                ));
+            }
+
+            if account.globally_unique_identifier.is_empty() {
+                return Err("A merchant account needs a globally unique identifier".to_string());
+            }
+
+            if let Some((id, _)) = account.fields.iter().find(|(id, _)| !(1..=99).contains(id)) {
+                return Err(format!(
+                    "Merchant account field id {} must be between 1 and 99",
+                    id
+                ));
+            }
+        }
+
+        let is_digits = |value: &str, len: usize| {
+            value.len() == len && value.chars().all(|c| c.is_ascii_digit())
+        };
+
+        if !is_digits(&self.merchant_category_code, 4) {
+            return Err(format!(
+                "Merchant category code {} must be 4 digits",
+                self.merchant_category_code
+            ));
+        }
+
+        if !is_digits(&self.currency, 3) {
+            return Err(format!(
+                "Currency {} must be a 3 digit ISO 4217 code",
+                self.currency
+            ));
+        }
+
+        if self.country.len() != 2 || !self.country.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(format!(
+                "Country {} must be a two letter ISO 3166 code",
+                self.country
+            ));
+        }
+
+        if self.merchant_name.is_empty() || self.merchant_city.is_empty() {
+            return Err("A merchant payment needs a merchant name and city".to_string());
+        }
+
+        validate_field("Merchant name", &self.merchant_name, 25)?;
+        validate_field("Merchant city", &self.merchant_city, 15)?;
+
+        if let Some(postal_code) = &self.postal_code {
+            validate_field("Postal code", postal_code, 10)?;
+        }
+
+        if let Some(bill_number) = &self.bill_number {
+            validate_field("Bill number", bill_number, 25)?;
+        }
+
+        if let Some(reference_label) = &self.reference_label {
+            validate_field("Reference label", reference_label, 25)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Payload for MerchantPayment {
+    fn to_payload_string(&self) -> Result<String, String> {
+        self.validate()?;
+
+        let mut payload = String::new();
+
+        payload += &tlv(0, "01")?;
+        // 11 is a static code used for many payments, 12 a dynamic code for a single payment
+        payload += &tlv(1, if self.amount.is_some() { "12" } else { "11" })?;
+
+        for account in &self.accounts {
+            let mut template = tlv(0, &account.globally_unique_identifier)?;
+
+            for (id, value) in &account.fields {
+                template += &tlv(*id, value)?;
+            }
+
+            payload += &tlv(account.id, &template)?;
+        }
+
+        payload += &tlv(52, &self.merchant_category_code)?;
+        payload += &tlv(53, &self.currency)?;
+
+        if let Some(amount) = &self.amount {
+            payload += &tlv(54, &format_amount(parse_amount(amount)?))?;
+        }
+
+        payload += &tlv(58, &self.country)?;
+        payload += &tlv(59, &self.merchant_name)?;
+        payload += &tlv(60, &self.merchant_city)?;
+
+        if let Some(postal_code) = &self.postal_code {
+            payload += &tlv(61, postal_code)?;
+        }
+
+        let mut additional_data = String::new();
+
+        if let Some(bill_number) = &self.bill_number {
+            additional_data += &tlv(1, bill_number)?;
+        }
+
+        if let Some(reference_label) = &self.reference_label {
+            additional_data += &tlv(5, reference_label)?;
+        }
+
+        if !additional_data.is_empty() {
+            payload += &tlv(62, &additional_data)?;
+        }
+
+        // the checksum covers its own id and length
+        payload += "6304";
+        payload += &format!("{:04X}", crc16_ccitt(payload.as_bytes()));
+
+        Ok(payload)
+    }
+}
+
+/**
+ * Formats a field as a two digit id, a two digit length and the value
+ */
+fn tlv(id: u8, value: &str) -> Result<String, String> {
+    let len = value.chars().count();
+
+    if len > 99 {
+        return Err(format!(
+            "Field {:02} must be at most 99 characters, it is {}",
+            id, len
+        ));
+    }
+
+    Ok(format!("{:02}{:02}{}", id, len, value))
+}
+
+/**
+ * Splits a string of fields written by tlv back into ids and values
+ */
+fn parse_tlv(data: &str) -> Result<Vec<(u8, String)>, String> {
+    let mut chars = data.chars();
+    let mut fields = Vec::new();
+
+    while !chars.as_str().is_empty() {
+        let header: String = chars.by_ref().take(4).collect();
+
+        let (id, len) = match (header.get(..2), header.get(2..)) {
+            (Some(id), Some(len)) if header.len() == 4 => (id.parse::<u8>(), len.parse::<usize>()),
+            _ => return Err(format!("Field header {} is incomplete", header)),
+        };
+
+        let (Ok(id), Ok(len)) = (id, len) else {
+            return Err(format!("Field header {} must be 4 digits", header));
+        };
+
+        let value: String = chars.by_ref().take(len).collect();
+
+        if value.chars().count() != len {
+            return Err(format!(
+                "Field {:02} is shorter than its length {}",
+                id, len
+            ));
+        }
+
+        fields.push((id, value));
+    }
+
+    Ok(fields)
+}
+
+/**
+ * CRC-16/CCITT-FALSE, polynomial 0x1021 starting from 0xFFFF
+ */
+fn crc16_ccitt(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+#[test]
+fn test_crc16_ccitt() {
+    assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+}
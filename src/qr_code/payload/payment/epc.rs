@@ -0,0 +1,244 @@
+use crate::qr_code::payload::payment::{
+    create_payment_qr_code, format_amount, parse_amount, validate_field, validate_iban,
+};
+use crate::qr_code::payload::Payload;
+use crate::qr_code::{ErrorCorrectionLevel, QrCode};
+
+/**
+ * A SEPA credit transfer in euro, also known as a GiroCode, which banking apps turn into a
+ * prefilled transfer
+ *
+ * based on EPC069-12 https://www.europeanpaymentscouncil.eu/document-library/guidance-documents/quick-response-code-guidelines-enable-data-capture-initiation
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SepaCreditTransfer {
+    pub name: String,
+    pub iban: String,
+    pub bic: Option<String>,
+    pub amount: Option<String>,
+    pub purpose: Option<String>,
+    pub reference: Option<String>,
+    pub text: Option<String>,
+    pub information: Option<String>,
+}
+
+impl SepaCreditTransfer {
+    /**
+     * A transfer to the beneficiary, the payer fills in the amount unless `amount` is set
+     */
+    pub fn new(name: &str, iban: &str) -> SepaCreditTransfer {
+        SepaCreditTransfer {
+            name: name.to_string(),
+            iban: iban.to_string(),
+            bic: None,
+            amount: None,
+            purpose: None,
+            reference: None,
+            text: None,
+            information: None,
+        }
+    }
+
+    /**
+     * Only required for payments from outside the EEA
+     */
+    pub fn bic(mut self, bic: &str) -> SepaCreditTransfer {
+        self.bic = Some(bic.to_string());
+        self
+    }
+
+    /**
+     * Amount in euro, such as 12.30
+     */
+    pub fn amount(mut self, amount: &str) -> SepaCreditTransfer {
+        self.amount = Some(amount.to_string());
+        self
+    }
+
+    /**
+     * Four letter ISO 20022 purpose code, such as CHAR for a charity payment
+     */
+    pub fn purpose(mut self, purpose: &str) -> SepaCreditTransfer {
+        self.purpose = Some(purpose.to_string());
+        self
+    }
+
+    /**
+     * Structured creditor reference, cannot be combined with `text`
+     */
+    pub fn reference(mut self, reference: &str) -> SepaCreditTransfer {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    /**
+     * Unstructured remittance information, cannot be combined with `reference`
+     */
+    pub fn text(mut self, text: &str) -> SepaCreditTransfer {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /**
+     * A note from the beneficiary shown to the payer, not passed on with the transfer
+     */
+    pub fn information(mut self, information: &str) -> SepaCreditTransfer {
+        self.information = Some(information.to_string());
+        self
+    }
+
+    /**
+     * Reads the fields of a scanned transfer, the IBAN and amount are checked when the payload is
+     * built again with `to_payload_string`
+     */
+    pub fn parse(text: &str) -> Result<SepaCreditTransfer, String> {
+        let lines: Vec<&str> = text.lines().map(|line| line.trim_end()).collect();
+
+        if lines.first() != Some(&"BCD") {
+            return Err("SEPA credit transfers must start with BCD".to_string());
+        }
+
+        if !matches!(lines.get(1), Some(&"001" | &"002")) {
+            return Err("Only versions 001 and 002 of EPC069-12 are supported".to_string());
+        }
+
+        if lines.get(3) != Some(&"SCT") {
+            return Err("Only SEPA credit transfers (SCT) are supported".to_string());
+        }
+
+        let field = |i: usize| {
+            lines
+                .get(i)
+                .filter(|line| !line.is_empty())
+                .map(|line| line.to_string())
+        };
+
+        let (Some(name), Some(iban)) = (field(5), field(6)) else {
+            return Err("The SEPA credit transfer has no beneficiary name or IBAN".to_string());
+        };
+
+        let amount = match field(7) {
+            Some(amount) => Some(
+                amount
+                    .strip_prefix("EUR")
+                    .map(|amount| amount.to_string())
+                    .ok_or_else(|| format!("Amount {} must be in euro", amount))?,
+            ),
+            None => None,
+        };
+
+        Ok(SepaCreditTransfer {
+            name,
+            iban,
+            bic: field(4),
+            amount,
+            purpose: field(8),
+            reference: field(9),
+            text: field(10),
+            information: field(11),
+        })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("A SEPA credit transfer needs a beneficiary name".to_string());
+        }
+
+        validate_field("Beneficiary name", &self.name, 70)?;
+
+        if let Some(bic) = &self.bic {
+            let valid_bic = (bic.len() == 8 || bic.len() == 11)
+                && bic.chars().all(|c| c.is_ascii_alphanumeric())
+                && bic[..6].chars().all(|c| c.is_ascii_alphabetic());
+
+            if !valid_bic {
+                return Err(format!("{} is not a valid BIC", bic));
+            }
+        }
+
+        if let Some(purpose) = &self.purpose {
+            if purpose.len() != 4 || !purpose.chars().all(|c| c.is_ascii_uppercase()) {
+                return Err(format!(
+                    "Purpose code {} must be 4 uppercase letters",
+                    purpose
+                ));
+            }
+        }
+
+        if self.reference.is_some() && self.text.is_some() {
+            return Err(
+                "A SEPA credit transfer can have a reference or a text, but not both".to_string(),
+            );
+        }
+
+        if let Some(reference) = &self.reference {
+            validate_field("Reference", reference, 35)?;
+        }
+
+        if let Some(text) = &self.text {
+            validate_field("Text", text, 140)?;
+        }
+
+        if let Some(information) = &self.information {
+            validate_field("Information", information, 70)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Payload for SepaCreditTransfer {
+    fn to_payload_string(&self) -> Result<String, String> {
+        self.validate()?;
+
+        let iban = validate_iban(&self.iban)?;
+        let amount = match &self.amount {
+            Some(amount) => format!("EUR{}", format_amount(parse_amount(amount)?)),
+            None => String::new(),
+        };
+
+        let mut fields = vec![
+            "BCD".to_string(),
+            "002".to_string(),
+            // 1 is UTF-8
+            "1".to_string(),
+            "SCT".to_string(),
+            self.bic.clone().unwrap_or_default(),
+            self.name.clone(),
+            iban,
+            amount,
+            self.purpose.clone().unwrap_or_default(),
+            self.reference.clone().unwrap_or_default(),
+            self.text.clone().unwrap_or_default(),
+            self.information.clone().unwrap_or_default(),
+        ];
+
+        // empty fields at the end can be left out
+        while fields.last().is_some_and(|field| field.is_empty()) {
+            fields.pop();
+        }
+
+        let payload = fields.join("\n");
+
+        if payload.len() > 331 {
+            return Err(format!(
+                "SEPA credit transfers must be at most 331 bytes, this one is {}",
+                payload.len()
+            ));
+        }
+
+        Ok(payload)
+    }
+
+    /**
+     * EPC069-12 requires error correction level Medium and at most version 13
+     */
+    fn to_qr_code(&self, error_correction_level: ErrorCorrectionLevel) -> Result<QrCode, String> {
+        create_payment_qr_code(
+            self.to_payload_string()?,
+            error_correction_level,
+            13,
+            "SEPA credit transfer",
+        )
+    }
+}
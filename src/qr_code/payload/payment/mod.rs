@@ -0,0 +1,172 @@
+mod emvco;
+mod epc;
+mod swiss_qr_bill;
+
+pub use emvco::{MerchantAccount, MerchantPayment};
+pub use epc::SepaCreditTransfer;
+pub use swiss_qr_bill::{SwissAddress, SwissCurrency, SwissQrBill, SwissReference};
+
+use crate::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode, QrCodeOptions};
+
+/**
+ * Removes spaces from an IBAN and checks the country code, length and mod-97 check digits
+ *
+ * based on ISO 13616 https://www.iso13616.org
+ */
+fn validate_iban(iban: &str) -> Result<String, String> {
+    let iban: String = iban
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase();
+
+    let valid_format = (15..=34).contains(&iban.len())
+        && iban.chars().all(|c| c.is_ascii_alphanumeric())
+        && iban[..2].chars().all(|c| c.is_ascii_alphabetic())
+        && iban[2..4].chars().all(|c| c.is_ascii_digit());
+
+    if !valid_format || mod_97(&iban) != Some(1) {
+        return Err(format!("{} is not a valid IBAN", iban));
+    }
+
+    Ok(iban)
+}
+
+/**
+ * Moves the first four characters to the end, replaces letters with 10 to 35 and takes the
+ * remainder of the resulting number divided by 97, used by IBANs and RF creditor references
+ *
+ * GB82WEST12345698765432 → 3214282912345698765432161182 % 97 = 1
+ */
+fn mod_97(value: &str) -> Option<u32> {
+    let mut remainder = 0;
+
+    for c in value[4..].chars().chain(value[..4].chars()) {
+        let digits = c.to_digit(36)?;
+
+        remainder = if digits < 10 {
+            (remainder * 10 + digits) % 97
+        } else {
+            (remainder * 100 + digits) % 97
+        };
+    }
+
+    Some(remainder)
+}
+
+/**
+ * Parses an amount such as 1949.75 into cents, allowing at most two decimal places and at most
+ * 999999999.99
+ */
+fn parse_amount(amount: &str) -> Result<u64, String> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    let valid_format = !whole.is_empty()
+        && whole.len() <= 9
+        && fraction.len() <= 2
+        && (!amount.contains('.') || !fraction.is_empty())
+        && whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit());
+
+    if !valid_format {
+        return Err(format!(
+            "Amount {} must be a number with at most 9 digits before and 2 digits after the decimal point",
+            amount
+        ));
+    }
+
+    let whole: u64 = whole
+        .parse()
+        .map_err(|_| format!("Invalid amount {}", amount))?;
+    let fraction: u64 = format!("{:0<2}", fraction)
+        .parse()
+        .map_err(|_| format!("Invalid amount {}", amount))?;
+
+    match whole * 100 + fraction {
+        0 => Err("Amount must be at least 0.01".to_string()),
+        cents => Ok(cents),
+    }
+}
+
+/**
+ * Formats cents with two decimal places and no leading zeros, 123 → 1.23
+ */
+fn format_amount(cents: u64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/**
+ * Fails when the field is longer than the format allows, counting characters rather than bytes,
+ * or contains a line break that would shift every field after it
+ */
+fn validate_field(name: &str, value: &str, max_len: usize) -> Result<(), String> {
+    if value.chars().count() > max_len {
+        return Err(format!("{} must be at most {} characters", name, max_len));
+    }
+
+    if value.contains(['\r', '\n']) {
+        return Err(format!("{} cannot contain line breaks", name));
+    }
+
+    Ok(())
+}
+
+/**
+ * Payment standards fix the error correction level and cap the version so that banking apps can
+ * scan the code at its printed size
+ */
+fn create_payment_qr_code(
+    payload: String,
+    error_correction_level: ErrorCorrectionLevel,
+    max_version: i16,
+    standard: &str,
+) -> Result<QrCode, String> {
+    if error_correction_level != ErrorCorrectionLevel::Medium {
+        return Err(format!(
+            "{} codes must use error correction level Medium",
+            standard
+        ));
+    }
+
+    let options = QrCodeOptions {
+        max_version,
+        ..QrCodeOptions::default()
+    };
+
+    QrCode::create_with_options(
+        payload,
+        EncodingMode::Byte,
+        error_correction_level,
+        &options,
+    )
+}
+
+#[test]
+fn test_validate_iban() {
+    assert_eq!(
+        validate_iban("gb82 west 1234 5698 7654 32").unwrap(),
+        "GB82WEST12345698765432"
+    );
+    assert_eq!(
+        validate_iban("CH44 3199 9123 0008 8901 2").unwrap(),
+        "CH4431999123000889012"
+    );
+    assert!(validate_iban("GB83WEST12345698765432").is_err());
+    assert!(validate_iban("GB82").is_err());
+}
+
+#[test]
+fn test_parse_amount() {
+    assert_eq!(parse_amount("1949.75"), Ok(194975));
+    assert_eq!(parse_amount("12.3"), Ok(1230));
+    assert_eq!(parse_amount("12"), Ok(1200));
+    assert_eq!(format_amount(1230), "12.30");
+
+    assert!(parse_amount("0.00").is_err());
+    assert!(parse_amount("12.").is_err());
+    assert!(parse_amount("1.234").is_err());
+    assert!(parse_amount("1,50").is_err());
+    assert!(parse_amount("1000000000").is_err());
+}
@@ -0,0 +1,301 @@
+use crate::qr_code::payload::payment::{
+    create_payment_qr_code, format_amount, mod_97, parse_amount, validate_field, validate_iban,
+};
+use crate::qr_code::payload::Payload;
+use crate::qr_code::{ErrorCorrectionLevel, QrCode};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwissCurrency {
+    Chf,
+    Eur,
+}
+
+/**
+ * A structured address, the only kind accepted by version 2.3 of the implementation guidelines
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwissAddress {
+    pub name: String,
+    pub street: String,
+    pub building_number: String,
+    pub postal_code: String,
+    pub town: String,
+    /// two letter ISO 3166-1 country code
+    pub country: String,
+}
+
+impl SwissAddress {
+    fn validate(&self, role: &str) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.postal_code.is_empty() || self.town.is_empty() {
+            return Err(format!(
+                "The {} address needs a name, postal code and town",
+                role
+            ));
+        }
+
+        if self.country.len() != 2 || !self.country.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(format!(
+                "The {} country {} must be a two letter ISO 3166 code",
+                role, self.country
+            ));
+        }
+
+        validate_field("Name", &self.name, 70)?;
+        validate_field("Street", &self.street, 70)?;
+        validate_field("Building number", &self.building_number, 16)?;
+        validate_field("Postal code", &self.postal_code, 16)?;
+        validate_field("Town", &self.town, 35)
+    }
+
+    fn lines(&self) -> [String; 7] {
+        [
+            "S".to_string(),
+            self.name.clone(),
+            self.street.clone(),
+            self.building_number.clone(),
+            self.postal_code.clone(),
+            self.town.clone(),
+            self.country.clone(),
+        ]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SwissReference {
+    /// 27 digit QR reference, only allowed with a QR-IBAN
+    Qr(String),
+    /// ISO 11649 creditor reference starting with RF
+    Creditor(String),
+    None,
+}
+
+/**
+ * The payment part of a Swiss QR-bill
+ *
+ * based on https://www.six-group.com/dam/download/banking-services/standardization/qr-bill/ig-qr-bill-v2.3-en.pdf
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwissQrBill {
+    pub iban: String,
+    pub creditor: SwissAddress,
+    pub currency: SwissCurrency,
+    pub amount: Option<String>,
+    pub debtor: Option<SwissAddress>,
+    pub reference: SwissReference,
+    pub message: Option<String>,
+    pub bill_information: Option<String>,
+}
+
+impl SwissQrBill {
+    pub fn new(iban: &str, creditor: SwissAddress, currency: SwissCurrency) -> SwissQrBill {
+        SwissQrBill {
+            iban: iban.to_string(),
+            creditor,
+            currency,
+            amount: None,
+            debtor: None,
+            reference: SwissReference::None,
+            message: None,
+            bill_information: None,
+        }
+    }
+
+    /**
+     * Amount such as 1949.75, the payer fills it in when left out
+     */
+    pub fn amount(mut self, amount: &str) -> SwissQrBill {
+        self.amount = Some(amount.to_string());
+        self
+    }
+
+    pub fn debtor(mut self, debtor: SwissAddress) -> SwissQrBill {
+        self.debtor = Some(debtor);
+        self
+    }
+
+    pub fn reference(mut self, reference: SwissReference) -> SwissQrBill {
+        self.reference = reference;
+        self
+    }
+
+    pub fn message(mut self, message: &str) -> SwissQrBill {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /**
+     * Structured bill information such as the Swico S1 syntax, passed on to the creditor
+     */
+    pub fn bill_information(mut self, bill_information: &str) -> SwissQrBill {
+        self.bill_information = Some(bill_information.to_string());
+        self
+    }
+
+    /**
+     * Checks the reference against the kind of IBAN and returns the reference type and value
+     */
+    fn reference_fields(&self, iban: &str) -> Result<(&'static str, String), String> {
+        let qr_iban = is_qr_iban(iban);
+
+        match &self.reference {
+            SwissReference::Qr(reference) => {
+                let reference: String = reference.chars().filter(|c| *c != ' ').collect();
+
+                if !qr_iban {
+                    return Err("A QR reference can only be used with a QR-IBAN".to_string());
+                }
+
+                if !is_valid_qr_reference(&reference) {
+                    return Err(format!("{} is not a valid QR reference", reference));
+                }
+
+                Ok(("QRR", reference))
+            }
+            _ if qr_iban => Err("A QR-IBAN must be used with a QR reference".to_string()),
+            SwissReference::Creditor(reference) => {
+                let reference: String = reference
+                    .chars()
+                    .filter(|c| *c != ' ')
+                    .collect::<String>()
+                    .to_ascii_uppercase();
+
+                if !is_valid_creditor_reference(&reference) {
+                    return Err(format!("{} is not a valid creditor reference", reference));
+                }
+
+                Ok(("SCOR", reference))
+            }
+            SwissReference::None => Ok(("NON", String::new())),
+        }
+    }
+}
+
+impl Payload for SwissQrBill {
+    fn to_payload_string(&self) -> Result<String, String> {
+        let iban = validate_iban(&self.iban)?;
+
+        if !iban.starts_with("CH") && !iban.starts_with("LI") {
+            return Err("Swiss QR-bills need a Swiss or Liechtenstein IBAN".to_string());
+        }
+
+        self.creditor.validate("creditor")?;
+
+        if let Some(debtor) = &self.debtor {
+            debtor.validate("debtor")?;
+        }
+
+        let message = self.message.clone().unwrap_or_default();
+        let bill_information = self.bill_information.clone().unwrap_or_default();
+
+        validate_field("Message", &message, 140)?;
+        validate_field("Bill information", &bill_information, 140)?;
+
+        if message.chars().count() + bill_information.chars().count() > 140 {
+            return Err(
+                "The message and bill information must be at most 140 characters together"
+                    .to_string(),
+            );
+        }
+
+        let (reference_type, reference) = self.reference_fields(&iban)?;
+
+        let amount = match &self.amount {
+            Some(amount) => format_amount(parse_amount(amount)?),
+            None => String::new(),
+        };
+
+        let currency = match self.currency {
+            SwissCurrency::Chf => "CHF",
+            SwissCurrency::Eur => "EUR",
+        };
+
+        let mut lines = vec![
+            "SPC".to_string(),
+            "0200".to_string(),
+            // 1 is UTF-8 limited to the Latin character set
+            "1".to_string(),
+            iban,
+        ];
+
+        lines.extend(self.creditor.lines());
+        // the ultimate creditor is reserved for future use and stays empty
+        lines.extend(std::iter::repeat_n(String::new(), 7));
+        lines.push(amount);
+        lines.push(currency.to_string());
+
+        match &self.debtor {
+            Some(debtor) => lines.extend(debtor.lines()),
+            None => lines.extend(std::iter::repeat_n(String::new(), 7)),
+        }
+
+        lines.push(reference_type.to_string());
+        lines.push(reference);
+        lines.push(message);
+        lines.push("EPD".to_string());
+
+        if !bill_information.is_empty() {
+            lines.push(bill_information);
+        }
+
+        Ok(lines.join("\r\n"))
+    }
+
+    /**
+     * The implementation guidelines require error correction level Medium and at most version 25,
+     * the code must be printed with the Swiss cross in the middle, see `QrCode::to_swiss_qr_bill_svg`
+     */
+    fn to_qr_code(&self, error_correction_level: ErrorCorrectionLevel) -> Result<QrCode, String> {
+        create_payment_qr_code(
+            self.to_payload_string()?,
+            error_correction_level,
+            25,
+            "Swiss QR-bill",
+        )
+    }
+}
+
+/**
+ * QR-IBANs have an institution identifier between 30000 and 31999
+ */
+fn is_qr_iban(iban: &str) -> bool {
+    iban[4..9]
+        .parse::<u32>()
+        .is_ok_and(|institution| (30000..=31999).contains(&institution))
+}
+
+/**
+ * QR references are 27 digits, the last being a recursive mod 10 check digit
+ */
+fn is_valid_qr_reference(reference: &str) -> bool {
+    const CARRY_TABLE: [usize; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+
+    if reference.len() != 27 || !reference.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let carry = reference.bytes().fold(0, |carry, digit| {
+        CARRY_TABLE[(carry + (digit - b'0') as usize) % 10]
+    });
+
+    carry == 0
+}
+
+/**
+ * ISO 11649 creditor references are RF, two check digits and up to 21 letters and digits
+ */
+fn is_valid_creditor_reference(reference: &str) -> bool {
+    (5..=25).contains(&reference.len())
+        && reference.starts_with("RF")
+        && reference.chars().all(|c| c.is_ascii_alphanumeric())
+        && mod_97(reference) == Some(1)
+}
+
+#[test]
+fn test_references() {
+    assert!(is_valid_qr_reference("210000000003139471430009017"));
+    assert!(!is_valid_qr_reference("210000000003139471430009018"));
+    assert!(is_valid_creditor_reference("RF18539007547034"));
+    assert!(!is_valid_creditor_reference("RF19539007547034"));
+    assert!(is_qr_iban("CH4431999123000889012"));
+    assert!(!is_qr_iban("CH9300762011623852957"));
+}
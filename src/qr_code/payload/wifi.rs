@@ -0,0 +1,134 @@
+use crate::qr_code::payload::{
+    escape_with_backslash, split_escaped, strip_prefix_ignore_case, unescape_backslash, Payload,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WifiSecurity {
+    Wpa,
+    Wep,
+    None,
+}
+
+/**
+ * Network details that let a phone join a WiFi network by scanning the code
+ *
+ * WIFI:T:WPA;S:network;P:password;;
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    pub security: WifiSecurity,
+    pub password: Option<String>,
+    pub hidden: bool,
+}
+
+impl WifiNetwork {
+    /**
+     * An open network, add a password with `password`
+     */
+    pub fn new(ssid: &str) -> WifiNetwork {
+        WifiNetwork {
+            ssid: ssid.to_string(),
+            security: WifiSecurity::None,
+            password: None,
+            hidden: false,
+        }
+    }
+
+    pub fn password(mut self, security: WifiSecurity, password: &str) -> WifiNetwork {
+        self.security = security;
+        self.password = Some(password.to_string());
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> WifiNetwork {
+        self.hidden = hidden;
+        self
+    }
+
+    /**
+     * Reads the network from scanned text, WPA2 and WPA3 are treated as WPA and a password without
+     * a type is assumed to be for a WPA network
+     */
+    pub fn parse(text: &str) -> Result<WifiNetwork, String> {
+        let fields = strip_prefix_ignore_case(text, "WIFI:")
+            .ok_or_else(|| "WiFi networks must start with WIFI:".to_string())?;
+
+        let mut network = WifiNetwork::new("");
+        let mut security = None;
+
+        for field in split_escaped(fields, ';') {
+            let Some((key, value)) = field.split_once(':') else {
+                continue;
+            };
+
+            let value = unescape_backslash(value);
+
+            match key.to_ascii_uppercase().as_str() {
+                "T" => {
+                    security = Some(match value.to_ascii_uppercase().as_str() {
+                        "WPA" | "WPA2" | "WPA3" | "SAE" => WifiSecurity::Wpa,
+                        "WEP" => WifiSecurity::Wep,
+                        "NOPASS" | "" => WifiSecurity::None,
+                        _ => return Err(format!("Unknown WiFi security type {}", value)),
+                    })
+                }
+                "S" => network.ssid = value,
+                "P" => network.password = Some(value),
+                "H" => network.hidden = value.eq_ignore_ascii_case("true"),
+                _ => {}
+            }
+        }
+
+        if network.ssid.is_empty() {
+            return Err("The WiFi network has no SSID".to_string());
+        }
+
+        network.security = match (security, &network.password) {
+            (Some(security), _) => security,
+            (None, Some(_)) => WifiSecurity::Wpa,
+            (None, None) => WifiSecurity::None,
+        };
+
+        if network.security == WifiSecurity::None {
+            network.password = None;
+        }
+
+        Ok(network)
+    }
+}
+
+impl Payload for WifiNetwork {
+    fn to_payload_string(&self) -> Result<String, String> {
+        if self.ssid.is_empty() {
+            return Err("A WiFi network needs an SSID".to_string());
+        }
+
+        let special = [';', ',', ':', '"'];
+        let mut payload = String::from("WIFI:");
+
+        match self.security {
+            WifiSecurity::Wpa => payload += "T:WPA;",
+            WifiSecurity::Wep => payload += "T:WEP;",
+            WifiSecurity::None => payload += "T:nopass;",
+        }
+
+        payload += &format!("S:{};", escape_with_backslash(&self.ssid, &special));
+
+        match (&self.security, &self.password) {
+            (WifiSecurity::None, _) => {}
+            (_, Some(password)) => {
+                payload += &format!("P:{};", escape_with_backslash(password, &special))
+            }
+            (_, None) => return Err("A secured WiFi network needs a password".to_string()),
+        }
+
+        if self.hidden {
+            payload += "H:true;";
+        }
+
+        payload += ";";
+
+        Ok(payload)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::qr_code::render::png::dpi_to_pixels_per_metre;
+
+/**
+ * Encodes the image as a 1-bit BMP with a black and white palette, where `image` holds a row of dark
+ * pixels for every line.
+ *
+ * based on https://learn.microsoft.com/en-us/windows/win32/gdi/bitmap-storage
+ */
+pub fn render_bmp(image: &[Vec<bool>], dpi: Option<u32>) -> Vec<u8> {
+    let pixels = image.len();
+    // rows are padded to a multiple of 4 bytes
+    let row_bytes = pixels.div_ceil(32) * 4;
+    let header_size = 14 + 40 + 2 * 4;
+    let file_size = header_size + row_bytes * pixels;
+
+    let mut bmp = Vec::with_capacity(file_size);
+
+    // file header
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(file_size as u32).to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]);
+    bmp.extend_from_slice(&(header_size as u32).to_le_bytes());
+
+    // BITMAPINFOHEADER, a positive height stores the rows bottom up
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    bmp.extend_from_slice(&(pixels as i32).to_le_bytes());
+    bmp.extend_from_slice(&(pixels as i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    // no compression, image size, horizontal and vertical resolution in pixels per metre, colors
+    // used and important
+    let resolution = dpi.map_or(0, |dpi| dpi_to_pixels_per_metre(dpi) as usize);
+    for value in [0, row_bytes * pixels, resolution, resolution, 2, 2] {
+        bmp.extend_from_slice(&(value as u32).to_le_bytes());
+    }
+
+    // palette entries are blue, green, red and a reserved byte; index 0 is black, 1 is white
+    bmp.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0x00]);
+
+    for row in image.iter().rev() {
+        let mut line = vec![0; row_bytes];
+
+        for (x, &dark) in row.iter().enumerate() {
+            if !dark {
+                line[x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+
+        bmp.extend_from_slice(&line);
+    }
+
+    bmp
+}
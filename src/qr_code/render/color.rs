@@ -0,0 +1,345 @@
+use crate::qr_code::render::Logo;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    /**
+     * Reads a color written as #RRGGBB or #RGB, the # is optional
+     */
+    pub fn from_hex(hex: &str) -> Result<Color, String> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let invalid = || format!("\"{}\" is not a color, expected #RRGGBB or #RGB", hex);
+
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let channel = |i: usize, len: usize| {
+            let value = u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).unwrap_or(0);
+            if len == 1 {
+                value * 17
+            } else {
+                value
+            }
+        };
+
+        match digits.len() {
+            3 => Ok(Color::rgb(channel(0, 1), channel(1, 1), channel(2, 1))),
+            6 => Ok(Color::rgb(channel(0, 2), channel(1, 2), channel(2, 2))),
+            _ => Err(invalid()),
+        }
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+
+    /**
+     * How bright the color looks, from 0 for black to 1 for white. Used as an estimate of how much
+     * light the printed color reflects.
+     *
+     * based on https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+     */
+    pub fn relative_luminance(&self) -> f64 {
+        let linear = |channel: u8| {
+            let value = channel as f64 / 255.0;
+            if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /**
+     * The color a fraction `t` of the way from this color to `other`
+     */
+    pub fn mix(&self, other: &Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let channel =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+
+        Color::rgb(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+/**
+ * How the dark modules are painted. Gradients span the symbol without the quiet zone.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fill {
+    Solid(Color),
+    LinearGradient {
+        start: Color,
+        end: Color,
+        /// direction in degrees clockwise, 0 runs left to right and 90 top to bottom
+        angle: u16,
+    },
+    RadialGradient {
+        center: Color,
+        edge: Color,
+    },
+}
+
+impl Fill {
+    /**
+     * The colors the fill runs between. Every channel changes steadily along a gradient, so the
+     * darkest and lightest points are always one of these.
+     */
+    pub fn stops(&self) -> [Color; 2] {
+        match *self {
+            Fill::Solid(color) => [color, color],
+            Fill::LinearGradient { start, end, .. } => [start, end],
+            Fill::RadialGradient { center, edge } => [center, edge],
+        }
+    }
+
+    /**
+     * The color at a point given as fractions of the width and height of the symbol
+     */
+    pub fn color_at(&self, x: f64, y: f64) -> Color {
+        match *self {
+            Fill::Solid(color) => color,
+            Fill::LinearGradient { start, end, angle } => {
+                let (dx, dy) = direction(angle);
+                let extent = dx.abs() + dy.abs();
+                start.mix(&end, ((x - 0.5) * dx + (y - 0.5) * dy) / extent + 0.5)
+            }
+            Fill::RadialGradient { center, edge } => {
+                let distance = ((x - 0.5).powi(2) + (y - 0.5).powi(2)).sqrt();
+                center.mix(&edge, distance / std::f64::consts::FRAC_1_SQRT_2)
+            }
+        }
+    }
+
+    /**
+     * The SVG paint for the fill, with the gradient definition it refers to if there is one.
+     * `offset` and `width` place the gradient on the symbol in user units.
+     *
+     * based on https://www.w3.org/TR/SVG2/pservers.html
+     */
+    pub fn to_svg_paint(&self, id: &str, offset: usize, width: usize) -> (String, String) {
+        let stop = |offset: u8, color: &Color| {
+            format!(
+                "<stop offset=\"{}\" stop-color=\"{}\"/>",
+                offset,
+                color.to_hex()
+            )
+        };
+        let (offset, width) = (offset as f64, width as f64);
+        let center = offset + width / 2.0;
+
+        let definition = match self {
+            Fill::Solid(color) => return (String::new(), color.to_hex()),
+            Fill::LinearGradient { start, end, angle } => {
+                let (dx, dy) = direction(*angle);
+                let extent = (dx.abs() + dy.abs()) * width / 2.0;
+
+                format!(
+                    "<linearGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\">{}{}</linearGradient>\n",
+                    id,
+                    center - dx * extent,
+                    center - dy * extent,
+                    center + dx * extent,
+                    center + dy * extent,
+                    stop(0, start),
+                    stop(1, end)
+                )
+            }
+            Fill::RadialGradient { center: inner, edge } => format!(
+                "<radialGradient id=\"{0}\" gradientUnits=\"userSpaceOnUse\" cx=\"{1}\" cy=\"{1}\" r=\"{2:.3}\">{3}{4}</radialGradient>\n",
+                id,
+                center,
+                width * std::f64::consts::FRAC_1_SQRT_2,
+                stop(0, inner),
+                stop(1, edge)
+            ),
+        };
+
+        (definition, format!("url(#{})", id))
+    }
+}
+
+fn direction(angle: u16) -> (f64, f64) {
+    let radians = (angle % 360) as f64 * std::f64::consts::PI / 180.0;
+    (radians.cos(), radians.sin())
+}
+
+/**
+ * The colors of a rendered code, the default is black modules on white
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Colors {
+    /// the dark modules
+    pub foreground: Fill,
+    /// the finder patterns, painted like the other dark modules when not set
+    pub finder: Option<Fill>,
+    /// the light modules and quiet zone
+    pub background: Color,
+    /// drawn over the background in SVG output, scaled to cover the whole code
+    pub background_image: Option<Logo>,
+}
+
+impl Default for Colors {
+    fn default() -> Colors {
+        Colors {
+            foreground: Fill::Solid(Color::BLACK),
+            finder: None,
+            background: Color::WHITE,
+            background_image: None,
+        }
+    }
+}
+
+impl Colors {
+    pub fn is_black_on_white(&self) -> bool {
+        *self == Colors::default()
+    }
+
+    /**
+     * Compares the background with every color the dark modules take and keeps the pair closest in
+     * luminance, the one a scanner is most likely to confuse
+     */
+    pub fn check_contrast(&self) -> ContrastCheck {
+        let background = self.background.relative_luminance();
+        let dark: Vec<f64> = self
+            .foreground
+            .stops()
+            .into_iter()
+            .chain(self.finder.iter().flat_map(Fill::stops))
+            .map(|color| color.relative_luminance())
+            .collect();
+
+        let inverted = dark.iter().any(|&luminance| luminance > background);
+        let symbol_contrast = dark
+            .iter()
+            .map(|&luminance| (background - luminance).abs())
+            .fold(1.0, f64::min);
+
+        ContrastCheck {
+            symbol_contrast,
+            inverted,
+            background_image: self.background_image.is_some(),
+        }
+    }
+}
+
+/**
+ * The result of comparing the dark and light colors of a code
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContrastCheck {
+    /// difference in luminance between the light and dark modules, from 0 to 1
+    pub symbol_contrast: f64,
+    /// some dark modules are lighter than the background
+    pub inverted: bool,
+    /// the background image is not part of the check
+    pub background_image: bool,
+}
+
+impl ContrastCheck {
+    /**
+     * The symbol contrast grade, A to D pass and F fails
+     *
+     * based on the symbol contrast thresholds of ISO/IEC 15415
+     */
+    pub fn grade(&self) -> char {
+        match self.symbol_contrast {
+            contrast if contrast >= 0.70 => 'A',
+            contrast if contrast >= 0.55 => 'B',
+            contrast if contrast >= 0.40 => 'C',
+            contrast if contrast >= 0.20 => 'D',
+            _ => 'F',
+        }
+    }
+
+    /**
+     * Problems that may stop some scanners reading the code
+     */
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.grade() == 'D' {
+            warnings.push(format!(
+                "Low contrast between dark and light modules ({:.0}%), some scanners may not read the code",
+                self.symbol_contrast * 100.0
+            ));
+        }
+
+        if self.inverted {
+            warnings.push(
+                "Dark modules are lighter than the background, many scanners cannot read inverted codes"
+                    .to_string(),
+            );
+        }
+
+        if self.background_image {
+            warnings.push(
+                "The contrast against the background image cannot be checked, make sure it is faint"
+                    .to_string(),
+            );
+        }
+
+        warnings
+    }
+
+    /**
+     * An error when the contrast is too low for the code to be read at all
+     */
+    pub fn validate(&self) -> Result<(), String> {
+        if self.grade() == 'F' {
+            return Err(format!(
+                "The contrast between dark and light modules is {:.0}%, at least 20% is needed for the code to be readable",
+                self.symbol_contrast * 100.0
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_color_from_hex() {
+    assert_eq!(Color::from_hex("#1A2b3c"), Ok(Color::rgb(0x1A, 0x2B, 0x3C)));
+    assert_eq!(Color::from_hex("fff"), Ok(Color::WHITE));
+    assert!(Color::from_hex("#12345").is_err());
+    assert!(Color::from_hex("#GGGGGG").is_err());
+}
+
+#[test]
+fn test_relative_luminance() {
+    assert_eq!(Color::BLACK.relative_luminance(), 0.0);
+    assert_eq!(Color::WHITE.relative_luminance(), 1.0);
+    assert!((Color::rgb(128, 128, 128).relative_luminance() - 0.2158).abs() < 0.001);
+}
+
+#[test]
+fn test_linear_gradient_color_at() {
+    let fill = Fill::LinearGradient {
+        start: Color::BLACK,
+        end: Color::WHITE,
+        angle: 0,
+    };
+
+    assert_eq!(fill.color_at(0.0, 0.5), Color::BLACK);
+    assert_eq!(fill.color_at(1.0, 0.5), Color::WHITE);
+    assert_eq!(fill.color_at(0.5, 0.0), Color::rgb(128, 128, 128));
+}
@@ -0,0 +1,80 @@
+use crate::qr_code::{ErrorCorrectionLevel, QrCode};
+
+const ESC: u8 = 0x1B;
+const GS: u8 = 0x1D;
+
+/**
+ * The printer's own QR code commands (GS ( k): model 2, the module size in dots, the crate's error
+ * correction level and the data. The printer picks the encoding mode, version and mask.
+ *
+ * based on https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/gs_lparen_lk.html
+ */
+pub fn render_escpos(qr_code: &QrCode, module_size: usize) -> Result<Vec<u8>, String> {
+    if !(1..=16).contains(&module_size) {
+        return Err(format!(
+            "ESC/POS QR code modules can be 1 to 16 dots, found {}",
+            module_size
+        ));
+    }
+
+    let error_correction = match qr_code.error_correction_level {
+        ErrorCorrectionLevel::Low => 48,
+        ErrorCorrectionLevel::Medium => 49,
+        ErrorCorrectionLevel::Quartile => 50,
+        ErrorCorrectionLevel::High => 51,
+    };
+
+    let mut commands = vec![ESC, b'@'];
+
+    let mut function = |function: u8, parameters: &[u8]| {
+        let length = (parameters.len() + 2) as u16;
+        commands.extend_from_slice(&[GS, b'(', b'k']);
+        commands.extend_from_slice(&length.to_le_bytes());
+        commands.extend_from_slice(&[49, function]);
+        commands.extend_from_slice(parameters);
+    };
+
+    // functions 165, 167 and 169 select the model, module size and error correction level
+    function(65, &[50, 0]);
+    function(67, &[module_size as u8]);
+    function(69, &[error_correction]);
+
+    // function 180 stores the data and function 181 prints it
+    let mut data = vec![48];
+    data.extend_from_slice(qr_code.data.as_bytes());
+    function(80, &data);
+    function(81, &[48]);
+
+    commands.push(b'\n');
+    Ok(commands)
+}
+
+/**
+ * The image as a raster bit image (GS v 0), which prints exactly the matrix the crate created
+ *
+ * based on https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/gs_lv_0.html
+ */
+pub fn render_escpos_raster(image: &[Vec<bool>]) -> Vec<u8> {
+    let pixels = image.len();
+    let row_bytes = pixels.div_ceil(8);
+
+    let mut commands = vec![ESC, b'@', GS, b'v', b'0', 0];
+    commands.extend_from_slice(&(row_bytes as u16).to_le_bytes());
+    commands.extend_from_slice(&(pixels as u16).to_le_bytes());
+
+    for row in image {
+        let mut line = vec![0u8; row_bytes];
+
+        // a set bit prints a black dot
+        for (x, &dark) in row.iter().enumerate() {
+            if dark {
+                line[x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+
+        commands.extend_from_slice(&line);
+    }
+
+    commands.push(b'\n');
+    commands
+}
@@ -0,0 +1,209 @@
+/**
+ * Encodes the image as a GIF with a two color palette, where `image` holds a row of dark pixels for
+ * every line.
+ *
+ * based on https://www.w3.org/Graphics/GIF/spec-gif89a.txt
+ */
+pub fn render_gif(image: &[Vec<bool>]) -> Vec<u8> {
+    let pixels = image.len() as u16;
+    let mut gif = b"GIF89a".to_vec();
+
+    // logical screen descriptor with a global color table of 2 entries
+    gif.extend_from_slice(&pixels.to_le_bytes());
+    gif.extend_from_slice(&pixels.to_le_bytes());
+    gif.extend_from_slice(&[0b1000_0000, 0, 0]);
+
+    // index 0 is black, 1 is white
+    gif.extend_from_slice(&[0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF]);
+
+    // image descriptor covering the whole screen, no local color table or interlacing
+    gif.push(b',');
+    gif.extend_from_slice(&[0, 0, 0, 0]);
+    gif.extend_from_slice(&pixels.to_le_bytes());
+    gif.extend_from_slice(&pixels.to_le_bytes());
+    gif.push(0);
+
+    let indices: Vec<u8> = image
+        .iter()
+        .flat_map(|row| row.iter().map(|&dark| if dark { 0 } else { 1 }))
+        .collect();
+
+    // the smallest code size GIF allows
+    let min_code_size = 2;
+    gif.push(min_code_size);
+
+    // the compressed data is split into sub-blocks of up to 255 bytes
+    for block in lzw_compress(&indices, min_code_size).chunks(255) {
+        gif.push(block.len() as u8);
+        gif.extend_from_slice(block);
+    }
+
+    gif.push(0);
+    gif.push(b';');
+
+    gif
+}
+
+/**
+ * The variable length LZW used by GIF. Codes start one bit wider than the pixel indices, grow as
+ * the table fills and the table is cleared once it reaches 4096 codes.
+ *
+ * based on appendix F of https://www.w3.org/Graphics/GIF/spec-gif89a.txt
+ */
+fn lzw_compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODES: u16 = 4096;
+
+    let clear_code = 1u16 << min_code_size;
+    let end_code = clear_code + 1;
+    let alphabet = clear_code as usize;
+
+    let mut writer = LsbWriter::default();
+    let mut code_size = min_code_size + 1;
+    // the code for each string followed by each index, 0 when there is none yet
+    let mut table: Vec<Vec<u16>> = vec![vec![0; alphabet]; alphabet + 2];
+    let mut next_code = end_code + 1;
+
+    writer.write(clear_code, code_size);
+
+    let mut indices = indices.iter().map(|&index| index as usize);
+    let Some(first) = indices.next() else {
+        writer.write(end_code, code_size);
+        return writer.finish();
+    };
+    let mut current = first as u16;
+
+    for index in indices {
+        let extended = table[current as usize][index];
+        if extended != 0 {
+            current = extended;
+            continue;
+        }
+
+        writer.write(current, code_size);
+
+        if next_code < MAX_CODES {
+            table[current as usize][index] = next_code;
+            table.push(vec![0; alphabet]);
+            next_code += 1;
+
+            if next_code > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            writer.write(clear_code, code_size);
+
+            table.truncate(alphabet + 2);
+            table.iter_mut().for_each(|entry| entry.fill(0));
+            next_code = end_code + 1;
+            code_size = min_code_size + 1;
+        }
+
+        current = index as u16;
+    }
+
+    writer.write(current, code_size);
+    writer.write(end_code, code_size);
+    writer.finish()
+}
+
+/**
+ * Packs codes starting from the least significant bit of each byte
+ */
+#[derive(Default)]
+struct LsbWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl LsbWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+#[test]
+fn test_lzw_round_trip() {
+    // long enough to fill the table and clear it several times
+    let indices: Vec<u8> = (0..200_000u32)
+        .map(|i| ((i * 7919) >> (i % 13) & 1) as u8)
+        .collect();
+    let compressed = lzw_compress(&indices, 2);
+
+    let mut position = 0;
+    let mut read = |size: u8| {
+        let mut code = 0;
+        for i in 0..size as usize {
+            let bit = compressed[(position + i) / 8] >> ((position + i) % 8) & 1;
+            code |= (bit as u16) << i;
+        }
+        position += size as usize;
+        code
+    };
+
+    let mut decoded: Vec<u8> = Vec::new();
+    let mut table: Vec<Vec<u8>> = Vec::new();
+    let mut code_size = 3;
+    let mut previous: Option<usize> = None;
+
+    loop {
+        let code = read(code_size) as usize;
+
+        if code == 4 {
+            table = (0..4).map(|index| vec![index]).collect();
+            table.extend([Vec::new(), Vec::new()]);
+            code_size = 3;
+            previous = None;
+            continue;
+        }
+        if code == 5 {
+            break;
+        }
+
+        let entry = match previous {
+            None => table[code].clone(),
+            Some(previous) => {
+                // a code can refer to the entry that is about to be added
+                let entry = match table.get(code) {
+                    Some(entry) => entry.clone(),
+                    None => {
+                        let mut entry = table[previous].clone();
+                        entry.push(table[previous][0]);
+                        entry
+                    }
+                };
+
+                if table.len() < 4096 {
+                    let mut added = table[previous].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+                }
+                if table.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+
+                entry
+            }
+        };
+
+        decoded.extend_from_slice(&entry);
+        previous = Some(code);
+    }
+
+    assert_eq!(decoded, indices);
+}
@@ -0,0 +1,148 @@
+use crate::qr_code::render::renderer::Renderer;
+use crate::qr_code::render::{Color, Fill, RenderOptions};
+use crate::qr_code::QrCode;
+
+/**
+ * How the modules of an HTML snippet are laid out
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HtmlLayout {
+    /// a table with a row per row of modules, runs of modules are cells spanning several columns
+    Table,
+    /// a CSS grid with an element for each run of dark modules over the background
+    Grid,
+}
+
+/**
+ * An HTML snippet with inline styles, so it can be dropped into a page without a stylesheet.
+ * Gradients are sampled in the middle of each run of dark modules.
+ */
+pub struct HtmlRenderer {
+    layout: HtmlLayout,
+    module_size: usize,
+    quiet_zone: usize,
+    foreground: Fill,
+    finder: Option<Fill>,
+    background: Color,
+    finders: Vec<(usize, usize)>,
+    width: usize,
+    output: String,
+}
+
+impl HtmlRenderer {
+    pub fn new(layout: HtmlLayout) -> HtmlRenderer {
+        HtmlRenderer {
+            layout,
+            module_size: 0,
+            quiet_zone: 0,
+            foreground: Fill::Solid(Color::BLACK),
+            finder: None,
+            background: Color::WHITE,
+            finders: Vec::new(),
+            width: 0,
+            output: String::new(),
+        }
+    }
+
+    /**
+     * The color in the middle of a run, gradients span the symbol without the quiet zone
+     */
+    fn color(&self, x: usize, y: usize, length: usize) -> String {
+        let symbol_width = self.width.saturating_sub(2 * self.quiet_zone).max(1) as f64;
+        let position = |module: f64| (module - self.quiet_zone as f64) / symbol_width;
+
+        let in_finder = self
+            .finders
+            .iter()
+            .any(|&(fx, fy)| (fx..fx + 7).contains(&x) && (fy..fy + 7).contains(&y));
+        let fill = match self.finder {
+            Some(finder) if in_finder => finder,
+            _ => self.foreground,
+        };
+
+        fill.color_at(
+            position(x as f64 + length as f64 / 2.0),
+            position(y as f64 + 0.5),
+        )
+        .to_hex()
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    type Output = String;
+
+    fn begin(&mut self, _qr_code: &QrCode, options: &RenderOptions, width: usize) {
+        self.module_size = options.module_size;
+        self.quiet_zone = options.quiet_zone;
+        self.foreground = options.colors.foreground;
+        self.finder = options.colors.finder;
+        self.background = options.colors.background;
+        self.width = width;
+
+        let pixels = width * self.module_size;
+        let background = self.background.to_hex();
+
+        self.output = match self.layout {
+            HtmlLayout::Table => format!(
+                "<table style=\"border-collapse:collapse;border-spacing:0;table-layout:fixed;width:{}px;background:{}\">\n<colgroup><col span=\"{}\" style=\"width:{}px\"></colgroup>\n",
+                pixels, background, width, self.module_size
+            ),
+            HtmlLayout::Grid => format!(
+                "<div style=\"display:grid;grid-template-columns:repeat({0},{1}px);grid-template-rows:repeat({0},{1}px);width:{2}px;height:{2}px;background:{3}\">\n",
+                width, self.module_size, pixels, background
+            ),
+        };
+    }
+
+    fn draw_finder(&mut self, x: usize, y: usize) {
+        self.finders.push((x, y));
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        self.draw_run(x, y, 1, dark);
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize, dark: bool) {
+        match self.layout {
+            HtmlLayout::Table => {
+                if x == 0 {
+                    self.output += &format!("<tr style=\"height:{}px\">", self.module_size);
+                }
+
+                let background = if dark {
+                    format!(";background:{}", self.color(x, y, length))
+                } else {
+                    String::new()
+                };
+                self.output += &format!(
+                    "<td colspan=\"{}\" style=\"padding:0{}\"></td>",
+                    length, background
+                );
+
+                if x + length == self.width {
+                    self.output += "</tr>\n";
+                }
+            }
+            HtmlLayout::Grid => {
+                if dark {
+                    self.output += &format!(
+                        "<div style=\"grid-area:{}/{}/span 1/span {};background:{}\"></div>\n",
+                        y + 1,
+                        x + 1,
+                        length,
+                        self.color(x, y, length)
+                    );
+                }
+            }
+        }
+    }
+
+    fn end(mut self) -> String {
+        self.output += match self.layout {
+            HtmlLayout::Table => "</table>\n",
+            HtmlLayout::Grid => "</div>\n",
+        };
+
+        self.output
+    }
+}
@@ -0,0 +1,112 @@
+use crate::qr_code::character_count::create_character_count_indicator;
+use crate::qr_code::render::renderer::Renderer;
+use crate::qr_code::render::RenderOptions;
+use crate::qr_code::QrCode;
+
+/**
+ * A JSON object with what the QR code was created with and its modules as rows of 1 for dark and 0
+ * for light, one row per line so fixtures diff well
+ */
+#[derive(Default)]
+pub struct JsonRenderer {
+    width: usize,
+    output: String,
+}
+
+impl JsonRenderer {
+    pub fn new() -> JsonRenderer {
+        JsonRenderer::default()
+    }
+}
+
+impl Renderer for JsonRenderer {
+    type Output = String;
+
+    fn begin(&mut self, qr_code: &QrCode, options: &RenderOptions, width: usize) {
+        self.width = width;
+
+        let character_count_indicator = create_character_count_indicator(
+            &qr_code.data,
+            &qr_code.encoding_mode,
+            &qr_code.version,
+        );
+        let segment_bits = qr_code.encoding_mode.to_bits().len()
+            + character_count_indicator.len()
+            + qr_code.encoded_data.len();
+
+        self.output = format!(
+            "{{\n  \"version\": {},\n  \"error_correction_level\": {},\n  \"mask\": {},\n  \"width\": {},\n  \"quiet_zone\": {},\n  \"inverted\": {},\n  \"data\": {},\n",
+            qr_code.version.version(),
+            json_string(&qr_code.error_correction_level.to_string()),
+            qr_code.mask,
+            qr_code.width(),
+            options.quiet_zone,
+            options.invert,
+            json_string(&qr_code.data)
+        );
+        self.output += &format!(
+            "  \"segments\": [\n    {{\"mode\": {}, \"characters\": {}, \"bits\": {}}}\n  ],\n",
+            json_string(&qr_code.encoding_mode.to_string()),
+            qr_code.data.len(),
+            segment_bits
+        );
+        self.output += "  \"modules\": [\n";
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        self.draw_run(x, y, 1, dark);
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize, dark: bool) {
+        if x == 0 {
+            self.output += "    [";
+        }
+
+        for i in x..x + length {
+            if i > 0 {
+                self.output.push(',');
+            }
+            self.output.push(if dark { '1' } else { '0' });
+        }
+
+        if x + length == self.width {
+            self.output += if y + 1 == self.width { "]\n" } else { "],\n" };
+        }
+    }
+
+    fn end(mut self) -> String {
+        self.output += "  ]\n}\n";
+        self.output
+    }
+}
+
+/**
+ * Quotes the value and escapes it as a JSON string
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc8259#section-7
+ */
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            '\r' => escaped += "\\r",
+            '\t' => escaped += "\\t",
+            c if (c as u32) < 0x20 => escaped += &format!("\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+#[test]
+fn test_json_string() {
+    assert_eq!(json_string("HELLO"), "\"HELLO\"");
+    assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+}
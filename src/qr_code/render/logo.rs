@@ -0,0 +1,144 @@
+use crate::qr_code::render::color::Colors;
+use crate::qr_code::{KnockoutShape, QrCode};
+
+/**
+ * An image drawn in the knockout area of a QR code created with `QrCodeOptions::knockout`.
+ *
+ * The image file is embedded in SVG output as it is. The crate cannot decode images, so raster and
+ * terminal output only leave the knockout area light.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Logo {
+    /// such as image/png or image/svg+xml
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl Logo {
+    pub fn new(mime_type: &str, data: Vec<u8>) -> Logo {
+        Logo {
+            mime_type: mime_type.to_string(),
+            data,
+        }
+    }
+
+    /**
+     * Guesses the MIME type from the file extension of the image
+     */
+    pub fn mime_type_for_path(path: &str) -> Option<&'static str> {
+        let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "png" => Some("image/png"),
+            "jpg" | "jpeg" => Some("image/jpeg"),
+            "gif" => Some("image/gif"),
+            "webp" => Some("image/webp"),
+            "svg" => Some("image/svg+xml"),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Draws the logo over the knockout area, clipped to its shape, then draws the function modules in
+ * the area again so alignment patterns stay visible
+ */
+pub fn logo_overlay(qr_code: &QrCode, logo: &Logo, quiet_zone: usize, colors: &Colors) -> String {
+    let Some(knockout) = qr_code.knockout else {
+        return String::new();
+    };
+
+    let size = qr_code.width();
+    let offset = quiet_zone as f64;
+    let (left, top, width, height) = knockout.bounds(size);
+    let (left, top) = (left + offset, top + offset);
+
+    let mut overlay = String::new();
+
+    // the gradient is already defined by render_svg
+    let (_, dark) = colors
+        .foreground
+        .to_svg_paint("foreground", quiet_zone, size);
+    let light = colors.background.to_hex();
+
+    let clip = match knockout.shape {
+        KnockoutShape::Rectangle => String::new(),
+        KnockoutShape::Circle => {
+            overlay += &format!(
+                "<clipPath id=\"logo-clip\"><ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"/></clipPath>\n",
+                left + width / 2.0,
+                top + height / 2.0,
+                width / 2.0,
+                height / 2.0
+            );
+            " clip-path=\"url(#logo-clip)\"".to_string()
+        }
+    };
+
+    overlay += &format!(
+        "<image x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" preserveAspectRatio=\"xMidYMid meet\"{} href=\"data:{};base64,{}\"/>\n",
+        left,
+        top,
+        width,
+        height,
+        clip,
+        logo.mime_type,
+        base64_encode(&logo.data)
+    );
+
+    for y in 0..size {
+        for x in 0..size {
+            if qr_code.is_function_module(x, y) && knockout.covers(size, x, y) {
+                overlay += &format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>\n",
+                    x + quiet_zone,
+                    y + quiet_zone,
+                    if qr_code.get_module(x, y) {
+                        &dark
+                    } else {
+                        &light
+                    }
+                );
+            }
+        }
+    }
+
+    overlay
+}
+
+/**
+ * based on https://www.rfc-editor.org/rfc/rfc4648#section-4
+ */
+pub fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let group = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[test]
+fn test_base64_encode() {
+    assert_eq!(base64_encode(b""), "");
+    assert_eq!(base64_encode(b"f"), "Zg==");
+    assert_eq!(base64_encode(b"fo"), "Zm8=");
+    assert_eq!(base64_encode(b"foo"), "Zm9v");
+    assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+}
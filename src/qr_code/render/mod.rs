@@ -0,0 +1,232 @@
+mod bmp;
+mod color;
+mod escpos;
+mod gif;
+mod html;
+mod json;
+mod logo;
+mod netpbm;
+mod physical;
+mod png;
+mod print;
+mod raster;
+mod renderer;
+mod style;
+mod svg;
+mod terminal;
+mod text;
+mod zlib;
+mod zpl;
+
+pub use color::{Color, Colors, ContrastCheck, Fill};
+pub use html::{HtmlLayout, HtmlRenderer};
+pub use json::JsonRenderer;
+pub use logo::Logo;
+pub use physical::{PhysicalSize, SizeReport};
+pub use print::{Cmyk, Length, PrintFormat, PrintOptions, PrintRenderer};
+pub use raster::{RasterFormat, RasterRenderer};
+pub use renderer::Renderer;
+pub use style::{FinderShape, ModuleShape, Style};
+pub use svg::SvgRenderer;
+pub use terminal::TerminalRenderer;
+pub use text::TextRenderer;
+
+use crate::qr_code::QrCode;
+
+/**
+ * Settings shared by every output format
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// light modules added around each side of the symbol, the specification requires 4
+    pub quiet_zone: usize,
+    /// pixels per module for raster output, user units per module for SVG
+    pub module_size: usize,
+    /// written to PNG and BMP metadata and used to give SVG output a size in millimetres
+    pub dpi: Option<u32>,
+    /// drawn in the knockout area, if the QR code has one
+    pub logo: Option<Logo>,
+    /// module and finder pattern shapes for SVG and PNG output
+    pub style: Style,
+    /// module, finder pattern and background colors for SVG and PNG output
+    pub colors: Colors,
+    /// swaps dark and light modules, the quiet zone included, for a light code on a dark background
+    pub invert: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions {
+            quiet_zone: 4,
+            module_size: 10,
+            dpi: None,
+            logo: None,
+            style: Style::default(),
+            colors: Colors::default(),
+            invert: false,
+        }
+    }
+}
+
+impl QrCode {
+    /**
+     * Draws the QR code with any renderer, built in or from another crate
+     */
+    pub fn render<R: Renderer>(&self, renderer: R, options: &RenderOptions) -> R::Output {
+        renderer::render(self, options, renderer)
+    }
+
+    /**
+     * Renders the QR code as an SVG document
+     */
+    pub fn to_svg(&self, options: &RenderOptions) -> String {
+        self.render(SvgRenderer::new(), options)
+    }
+
+    /**
+     * Renders the QR code as an SVG document with the Swiss cross in the middle, as required for the
+     * payment part of a Swiss QR-bill.
+     *
+     * The cross covers data modules, so the code must be created by `SwissQrBill::to_qr_code` which
+     * uses error correction level Medium to recover them. The code is always black on white as the
+     * standard requires, the colors in the options are ignored.
+     */
+    pub fn to_swiss_qr_bill_svg(&self, options: &RenderOptions) -> String {
+        self.render(SvgRenderer::swiss_qr_bill(), options)
+    }
+
+    /**
+     * Renders the QR code as a 1-bit grayscale PNG image, or a truecolor one when it is not black on
+     * white. Background images are only drawn in SVG output.
+     */
+    pub fn to_png(&self, options: &RenderOptions) -> Vec<u8> {
+        self.render(RasterRenderer::new(RasterFormat::Png), options)
+    }
+
+    /**
+     * Renders the QR code as a 1-bit BMP image, colors are ignored
+     */
+    pub fn to_bmp(&self, options: &RenderOptions) -> Vec<u8> {
+        self.render(RasterRenderer::new(RasterFormat::Bmp), options)
+    }
+
+    /**
+     * Renders the QR code as a raw (P4) PBM image, colors are ignored
+     */
+    pub fn to_pbm(&self, options: &RenderOptions) -> Vec<u8> {
+        self.render(RasterRenderer::new(RasterFormat::Pbm), options)
+    }
+
+    /**
+     * Renders the QR code as a plain text (P1) PBM image, colors are ignored
+     */
+    pub fn to_plain_pbm(&self, options: &RenderOptions) -> Vec<u8> {
+        self.render(RasterRenderer::new(RasterFormat::PlainPbm), options)
+    }
+
+    /**
+     * Renders the QR code as an 8-bit grayscale PGM image, colors are ignored
+     */
+    pub fn to_pgm(&self, options: &RenderOptions) -> Vec<u8> {
+        self.render(RasterRenderer::new(RasterFormat::Pgm), options)
+    }
+
+    /**
+     * Renders the QR code as a black and white GIF image, colors are ignored
+     */
+    pub fn to_gif(&self, options: &RenderOptions) -> Vec<u8> {
+        self.render(RasterRenderer::new(RasterFormat::Gif), options)
+    }
+
+    /**
+     * Renders the QR code as Encapsulated PostScript at the physical size in the print options, the
+     * quiet zone and module shapes come from the render options and its colors are ignored
+     */
+    pub fn to_eps(&self, options: &RenderOptions, print: &PrintOptions) -> String {
+        let eps = self.render(PrintRenderer::new(PrintFormat::Eps, *print), options);
+        String::from_utf8(eps).expect("PostScript is written as text")
+    }
+
+    /**
+     * Renders the QR code as a single page PostScript document, like `to_eps`
+     */
+    pub fn to_postscript(&self, options: &RenderOptions, print: &PrintOptions) -> String {
+        let postscript = self.render(PrintRenderer::new(PrintFormat::PostScript, *print), options);
+        String::from_utf8(postscript).expect("PostScript is written as text")
+    }
+
+    /**
+     * Renders the QR code as a single page PDF, like `to_eps`
+     */
+    pub fn to_pdf(&self, options: &RenderOptions, print: &PrintOptions) -> Vec<u8> {
+        self.render(PrintRenderer::new(PrintFormat::Pdf, *print), options)
+    }
+
+    /**
+     * A ZPL label that has the printer draw the code with its own QR code command, at
+     * `options.module_size` dots per module from 1 to 10. The printer picks the version and mask, so
+     * use `to_zpl_graphic` when the exact matrix matters.
+     */
+    pub fn to_zpl(&self, options: &RenderOptions) -> Result<String, String> {
+        zpl::render_zpl(self, options.module_size, options.quiet_zone)
+    }
+
+    /**
+     * A ZPL label with the QR code as a graphic field, colors are ignored
+     */
+    pub fn to_zpl_graphic(&self, options: &RenderOptions) -> String {
+        let zpl = self.render(RasterRenderer::new(RasterFormat::ZplGraphic), options);
+        String::from_utf8(zpl).expect("ZPL is written as text")
+    }
+
+    /**
+     * ESC/POS commands that have the printer draw the code with its own QR code commands, at
+     * `options.module_size` dots per module from 1 to 16. The printer picks the encoding mode,
+     * version and mask, so use `to_escpos_raster` when the exact matrix matters.
+     */
+    pub fn to_escpos(&self, options: &RenderOptions) -> Result<Vec<u8>, String> {
+        escpos::render_escpos(self, options.module_size)
+    }
+
+    /**
+     * ESC/POS commands that print the QR code as a raster image, colors are ignored
+     */
+    pub fn to_escpos_raster(&self, options: &RenderOptions) -> Vec<u8> {
+        self.render(RasterRenderer::new(RasterFormat::EscPosRaster), options)
+    }
+
+    /**
+     * Renders the QR code as an HTML snippet with inline styles, `options.module_size` pixels per
+     * module. The style and logo are ignored.
+     */
+    pub fn to_html(&self, options: &RenderOptions, layout: HtmlLayout) -> String {
+        self.render(HtmlRenderer::new(layout), options)
+    }
+
+    /**
+     * Renders the QR code as text with a line per row, using `dark` and `light` for each module.
+     * Pass "1" and "0" for a plain matrix, or two characters per module to keep it square in a
+     * terminal.
+     */
+    pub fn to_text(&self, options: &RenderOptions, dark: &str, light: &str) -> String {
+        self.render(TextRenderer::new(dark, light), options)
+    }
+
+    /**
+     * The version, error correction level, mask, encoding mode segments and modules of the QR code
+     * as JSON, for test fixtures and web pages that draw the code themselves
+     */
+    pub fn to_json(&self, options: &RenderOptions) -> String {
+        self.render(JsonRenderer::new(), options)
+    }
+
+    /**
+     * Renders the QR code with Unicode half block characters, two rows of modules per line of text.
+     *
+     * Dark modules are drawn with blocks, so the code scans when printed on a light background.
+     * The style is ignored, every module is a block.
+     */
+    pub fn to_terminal_string(&self, options: &RenderOptions) -> String {
+        self.render(TerminalRenderer::new(), options)
+    }
+}
@@ -0,0 +1,73 @@
+/**
+ * Encodes the image as a raw (P4) PBM, where a set bit is black, or a plain (P1) PBM with a 1 for
+ * every black pixel.
+ *
+ * based on https://netpbm.sourceforge.net/doc/pbm.html
+ */
+pub fn render_pbm(image: &[Vec<bool>], plain: bool) -> Vec<u8> {
+    let pixels = image.len();
+
+    if plain {
+        let mut pbm = format!("P1\n{} {}\n", pixels, pixels);
+
+        for row in image {
+            // lines should be no longer than 70 characters
+            for (i, chunk) in row.chunks(35).enumerate() {
+                if i > 0 {
+                    pbm.push('\n');
+                }
+
+                let line: Vec<&str> = chunk
+                    .iter()
+                    .map(|&dark| if dark { "1" } else { "0" })
+                    .collect();
+                pbm += &line.join(" ");
+            }
+
+            pbm.push('\n');
+        }
+
+        return pbm.into_bytes();
+    }
+
+    let mut pbm = format!("P4\n{} {}\n", pixels, pixels).into_bytes();
+    let row_bytes = pixels.div_ceil(8);
+
+    for row in image {
+        let mut line = vec![0; row_bytes];
+
+        for (x, &dark) in row.iter().enumerate() {
+            if dark {
+                line[x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+
+        pbm.extend_from_slice(&line);
+    }
+
+    pbm
+}
+
+/**
+ * Encodes the image as a raw (P5) PGM with 8-bit gray levels, 0 for dark pixels and 255 for light
+ *
+ * based on https://netpbm.sourceforge.net/doc/pgm.html
+ */
+pub fn render_pgm(image: &[Vec<bool>]) -> Vec<u8> {
+    let pixels = image.len();
+    let mut pgm = format!("P5\n{} {}\n255\n", pixels, pixels).into_bytes();
+
+    for row in image {
+        pgm.extend(row.iter().map(|&dark| if dark { 0 } else { 255 }));
+    }
+
+    pgm
+}
+
+#[test]
+fn test_plain_pbm() {
+    let image = vec![vec![true, false], vec![false, true]];
+
+    assert_eq!(render_pbm(&image, true), b"P1\n2 2\n1 0\n0 1\n");
+    assert_eq!(render_pbm(&image, false), b"P4\n2 2\n\x80\x40");
+}
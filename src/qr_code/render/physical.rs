@@ -0,0 +1,102 @@
+use crate::qr_code::render::{Length, RenderOptions};
+use crate::qr_code::QrCode;
+
+/**
+ * A printed size to fit raster output to, such as 25 mm square at 300 dpi
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicalSize {
+    /// largest width and height of the code including the quiet zone
+    pub size: Length,
+    /// pixels per inch of the printer
+    pub dpi: u32,
+    /// modules smaller than this are refused
+    pub min_module_size: Length,
+}
+
+impl PhysicalSize {
+    /**
+     * A size with the common minimum printable module size of 0.33 mm
+     */
+    pub fn new(size: Length, dpi: u32) -> PhysicalSize {
+        PhysicalSize {
+            size,
+            dpi,
+            min_module_size: Length::millimeters(0.33),
+        }
+    }
+}
+
+/**
+ * How a code fits a physical size, printed modules are a whole number of pixels
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SizeReport {
+    /// pixels per module
+    pub module_size: usize,
+    /// width and height of the image in pixels
+    pub pixels: usize,
+    pub dpi: u32,
+    /// width and height when printed at the dpi, at most the requested size
+    pub printed_size: Length,
+    pub printed_module_size: Length,
+}
+
+impl SizeReport {
+    /**
+     * The options with the module size and dpi of the report
+     */
+    pub fn apply(&self, options: &RenderOptions) -> RenderOptions {
+        RenderOptions {
+            module_size: self.module_size,
+            dpi: Some(self.dpi),
+            ..options.clone()
+        }
+    }
+}
+
+impl QrCode {
+    /**
+     * Picks the largest whole number of pixels per module that keeps the code, with the quiet zone
+     * of the options, within the physical size.
+     */
+    pub fn fit_physical_size(
+        &self,
+        options: &RenderOptions,
+        physical: &PhysicalSize,
+    ) -> Result<SizeReport, String> {
+        if physical.dpi == 0 {
+            return Err("The dpi must be at least 1".to_string());
+        }
+
+        let width = self.width() + 2 * options.quiet_zone;
+        let dpi = physical.dpi as f64;
+        let available_pixels = physical.size.to_points() / 72.0 * dpi;
+        let module_size = (available_pixels / width as f64).floor() as usize;
+
+        let pixels_to_length = |pixels: usize| Length::inches(pixels as f64 / dpi);
+        let printed_module_size = pixels_to_length(module_size);
+
+        if module_size == 0
+            || printed_module_size.to_points() < physical.min_module_size.to_points()
+        {
+            return Err(format!(
+                "A {}x{} module code in {:.2} mm at {} dpi would have modules of {:.3} mm, the smallest printable module is {:.2} mm",
+                width,
+                width,
+                physical.size.to_millimeters(),
+                physical.dpi,
+                printed_module_size.to_millimeters(),
+                physical.min_module_size.to_millimeters()
+            ));
+        }
+
+        Ok(SizeReport {
+            module_size,
+            pixels: module_size * width,
+            dpi: physical.dpi,
+            printed_size: pixels_to_length(module_size * width),
+            printed_module_size,
+        })
+    }
+}
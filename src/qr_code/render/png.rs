@@ -0,0 +1,136 @@
+use crate::qr_code::render::color::Color;
+use crate::qr_code::render::zlib::zlib_compress;
+
+/**
+ * Encodes the image as a 1-bit grayscale PNG, where `image` holds a row of dark pixels for every
+ * line.
+ *
+ * based on https://www.w3.org/TR/png/
+ */
+pub fn render_png(image: &[Vec<bool>], dpi: Option<u32>) -> Vec<u8> {
+    let pixels = image.len();
+
+    // bit depth 1, grayscale
+    encode_png(
+        pixels,
+        [1, 0],
+        &scanlines(image),
+        pixels.div_ceil(8) + 1,
+        dpi,
+    )
+}
+
+/**
+ * Encodes the image as an 8-bit truecolor PNG, used when the code is not black on white
+ */
+pub fn render_png_rgb(image: &[Vec<Color>], dpi: Option<u32>) -> Vec<u8> {
+    let pixels = image.len();
+    let mut data = Vec::with_capacity((pixels * 3 + 1) * pixels);
+
+    for row in image {
+        // filter type 0, none
+        data.push(0);
+
+        for color in row {
+            data.extend_from_slice(&[color.r, color.g, color.b]);
+        }
+    }
+
+    // bit depth 8, truecolor
+    encode_png(pixels, [8, 2], &data, pixels * 3 + 1, dpi)
+}
+
+fn encode_png(
+    pixels: usize,
+    format: [u8; 2],
+    scanlines: &[u8],
+    row_len: usize,
+    dpi: Option<u32>,
+) -> Vec<u8> {
+    let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(pixels as u32).to_be_bytes());
+    header.extend_from_slice(&(pixels as u32).to_be_bytes());
+    header.extend_from_slice(&format);
+    // deflate compression, adaptive filtering, no interlacing
+    header.extend_from_slice(&[0, 0, 0]);
+
+    let image_data = zlib_compress(scanlines, row_len);
+
+    write_chunk(&mut png, b"IHDR", &header);
+
+    if let Some(dpi) = dpi {
+        // pixels per metre in both directions
+        let pixels_per_metre = (dpi_to_pixels_per_metre(dpi) as u32).to_be_bytes();
+        let mut physical = Vec::with_capacity(9);
+        physical.extend_from_slice(&pixels_per_metre);
+        physical.extend_from_slice(&pixels_per_metre);
+        physical.push(1);
+
+        write_chunk(&mut png, b"pHYs", &physical);
+    }
+
+    write_chunk(&mut png, b"IDAT", &image_data);
+    write_chunk(&mut png, b"IEND", &[]);
+
+    png
+}
+
+/**
+ * Each scanline starts with the filter type (0, none) followed by the pixels packed 8 to a byte,
+ * a set bit is white
+ */
+fn scanlines(image: &[Vec<bool>]) -> Vec<u8> {
+    let pixels = image.len();
+    let row_bytes = pixels.div_ceil(8);
+
+    let mut data = Vec::with_capacity((row_bytes + 1) * pixels);
+
+    for row in image {
+        let mut line = vec![0; row_bytes + 1];
+
+        for (x, &dark) in row.iter().enumerate() {
+            if !dark {
+                line[1 + x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+
+        data.extend_from_slice(&line);
+    }
+
+    data
+}
+
+pub fn dpi_to_pixels_per_metre(dpi: u32) -> f64 {
+    (dpi as f64 / 0.0254).round()
+}
+
+pub fn write_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start = png.len();
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFF;
+
+    for &byte in bytes {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
@@ -0,0 +1,426 @@
+use crate::qr_code::render::renderer::{ModuleGrid, Renderer};
+use crate::qr_code::render::style::{self, format_number, PathSegment, Shapes, Style};
+use crate::qr_code::render::RenderOptions;
+use crate::qr_code::QrCode;
+
+/**
+ * A physical length, stored in PostScript points of 1/72 inch
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Length {
+    points: f64,
+}
+
+impl Length {
+    pub fn points(points: f64) -> Length {
+        Length { points }
+    }
+
+    pub fn millimeters(millimeters: f64) -> Length {
+        Length::points(millimeters / 25.4 * 72.0)
+    }
+
+    pub fn inches(inches: f64) -> Length {
+        Length::points(inches * 72.0)
+    }
+
+    /**
+     * Reads a number followed by mm, cm, in or pt, such as 30mm or 1.5in
+     */
+    pub fn parse(text: &str) -> Result<Length, String> {
+        let text = text.trim();
+        let split = text
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+
+        let number: f64 = match number.trim().parse() {
+            Ok(number) if number > 0.0 && f64::is_finite(number) => number,
+            _ => {
+                return Err(format!(
+                "\"{}\" is not a length, expected a positive number followed by mm, cm, in or pt",
+                text
+            ))
+            }
+        };
+
+        match unit.to_ascii_lowercase().as_str() {
+            "mm" => Ok(Length::millimeters(number)),
+            "cm" => Ok(Length::millimeters(number * 10.0)),
+            "in" => Ok(Length::inches(number)),
+            "pt" => Ok(Length::points(number)),
+            _ => Err(format!(
+                "Unknown unit \"{}\" in \"{}\", expected mm, cm, in or pt",
+                unit, text
+            )),
+        }
+    }
+
+    pub fn to_points(&self) -> f64 {
+        self.points
+    }
+
+    pub fn to_millimeters(&self) -> f64 {
+        self.points / 72.0 * 25.4
+    }
+}
+
+/**
+ * A process color in percent of cyan, magenta, yellow and black ink
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cmyk {
+    pub c: u8,
+    pub m: u8,
+    pub y: u8,
+    pub k: u8,
+}
+
+impl Cmyk {
+    pub const BLACK: Cmyk = Cmyk::new(0, 0, 0, 100);
+    pub const WHITE: Cmyk = Cmyk::new(0, 0, 0, 0);
+
+    pub const fn new(c: u8, m: u8, y: u8, k: u8) -> Cmyk {
+        Cmyk { c, m, y, k }
+    }
+
+    /**
+     * Reads four percentages separated by commas, such as 0,0,0,100
+     */
+    pub fn parse(text: &str) -> Result<Cmyk, String> {
+        let values: Vec<u8> = text
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|&value| value <= 100)
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(|| {
+                format!(
+                    "\"{}\" is not a CMYK color, expected C,M,Y,K from 0 to 100",
+                    text
+                )
+            })?;
+
+        match values.as_slice() {
+            &[c, m, y, k] => Ok(Cmyk::new(c, m, y, k)),
+            _ => Err(format!(
+                "\"{}\" is not a CMYK color, expected C,M,Y,K from 0 to 100",
+                text
+            )),
+        }
+    }
+
+    /**
+     * The four components from 0 to 1, separated by spaces as PostScript and PDF expect them
+     */
+    fn operands(&self) -> String {
+        [self.c, self.m, self.y, self.k]
+            .iter()
+            .map(|&value| format_number(value as f64 / 100.0))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/**
+ * Settings for EPS, PostScript and PDF output
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrintOptions {
+    /// width and height of the code including the quiet zone, there is no bleed
+    pub size: Length,
+    /// ink for the dark modules
+    pub foreground: Cmyk,
+    /// ink for the light modules and quiet zone, unprinted paper when not set
+    pub background: Option<Cmyk>,
+}
+
+impl Default for PrintOptions {
+    fn default() -> PrintOptions {
+        PrintOptions {
+            size: Length::millimeters(30.0),
+            foreground: Cmyk::BLACK,
+            background: None,
+        }
+    }
+}
+
+impl PrintOptions {
+    /**
+     * The size of one module when the code is `width` modules wide including the quiet zone
+     */
+    pub fn module_size(&self, width: usize) -> Length {
+        Length::points(self.size.to_points() / width as f64)
+    }
+}
+
+/**
+ * The drawing operators shared by PostScript and PDF, with the y axis flipped to run upwards from
+ * the bottom left corner
+ */
+fn drawing(shapes: &Shapes, width: usize, options: &PrintOptions, operators: [&str; 7]) -> String {
+    let [set_color, rectangle, move_to, line_to, curve_to, close, fill] = operators;
+    let size = options.size.to_points();
+    let scale = options.module_size(width).to_points();
+    let n = format_number;
+    let x = |x: f64| n(x * scale);
+    let y = |y: f64| n(size - y * scale);
+
+    let mut drawing = String::new();
+
+    if let Some(background) = options.background {
+        drawing += &format!("{} {}\n", background.operands(), set_color);
+        drawing += &format!("0 0 {} {} {}\n", n(size), n(size), rectangle);
+    }
+
+    drawing += &format!("{} {}\n", options.foreground.operands(), set_color);
+
+    for shape in shapes.finders.iter().chain(&shapes.modules) {
+        for segment in shape.outline() {
+            match segment {
+                PathSegment::MoveTo(px, py) => {
+                    drawing += &format!("{} {} {}\n", x(px), y(py), move_to)
+                }
+                PathSegment::LineTo(px, py) => {
+                    drawing += &format!("{} {} {}\n", x(px), y(py), line_to)
+                }
+                PathSegment::CurveTo(x1, y1, x2, y2, x3, y3) => {
+                    drawing += &format!(
+                        "{} {} {} {} {} {} {}\n",
+                        x(x1),
+                        y(y1),
+                        x(x2),
+                        y(y2),
+                        x(x3),
+                        y(y3),
+                        curve_to
+                    )
+                }
+                PathSegment::Close => {
+                    drawing += close;
+                    drawing.push('\n');
+                }
+            }
+        }
+    }
+
+    drawing += fill;
+    drawing.push('\n');
+    drawing
+}
+
+/**
+ * The vector formats for print
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrintFormat {
+    Eps,
+    PostScript,
+    Pdf,
+}
+
+/**
+ * Vector paths at the physical size of the print options, in the quiet zone and module shapes of the
+ * render options. The render options' colors are ignored, the print options have the inks.
+ */
+pub struct PrintRenderer {
+    format: PrintFormat,
+    print: PrintOptions,
+    quiet_zone: usize,
+    style: Style,
+    grid: ModuleGrid,
+}
+
+impl PrintRenderer {
+    pub fn new(format: PrintFormat, print: PrintOptions) -> PrintRenderer {
+        PrintRenderer {
+            format,
+            print,
+            quiet_zone: 0,
+            style: Style::default(),
+            grid: ModuleGrid::default(),
+        }
+    }
+}
+
+impl Renderer for PrintRenderer {
+    type Output = Vec<u8>;
+
+    fn begin(&mut self, _qr_code: &QrCode, options: &RenderOptions, width: usize) {
+        self.quiet_zone = options.quiet_zone;
+        self.style = options.style;
+        self.grid.begin(width);
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        self.grid.set(x, y, dark);
+    }
+
+    fn end(self) -> Vec<u8> {
+        let shapes = style::shapes(&self.grid.rows, self.quiet_zone, &self.style);
+        let width = self.grid.rows.len();
+
+        match self.format {
+            PrintFormat::Eps => render_eps(&shapes, width, &self.print).into_bytes(),
+            PrintFormat::PostScript => render_postscript(&shapes, width, &self.print).into_bytes(),
+            PrintFormat::Pdf => render_pdf(&shapes, width, &self.print),
+        }
+    }
+}
+
+/**
+ * Encapsulated PostScript with the bounding box set to the code, for placing in a layout
+ *
+ * based on https://www.adobe.com/content/dam/acom/en/devnet/actionscript/articles/5002.EPSF_Spec.pdf
+ */
+pub fn render_eps(shapes: &Shapes, width: usize, options: &PrintOptions) -> String {
+    let size = options.size.to_points();
+
+    format!(
+        concat!(
+            "%!PS-Adobe-3.0 EPSF-3.0\n",
+            "%%BoundingBox: 0 0 {rounded} {rounded}\n",
+            "%%HiResBoundingBox: 0 0 {size} {size}\n",
+            "%%Creator: qr-creator\n",
+            "%%Title: QR code\n",
+            "%%LanguageLevel: 2\n",
+            "%%EndComments\n",
+            "{procedures}",
+            "{drawing}",
+            "%%EOF\n"
+        ),
+        rounded = size.ceil() as u64,
+        size = format_number(size),
+        procedures = POSTSCRIPT_PROCEDURES,
+        drawing = postscript_drawing(shapes, width, options)
+    )
+}
+
+/**
+ * A single page PostScript document the size of the code
+ *
+ * based on https://www.adobe.com/jp/print/postscript/pdfs/PLRM.pdf
+ */
+pub fn render_postscript(shapes: &Shapes, width: usize, options: &PrintOptions) -> String {
+    let size = format_number(options.size.to_points());
+
+    format!(
+        concat!(
+            "%!PS-Adobe-3.0\n",
+            "%%BoundingBox: 0 0 {rounded} {rounded}\n",
+            "%%Creator: qr-creator\n",
+            "%%Title: QR code\n",
+            "%%Pages: 1\n",
+            "%%LanguageLevel: 2\n",
+            "%%EndComments\n",
+            "{procedures}",
+            "%%Page: 1 1\n",
+            "<< /PageSize [{size} {size}] >> setpagedevice\n",
+            "{drawing}",
+            "showpage\n",
+            "%%EOF\n"
+        ),
+        rounded = options.size.to_points().ceil() as u64,
+        size = size,
+        procedures = POSTSCRIPT_PROCEDURES,
+        drawing = postscript_drawing(shapes, width, options)
+    )
+}
+
+const POSTSCRIPT_PROCEDURES: &str = concat!(
+    "%%BeginProlog\n",
+    "/m /moveto load def\n",
+    "/l /lineto load def\n",
+    "/c /curveto load def\n",
+    "/h /closepath load def\n",
+    "%%EndProlog\n",
+);
+
+fn postscript_drawing(shapes: &Shapes, width: usize, options: &PrintOptions) -> String {
+    let drawing = drawing(
+        shapes,
+        width,
+        options,
+        ["setcmykcolor", "rectfill", "m", "l", "c", "h", "fill"],
+    );
+
+    format!("newpath\n{}", drawing)
+}
+
+/**
+ * A single page PDF with the media and trim boxes set to the code
+ *
+ * based on https://opensource.adobe.com/dc-acrobat-sdk-docs/pdfstandards/PDF32000_2008.pdf
+ */
+pub fn render_pdf(shapes: &Shapes, width: usize, options: &PrintOptions) -> Vec<u8> {
+    let size = format_number(options.size.to_points());
+    let content = drawing(
+        shapes,
+        width,
+        options,
+        ["k", "re f", "m", "l", "c", "h", "f"],
+    );
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {0} {0}] /TrimBox [0 0 {0} {0}] /Resources << >> /Contents 4 0 R >>",
+            size
+        ),
+        format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ),
+        "<< /Producer (qr-creator) /Title (QR code) >>".to_string(),
+    ];
+
+    // the binary comment marks the file as binary for transfer programs
+    let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+
+    let xref = pdf.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        table += &format!("{:010} 00000 n \n", offset);
+    }
+
+    pdf.extend_from_slice(table.as_bytes());
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .as_bytes(),
+    );
+
+    pdf
+}
+
+#[test]
+fn test_length_parse() {
+    assert_eq!(Length::parse("1in"), Ok(Length::points(72.0)));
+    assert_eq!(Length::parse("25.4 mm"), Ok(Length::points(72.0)));
+    assert!((Length::parse("2.54cm").unwrap().to_points() - 72.0).abs() < 1e-9);
+    assert!(Length::parse("30").is_err());
+    assert!(Length::parse("-1mm").is_err());
+    assert!(Length::parse("1ft").is_err());
+}
+
+#[test]
+fn test_cmyk_parse() {
+    assert_eq!(Cmyk::parse("100, 50,0,10"), Ok(Cmyk::new(100, 50, 0, 10)));
+    assert!(Cmyk::parse("0,0,0").is_err());
+    assert!(Cmyk::parse("0,0,0,101").is_err());
+}
@@ -0,0 +1,128 @@
+use crate::qr_code::render::renderer::{ModuleGrid, Renderer};
+use crate::qr_code::render::style::{self, Region};
+use crate::qr_code::render::{bmp, escpos, gif, netpbm, png, zpl};
+use crate::qr_code::render::{Color, Fill, RenderOptions};
+use crate::qr_code::QrCode;
+
+/**
+ * The image formats drawn pixel by pixel, `options.module_size` pixels to a module
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RasterFormat {
+    /// 1-bit grayscale, or truecolor when the code is not black on white
+    Png,
+    /// 1-bit, colors are ignored
+    Bmp,
+    /// raw (P4) PBM, colors are ignored
+    Pbm,
+    /// plain text (P1) PBM, colors are ignored
+    PlainPbm,
+    /// 8-bit grayscale PGM, colors are ignored
+    Pgm,
+    /// black and white GIF, colors are ignored
+    Gif,
+    /// a ZPL label with the image as a graphic field, colors are ignored
+    ZplGraphic,
+    /// ESC/POS commands that print the image as a raster bit image, colors are ignored
+    EscPosRaster,
+}
+
+/**
+ * Draws the modules in the style of the options into pixels, then encodes them in the format
+ */
+pub struct RasterRenderer {
+    format: RasterFormat,
+    options: RenderOptions,
+    grid: ModuleGrid,
+}
+
+impl RasterRenderer {
+    pub fn new(format: RasterFormat) -> RasterRenderer {
+        RasterRenderer {
+            format,
+            options: RenderOptions::default(),
+            grid: ModuleGrid::default(),
+        }
+    }
+}
+
+impl Renderer for RasterRenderer {
+    type Output = Vec<u8>;
+
+    fn begin(&mut self, _qr_code: &QrCode, options: &RenderOptions, width: usize) {
+        self.options = options.clone();
+        self.grid.begin(width);
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        self.grid.set(x, y, dark);
+    }
+
+    fn end(self) -> Vec<u8> {
+        let options = &self.options;
+        let width = self.grid.rows.len();
+        let shapes = style::shapes(&self.grid.rows, options.quiet_zone, &options.style);
+        let image = style::rasterize(&shapes, width, options.module_size);
+
+        match self.format {
+            RasterFormat::Png => render_png(&image, width, options),
+            RasterFormat::Bmp => bmp::render_bmp(&monochrome(&image), options.dpi),
+            RasterFormat::Pbm => netpbm::render_pbm(&monochrome(&image), false),
+            RasterFormat::PlainPbm => netpbm::render_pbm(&monochrome(&image), true),
+            RasterFormat::Pgm => netpbm::render_pgm(&monochrome(&image)),
+            RasterFormat::Gif => gif::render_gif(&monochrome(&image)),
+            RasterFormat::ZplGraphic => zpl::render_zpl_graphic(&monochrome(&image)).into_bytes(),
+            RasterFormat::EscPosRaster => escpos::render_escpos_raster(&monochrome(&image)),
+        }
+    }
+}
+
+/**
+ * A 1-bit grayscale PNG when the code is black on white, otherwise a truecolor one. Background
+ * images are only drawn in SVG output.
+ */
+fn render_png(image: &[Vec<Option<Region>>], width: usize, options: &RenderOptions) -> Vec<u8> {
+    let colors = &options.colors;
+
+    let black = Fill::Solid(Color::BLACK);
+    if colors.foreground == black
+        && colors.finder.is_none_or(|finder| finder == black)
+        && colors.background == Color::WHITE
+    {
+        return png::render_png(&monochrome(image), options.dpi);
+    }
+
+    // gradients span the symbol without the quiet zone
+    let symbol_width = width.saturating_sub(2 * options.quiet_zone).max(1);
+    let symbol_pixels = (symbol_width * options.module_size) as f64;
+    let offset = (options.quiet_zone * options.module_size) as f64;
+    let position = |pixel: usize| (pixel as f64 + 0.5 - offset) / symbol_pixels;
+
+    let image: Vec<Vec<Color>> = image
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, region)| {
+                    let fill = match region {
+                        None => return colors.background,
+                        Some(Region::Finder) => colors.finder.unwrap_or(colors.foreground),
+                        Some(Region::Module) => colors.foreground,
+                    };
+
+                    fill.color_at(position(x), position(y))
+                })
+                .collect()
+        })
+        .collect();
+
+    png::render_png_rgb(&image, options.dpi)
+}
+
+fn monochrome(image: &[Vec<Option<Region>>]) -> Vec<Vec<bool>> {
+    image
+        .iter()
+        .map(|row| row.iter().map(Option::is_some).collect())
+        .collect()
+}
@@ -0,0 +1,93 @@
+use crate::qr_code::render::RenderOptions;
+use crate::qr_code::QrCode;
+
+/**
+ * An output backend, implemented by every built-in format and open to other crates for their own
+ * targets.
+ *
+ * `QrCode::render` calls `begin`, then `draw_finder` for each finder pattern, then `draw_run` for
+ * every row from top to bottom as runs of modules of the same color from left to right, then `end`.
+ * Every module including the quiet zone is part of exactly one run, with `options.invert` already
+ * applied. Coordinates are in modules from the top left of the quiet zone.
+ */
+pub trait Renderer {
+    type Output;
+
+    /**
+     * Called once before anything is drawn, with the width in modules including the quiet zone
+     */
+    fn begin(&mut self, qr_code: &QrCode, options: &RenderOptions, width: usize);
+
+    /**
+     * The top left module of a 7x7 finder pattern. Its modules are still passed to `draw_run`,
+     * renderers that draw their own finder patterns can skip runs inside it.
+     */
+    fn draw_finder(&mut self, _x: usize, _y: usize) {}
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool);
+
+    /**
+     * A run of `length` modules starting at column x, draws each module by default
+     */
+    fn draw_run(&mut self, x: usize, y: usize, length: usize, dark: bool) {
+        for x in x..x + length {
+            self.draw_module(x, y, dark);
+        }
+    }
+
+    fn end(self) -> Self::Output;
+}
+
+/**
+ * Passes the modules of the QR code with the quiet zone of the options to the renderer
+ */
+pub fn render<R: Renderer>(
+    qr_code: &QrCode,
+    options: &RenderOptions,
+    mut renderer: R,
+) -> R::Output {
+    let quiet_zone = options.quiet_zone;
+    let matrix = qr_code.to_matrix(quiet_zone);
+    let symbol_width = qr_code.width();
+
+    renderer.begin(qr_code, options, matrix.len());
+
+    for (x, y) in [
+        (quiet_zone, quiet_zone),
+        (quiet_zone + symbol_width - 7, quiet_zone),
+        (quiet_zone, quiet_zone + symbol_width - 7),
+    ] {
+        renderer.draw_finder(x, y);
+    }
+
+    for (y, row) in matrix.iter().enumerate() {
+        let mut start = 0;
+
+        for x in 1..=row.len() {
+            if x == row.len() || row[x] != row[start] {
+                renderer.draw_run(start, y, x - start, row[start] != options.invert);
+                start = x;
+            }
+        }
+    }
+
+    renderer.end()
+}
+
+/**
+ * Collects the modules into rows, for renderers that need the whole matrix at once
+ */
+#[derive(Default)]
+pub struct ModuleGrid {
+    pub rows: Vec<Vec<bool>>,
+}
+
+impl ModuleGrid {
+    pub fn begin(&mut self, width: usize) {
+        self.rows = vec![vec![false; width]; width];
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, dark: bool) {
+        self.rows[y][x] = dark;
+    }
+}
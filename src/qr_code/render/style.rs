@@ -0,0 +1,623 @@
+/**
+ * How the dark modules outside the finder patterns are drawn. Every shape stays inside the modules it
+ * stands for, so the grid a scanner samples is unchanged.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleShape {
+    Square,
+    /// a dot as wide as the module
+    Circle,
+    /// squares with the corners that have no dark neighbours rounded off
+    Rounded,
+    /// dark modules in the same column joined into bars with rounded ends
+    VerticalBars,
+    /// dark modules in the same row joined into bars with rounded ends
+    HorizontalBars,
+}
+
+/**
+ * The shape of the 7x7 outer ring or the 3x3 inner eye of the finder patterns
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FinderShape {
+    Square,
+    /// a square with its corners rounded by the corner radius
+    Rounded,
+    Circle,
+}
+
+/**
+ * Styling for SVG and raster output, the default draws plain square modules
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Style {
+    pub module_shape: ModuleShape,
+    pub finder_ring: FinderShape,
+    pub finder_eye: FinderShape,
+    /// radius of the rounded shapes as a percentage of their width, 50 makes a circle
+    pub corner_radius: u8,
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style {
+            module_shape: ModuleShape::Square,
+            finder_ring: FinderShape::Square,
+            finder_eye: FinderShape::Square,
+            corner_radius: 25,
+        }
+    }
+}
+
+impl Style {
+    /**
+     * Whether every shape is made of straight edges along the module grid
+     */
+    pub fn is_square(&self) -> bool {
+        self.module_shape == ModuleShape::Square
+            && self.finder_ring == FinderShape::Square
+            && self.finder_eye == FinderShape::Square
+    }
+
+    fn radius(&self, width: f64) -> f64 {
+        width * self.corner_radius.min(50) as f64 / 100.0
+    }
+
+    fn finder_radius(&self, shape: FinderShape, width: f64) -> f64 {
+        match shape {
+            FinderShape::Square => 0.0,
+            FinderShape::Rounded => self.radius(width),
+            FinderShape::Circle => width / 2.0,
+        }
+    }
+}
+
+/**
+ * A rectangle in module units with the radius of each corner, clockwise from the top left
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundedRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub radii: [f64; 4],
+}
+
+impl RoundedRect {
+    fn new(x: f64, y: f64, width: f64, height: f64, radius: f64) -> RoundedRect {
+        RoundedRect {
+            x,
+            y,
+            width,
+            height,
+            radii: [radius; 4],
+        }
+    }
+
+    /**
+     * Whether the point lies inside the rectangle and not in one of the rounded off corners
+     */
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let right = self.x + self.width;
+        let bottom = self.y + self.height;
+
+        if x < self.x || x >= right || y < self.y || y >= bottom {
+            return false;
+        }
+
+        let corners = [
+            (
+                self.x + self.radii[0],
+                self.y + self.radii[0],
+                self.radii[0],
+            ),
+            (right - self.radii[1], self.y + self.radii[1], self.radii[1]),
+            (right - self.radii[2], bottom - self.radii[2], self.radii[2]),
+            (
+                self.x + self.radii[3],
+                bottom - self.radii[3],
+                self.radii[3],
+            ),
+        ];
+
+        corners.iter().enumerate().all(|(i, &(cx, cy, radius))| {
+            let outside_x = if i == 0 || i == 3 { x < cx } else { x > cx };
+            let outside_y = if i < 2 { y < cy } else { y > cy };
+
+            !(outside_x && outside_y) || (x - cx).powi(2) + (y - cy).powi(2) <= radius.powi(2)
+        })
+    }
+
+    /**
+     * The outline as lines and cubic Bezier curves, for formats without elliptical arcs. Each
+     * rounded corner is a quarter circle, approximated by a curve with its control points pulled
+     * towards the corner.
+     *
+     * based on https://spencermortensen.com/articles/bezier-circle/
+     */
+    pub fn outline(&self, hole: bool) -> Vec<PathSegment> {
+        const KAPPA: f64 = 0.552_284_749_8;
+
+        let (right, bottom) = (self.x + self.width, self.y + self.height);
+        let mut corners = [
+            ((self.x, self.y), self.radii[0]),
+            ((right, self.y), self.radii[1]),
+            ((right, bottom), self.radii[2]),
+            ((self.x, bottom), self.radii[3]),
+        ];
+
+        if hole {
+            corners.reverse();
+        }
+
+        let toward = |(from_x, from_y): (f64, f64), (to_x, to_y): (f64, f64), distance: f64| {
+            let length = ((to_x - from_x).powi(2) + (to_y - from_y).powi(2)).sqrt();
+            (
+                from_x + (to_x - from_x) / length * distance,
+                from_y + (to_y - from_y) / length * distance,
+            )
+        };
+
+        let count = corners.len();
+        let around = |i: usize| {
+            let (corner, radius) = corners[i % count];
+            let previous = corners[(i + count - 1) % count].0;
+            let next = corners[(i + 1) % count].0;
+
+            (
+                corner,
+                radius,
+                toward(corner, previous, radius),
+                toward(corner, next, radius),
+            )
+        };
+
+        let (_, _, _, start) = around(0);
+        let mut segments = vec![PathSegment::MoveTo(start.0, start.1)];
+
+        let mut current = start;
+
+        for i in 1..=count {
+            let (corner, radius, before, after) = around(i);
+
+            // straight edges between two fully rounded corners have no length
+            if before != current {
+                segments.push(PathSegment::LineTo(before.0, before.1));
+            }
+            current = after;
+
+            if radius > 0.0 {
+                let first = toward(before, corner, radius * KAPPA);
+                let second = toward(after, corner, radius * KAPPA);
+                segments.push(PathSegment::CurveTo(
+                    first.0, first.1, second.0, second.1, after.0, after.1,
+                ));
+            }
+        }
+
+        segments.push(PathSegment::Close);
+        segments
+    }
+
+    /**
+     * The outline as SVG path commands. A hole is drawn the other way round so the nonzero fill rule
+     * leaves it empty.
+     *
+     * based on https://www.w3.org/TR/SVG2/paths.html#PathDataEllipticalArcCommands
+     */
+    pub fn svg_path(&self, hole: bool) -> String {
+        let (x, y, w, h) = (self.x, self.y, self.width, self.height);
+        let n = format_number;
+
+        if self.radii.iter().all(|&radius| radius == 0.0) {
+            return if hole {
+                format!("M{},{}v{}h{}v-{}z", n(x), n(y), n(h), n(w), n(h))
+            } else {
+                format!("M{},{}h{}v{}h-{}z", n(x), n(y), n(w), n(h), n(w))
+            };
+        }
+
+        let [top_left, top_right, bottom_right, bottom_left] = self.radii;
+        let (right, bottom) = (x + w, y + h);
+        let sweep = if hole { 0 } else { 1 };
+
+        let arc = |radius: f64, end_x: f64, end_y: f64| {
+            if radius == 0.0 {
+                String::new()
+            } else {
+                format!(
+                    "A{},{} 0 0 {} {},{}",
+                    n(radius),
+                    n(radius),
+                    sweep,
+                    n(end_x),
+                    n(end_y)
+                )
+            }
+        };
+
+        if hole {
+            format!(
+                "M{},{}{}V{}{}H{}{}V{}{}z",
+                n(x + top_left),
+                n(y),
+                arc(top_left, x, y + top_left),
+                n(bottom - bottom_left),
+                arc(bottom_left, x + bottom_left, bottom),
+                n(right - bottom_right),
+                arc(bottom_right, right, bottom - bottom_right),
+                n(y + top_right),
+                arc(top_right, right - top_right, y),
+            )
+        } else {
+            format!(
+                "M{},{}H{}{}V{}{}H{}{}V{}{}z",
+                n(x + top_left),
+                n(y),
+                n(right - top_right),
+                arc(top_right, right, y + top_right),
+                n(bottom - bottom_right),
+                arc(bottom_right, right - bottom_right, bottom),
+                n(x + bottom_left),
+                arc(bottom_left, x, bottom - bottom_left),
+                n(y + top_left),
+                arc(top_left, x + top_left, y),
+            )
+        }
+    }
+}
+
+/**
+ * A step along an outline in module units
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathSegment {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    /// two control points and the end point
+    CurveTo(f64, f64, f64, f64, f64, f64),
+    Close,
+}
+
+/**
+ * A dark area of the symbol
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    Rect(RoundedRect),
+    /// the outer rectangle with the inner one cut out
+    Ring(RoundedRect, RoundedRect),
+}
+
+impl Shape {
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        match self {
+            Shape::Rect(rect) => rect.contains(x, y),
+            Shape::Ring(outer, inner) => outer.contains(x, y) && !inner.contains(x, y),
+        }
+    }
+
+    pub fn outline(&self) -> Vec<PathSegment> {
+        match self {
+            Shape::Rect(rect) => rect.outline(false),
+            Shape::Ring(outer, inner) => {
+                let mut outline = outer.outline(false);
+                outline.extend(inner.outline(true));
+                outline
+            }
+        }
+    }
+
+    pub fn svg_path(&self) -> String {
+        match self {
+            Shape::Rect(rect) => rect.svg_path(false),
+            Shape::Ring(outer, inner) => outer.svg_path(false) + &inner.svg_path(true),
+        }
+    }
+
+    /**
+     * The left, top, right and bottom edges
+     */
+    pub fn bounds(&self) -> (f64, f64, f64, f64) {
+        let (Shape::Rect(rect) | Shape::Ring(rect, _)) = self;
+        (rect.x, rect.y, rect.x + rect.width, rect.y + rect.height)
+    }
+}
+
+/**
+ * The part of the symbol a shape belongs to, which can be painted differently
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Region {
+    Finder,
+    Module,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Shapes {
+    /// a ring and an eye for each finder pattern
+    pub finders: Vec<Shape>,
+    /// every other dark module
+    pub modules: Vec<Shape>,
+}
+
+/**
+ * Breaks the dark modules of a matrix with a quiet zone into shapes in the given style, with each
+ * finder pattern drawn as a ring and an eye
+ */
+pub fn shapes(matrix: &[Vec<bool>], quiet_zone: usize, style: &Style) -> Shapes {
+    let size = matrix.len();
+    let symbol_width = size.saturating_sub(2 * quiet_zone);
+    let mut shapes = Shapes::default();
+
+    if symbol_width < 21 {
+        return shapes;
+    }
+
+    // the finder patterns of an inverted code are light, they are drawn module by module
+    let finders: Vec<(usize, usize)> = [
+        (quiet_zone, quiet_zone),
+        (quiet_zone + symbol_width - 7, quiet_zone),
+        (quiet_zone, quiet_zone + symbol_width - 7),
+    ]
+    .into_iter()
+    .filter(|&(x, y)| matrix[y][x])
+    .collect();
+
+    for &(x, y) in &finders {
+        let (x, y) = (x as f64, y as f64);
+        let ring_radius = style.finder_radius(style.finder_ring, 7.0);
+
+        shapes.finders.push(Shape::Ring(
+            RoundedRect::new(x, y, 7.0, 7.0, ring_radius),
+            RoundedRect::new(x + 1.0, y + 1.0, 5.0, 5.0, (ring_radius - 1.0).max(0.0)),
+        ));
+        shapes.finders.push(Shape::Rect(RoundedRect::new(
+            x + 2.0,
+            y + 2.0,
+            3.0,
+            3.0,
+            style.finder_radius(style.finder_eye, 3.0),
+        )));
+    }
+
+    let in_finder = |x: usize, y: usize| {
+        finders
+            .iter()
+            .any(|&(fx, fy)| (fx..fx + 7).contains(&x) && (fy..fy + 7).contains(&y))
+    };
+    let dark = |x: usize, y: usize| x < size && y < size && matrix[y][x] && !in_finder(x, y);
+    let radius = style.radius(1.0);
+
+    for y in 0..size {
+        for x in 0..size {
+            if !dark(x, y) {
+                continue;
+            }
+
+            let (left, top) = (x as f64, y as f64);
+
+            match style.module_shape {
+                ModuleShape::Square => shapes
+                    .modules
+                    .push(Shape::Rect(RoundedRect::new(left, top, 1.0, 1.0, 0.0))),
+                ModuleShape::Circle => shapes
+                    .modules
+                    .push(Shape::Rect(RoundedRect::new(left, top, 1.0, 1.0, 0.5))),
+                ModuleShape::Rounded => {
+                    let up = y > 0 && dark(x, y - 1);
+                    let down = dark(x, y + 1);
+                    let left_dark = x > 0 && dark(x - 1, y);
+                    let right_dark = dark(x + 1, y);
+                    let round = |a: bool, b: bool| if a || b { 0.0 } else { radius };
+
+                    shapes.modules.push(Shape::Rect(RoundedRect {
+                        x: left,
+                        y: top,
+                        width: 1.0,
+                        height: 1.0,
+                        radii: [
+                            round(up, left_dark),
+                            round(up, right_dark),
+                            round(down, right_dark),
+                            round(down, left_dark),
+                        ],
+                    }));
+                }
+                ModuleShape::VerticalBars => {
+                    // only the first module of each run starts a bar
+                    if y > 0 && dark(x, y - 1) {
+                        continue;
+                    }
+
+                    let length = (y..size).take_while(|&y| dark(x, y)).count();
+                    shapes.modules.push(Shape::Rect(RoundedRect::new(
+                        left,
+                        top,
+                        1.0,
+                        length as f64,
+                        radius,
+                    )));
+                }
+                ModuleShape::HorizontalBars => {
+                    if x > 0 && dark(x - 1, y) {
+                        continue;
+                    }
+
+                    let length = (x..size).take_while(|&x| dark(x, y)).count();
+                    shapes.modules.push(Shape::Rect(RoundedRect::new(
+                        left,
+                        top,
+                        length as f64,
+                        1.0,
+                        radius,
+                    )));
+                }
+            }
+        }
+    }
+
+    shapes
+}
+
+/**
+ * Marks the pixels whose centres fall inside a shape with its region, `module_size` pixels to a
+ * module
+ */
+pub fn rasterize(shapes: &Shapes, width: usize, module_size: usize) -> Vec<Vec<Option<Region>>> {
+    let pixels = width * module_size;
+    let scale = module_size as f64;
+    let mut image = vec![vec![None; pixels]; pixels];
+
+    let regions = shapes
+        .finders
+        .iter()
+        .map(|shape| (Region::Finder, shape))
+        .chain(shapes.modules.iter().map(|shape| (Region::Module, shape)));
+
+    for (region, shape) in regions {
+        let (left, top, right, bottom) = shape.bounds();
+        let to_pixel = |edge: f64| ((edge * scale) as usize).min(pixels);
+
+        for (py, row) in image
+            .iter_mut()
+            .enumerate()
+            .take(to_pixel(bottom))
+            .skip(to_pixel(top))
+        {
+            for (px, pixel) in row
+                .iter_mut()
+                .enumerate()
+                .take(to_pixel(right))
+                .skip(to_pixel(left))
+            {
+                if shape.contains((px as f64 + 0.5) / scale, (py as f64 + 0.5) / scale) {
+                    *pixel = Some(region);
+                }
+            }
+        }
+    }
+
+    image
+}
+
+/**
+ * Up to three decimals without trailing zeros
+ */
+pub fn format_number(number: f64) -> String {
+    let formatted = format!("{:.3}", number);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    match trimmed {
+        "-0" => "0".to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+#[test]
+fn test_rounded_rect_contains() {
+    let circle = RoundedRect::new(0.0, 0.0, 1.0, 1.0, 0.5);
+
+    assert!(circle.contains(0.5, 0.5));
+    assert!(circle.contains(0.5, 0.05));
+    assert!(!circle.contains(0.05, 0.05));
+    assert!(!circle.contains(0.95, 0.95));
+    assert!(RoundedRect::new(0.0, 0.0, 1.0, 1.0, 0.0).contains(0.05, 0.05));
+}
+
+#[test]
+fn test_rounded_rect_outline() {
+    let square = RoundedRect::new(1.0, 2.0, 1.0, 1.0, 0.0).outline(false);
+    assert_eq!(
+        square,
+        vec![
+            PathSegment::MoveTo(1.0, 2.0),
+            PathSegment::LineTo(2.0, 2.0),
+            PathSegment::LineTo(2.0, 3.0),
+            PathSegment::LineTo(1.0, 3.0),
+            PathSegment::LineTo(1.0, 2.0),
+            PathSegment::Close,
+        ]
+    );
+
+    // a hole runs the other way, from the bottom left corner towards the bottom right
+    let circle = RoundedRect::new(0.0, 0.0, 2.0, 2.0, 1.0).outline(true);
+    assert_eq!(circle[0], PathSegment::MoveTo(1.0, 2.0));
+    assert_eq!(circle.len(), 6);
+    assert!(matches!(
+        circle[1],
+        PathSegment::CurveTo(_, _, _, _, 2.0, 1.0)
+    ));
+}
+
+#[test]
+fn test_rounded_rect_path() {
+    assert_eq!(
+        RoundedRect::new(3.0, 4.0, 1.0, 1.0, 0.0).svg_path(false),
+        "M3,4h1v1h-1z"
+    );
+    assert_eq!(
+        RoundedRect::new(0.0, 0.0, 2.0, 1.0, 0.5).svg_path(false),
+        "M0.5,0H1.5A0.5,0.5 0 0 1 2,0.5V0.5A0.5,0.5 0 0 1 1.5,1H0.5A0.5,0.5 0 0 1 0,0.5V0.5A0.5,0.5 0 0 1 0.5,0z"
+    );
+}
+
+#[test]
+fn test_shapes_keep_module_centres() {
+    use crate::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode};
+
+    let qr_code = QrCode::create(
+        "HELLO WORLD".to_string(),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Quartile,
+    )
+    .unwrap();
+    let matrix = qr_code.to_matrix(4);
+
+    for module_shape in [
+        ModuleShape::Square,
+        ModuleShape::Circle,
+        ModuleShape::Rounded,
+        ModuleShape::VerticalBars,
+        ModuleShape::HorizontalBars,
+    ] {
+        for finder in [
+            FinderShape::Square,
+            FinderShape::Rounded,
+            FinderShape::Circle,
+        ] {
+            let style = Style {
+                module_shape,
+                finder_ring: finder,
+                finder_eye: finder,
+                corner_radius: 50,
+            };
+            let image = rasterize(&shapes(&matrix, 4, &style), matrix.len(), 5);
+
+            for (y, row) in matrix.iter().enumerate() {
+                for (x, &dark) in row.iter().enumerate() {
+                    // rounded finder patterns only keep the 1:1:3:1:1 ratio through their centre
+                    let off_centre = [(4, 4), (18, 4), (4, 18)].iter().any(|&(fx, fy)| {
+                        (fx..fx + 7).contains(&x)
+                            && (fy..fy + 7).contains(&y)
+                            && x != fx + 3
+                            && y != fy + 3
+                    });
+                    if off_centre {
+                        continue;
+                    }
+
+                    assert_eq!(
+                        image[y * 5 + 2][x * 5 + 2].is_some(),
+                        dark,
+                        "module ({}, {}) with {:?}",
+                        x,
+                        y,
+                        style
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::qr_code::render::logo::{base64_encode, logo_overlay};
+use crate::qr_code::render::renderer::{ModuleGrid, Renderer};
+use crate::qr_code::render::style::{self, format_number, Shape, Shapes};
+use crate::qr_code::render::{Colors, RenderOptions};
+use crate::qr_code::QrCode;
+
+/**
+ * An SVG document in the style and colors of the options, with the logo drawn over the knockout
+ * area
+ */
+#[derive(Default)]
+pub struct SvgRenderer {
+    swiss_cross: bool,
+    options: RenderOptions,
+    overlay: String,
+    grid: ModuleGrid,
+}
+
+impl SvgRenderer {
+    pub fn new() -> SvgRenderer {
+        SvgRenderer::default()
+    }
+
+    /**
+     * Draws the Swiss cross in the middle, as required for the payment part of a Swiss QR-bill. The
+     * code is always black on white as the standard requires, the colors in the options are ignored.
+     */
+    pub fn swiss_qr_bill() -> SvgRenderer {
+        SvgRenderer {
+            swiss_cross: true,
+            ..SvgRenderer::default()
+        }
+    }
+}
+
+impl Renderer for SvgRenderer {
+    type Output = String;
+
+    fn begin(&mut self, qr_code: &QrCode, options: &RenderOptions, width: usize) {
+        self.options = options.clone();
+        self.grid.begin(width);
+
+        if self.swiss_cross {
+            self.options.colors = Colors::default();
+            self.overlay = swiss_cross(qr_code.width(), options.quiet_zone);
+        } else if let Some(logo) = &options.logo {
+            self.overlay = logo_overlay(qr_code, logo, options.quiet_zone, &options.colors);
+        }
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        self.grid.set(x, y, dark);
+    }
+
+    fn end(self) -> String {
+        let options = &self.options;
+        let shapes = style::shapes(&self.grid.rows, options.quiet_zone, &options.style);
+
+        render_svg(&shapes, self.grid.rows.len(), options, &self.overlay)
+    }
+}
+
+/**
+ * Draws the shapes of each region as a single path, so the file stays small and there are no
+ * hairline gaps between neighbouring modules when scaled. Square shapes keep crisp edges, anything
+ * rounded is anti-aliased.
+ *
+ * The overlay is drawn on top of the modules, such as a logo.
+ */
+pub fn render_svg(shapes: &Shapes, width: usize, options: &RenderOptions, overlay: &str) -> String {
+    let quiet_zone = options.quiet_zone;
+    let colors = &options.colors;
+    let pixels = width * options.module_size;
+    let symbol_width = width.saturating_sub(2 * quiet_zone);
+    let path = |shapes: &[Shape]| shapes.iter().map(Shape::svg_path).collect::<String>();
+
+    // with a dpi the document has a physical size, which is what the pixels would print at
+    let size = match options.dpi {
+        Some(dpi) => format!("{}mm", format_number(pixels as f64 / dpi as f64 * 25.4)),
+        None => pixels.to_string(),
+    };
+
+    let shape_rendering = if options.style.is_square() {
+        "crispEdges"
+    } else {
+        "geometricPrecision"
+    };
+
+    let (mut definitions, foreground) =
+        colors
+            .foreground
+            .to_svg_paint("foreground", quiet_zone, symbol_width);
+
+    let paths = match &colors.finder {
+        None => format!(
+            "<path d=\"{}{}\" fill=\"{}\"/>\n",
+            path(&shapes.finders),
+            path(&shapes.modules),
+            foreground
+        ),
+        Some(finder) => {
+            let (definition, finder) = finder.to_svg_paint("finder", quiet_zone, symbol_width);
+            definitions += &definition;
+
+            format!(
+                "<path d=\"{}\" fill=\"{}\"/>\n<path d=\"{}\" fill=\"{}\"/>\n",
+                path(&shapes.modules),
+                foreground,
+                path(&shapes.finders),
+                finder
+            )
+        }
+    };
+
+    if !definitions.is_empty() {
+        definitions = format!("<defs>\n{}</defs>\n", definitions);
+    }
+
+    let background_image = match &colors.background_image {
+        Some(image) => format!(
+            "<image width=\"{0}\" height=\"{0}\" preserveAspectRatio=\"xMidYMid slice\" href=\"data:{1};base64,{2}\"/>\n",
+            width,
+            image.mime_type,
+            base64_encode(&image.data)
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"{size}\" height=\"{size}\" ",
+            "viewBox=\"0 0 {width} {width}\" shape-rendering=\"{shape_rendering}\">\n",
+            "{definitions}",
+            "<rect width=\"100%\" height=\"100%\" fill=\"{background}\"/>\n",
+            "{background_image}",
+            "{paths}",
+            "{overlay}",
+            "</svg>\n"
+        ),
+        size = size,
+        width = width,
+        shape_rendering = shape_rendering,
+        definitions = definitions,
+        background = colors.background.to_hex(),
+        background_image = background_image,
+        paths = paths,
+        overlay = overlay
+    )
+}
+
+/**
+ * The Swiss cross required in the middle of a Swiss QR-bill code, 7 mm on a 46 mm code including a
+ * light border, with the cross arms in the proportions of the Swiss flag.
+ *
+ * based on section 6.4.2.1 of https://www.six-group.com/dam/download/banking-services/standardization/qr-bill/ig-qr-bill-v2.3-en.pdf
+ */
+pub fn swiss_cross(symbol_width: usize, quiet_zone: usize) -> String {
+    let mm = symbol_width as f64 / 46.0;
+    let center = quiet_zone as f64 + symbol_width as f64 / 2.0;
+
+    let square = |size: f64, fill: &str| {
+        format!(
+            "<rect x=\"{0:.3}\" y=\"{0:.3}\" width=\"{1:.3}\" height=\"{1:.3}\" fill=\"{2}\"/>\n",
+            center - size / 2.0,
+            size,
+            fill
+        )
+    };
+
+    let black_square = 6.0 * mm;
+    let arm_length = black_square * 20.0 / 32.0;
+    let arm_width = black_square * 6.0 / 32.0;
+
+    let arm = |width: f64, height: f64| {
+        format!(
+            "<rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" fill=\"#FFFFFF\"/>\n",
+            center - width / 2.0,
+            center - height / 2.0,
+            width,
+            height
+        )
+    };
+
+    square(7.0 * mm, "#FFFFFF")
+        + &square(black_square, "#000000")
+        + &arm(arm_length, arm_width)
+        + &arm(arm_width, arm_length)
+}
@@ -0,0 +1,61 @@
+use crate::qr_code::render::renderer::{ModuleGrid, Renderer};
+use crate::qr_code::render::RenderOptions;
+use crate::qr_code::QrCode;
+
+/**
+ * Unicode half block characters, two rows of modules per line of text
+ */
+#[derive(Default)]
+pub struct TerminalRenderer {
+    grid: ModuleGrid,
+}
+
+impl TerminalRenderer {
+    pub fn new() -> TerminalRenderer {
+        TerminalRenderer::default()
+    }
+}
+
+impl Renderer for TerminalRenderer {
+    type Output = String;
+
+    fn begin(&mut self, _qr_code: &QrCode, _options: &RenderOptions, width: usize) {
+        self.grid.begin(width);
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        self.grid.set(x, y, dark);
+    }
+
+    fn end(self) -> String {
+        render_terminal(&self.grid.rows)
+    }
+}
+
+/**
+ * Combines each pair of rows into one line of text using the upper half, lower half and full block
+ * characters
+ */
+pub fn render_terminal(matrix: &[Vec<bool>]) -> String {
+    let mut output = String::new();
+
+    for rows in matrix.chunks(2) {
+        let top = &rows[0];
+        let bottom = rows.get(1);
+
+        for (x, &top_dark) in top.iter().enumerate() {
+            let bottom_dark = bottom.map(|row| row[x]).unwrap_or(false);
+
+            output.push(match (top_dark, bottom_dark) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            });
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,51 @@
+use crate::qr_code::render::renderer::Renderer;
+use crate::qr_code::render::RenderOptions;
+use crate::qr_code::QrCode;
+
+/**
+ * One line of text per row of modules, with a string for each dark and each light module
+ */
+pub struct TextRenderer<'a> {
+    dark: &'a str,
+    light: &'a str,
+    width: usize,
+    output: String,
+}
+
+impl<'a> TextRenderer<'a> {
+    pub fn new(dark: &'a str, light: &'a str) -> TextRenderer<'a> {
+        TextRenderer {
+            dark,
+            light,
+            width: 0,
+            output: String::new(),
+        }
+    }
+}
+
+impl Renderer for TextRenderer<'_> {
+    type Output = String;
+
+    fn begin(&mut self, _qr_code: &QrCode, _options: &RenderOptions, width: usize) {
+        let characters = self.dark.len().max(self.light.len());
+        self.output = String::with_capacity(width * (width * characters + 1));
+        self.width = width;
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        self.draw_run(x, y, 1, dark);
+    }
+
+    fn draw_run(&mut self, x: usize, _y: usize, length: usize, dark: bool) {
+        let module = if dark { self.dark } else { self.light };
+        self.output += &module.repeat(length);
+
+        if x + length == self.width {
+            self.output.push('\n');
+        }
+    }
+
+    fn end(self) -> String {
+        self.output
+    }
+}
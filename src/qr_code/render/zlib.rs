@@ -0,0 +1,181 @@
+/**
+ * Compresses data into a zlib stream using a single fixed Huffman deflate block.
+ *
+ * Rendered QR codes are made of long runs of identical bytes and rows that repeat `module_size`
+ * times, so only two back references are tried: the previous byte and the previous row. This
+ * gets most of the benefit of a full LZ77 search for a fraction of the code.
+ *
+ * based on https://www.rfc-editor.org/rfc/rfc1951 and https://www.rfc-editor.org/rfc/rfc1950
+ */
+pub fn zlib_compress(data: &[u8], row_len: usize) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+
+    // final block, fixed Huffman codes
+    writer.write_bits(1, 1);
+    writer.write_bits(0b01, 2);
+
+    let mut i = 0;
+    while i < data.len() {
+        let run = match_length(data, i, 1);
+        let repeat = if row_len > 1 {
+            match_length(data, i, row_len)
+        } else {
+            0
+        };
+
+        let (length, distance) = if repeat >= run {
+            (repeat, row_len)
+        } else {
+            (run, 1)
+        };
+
+        if length >= 3 {
+            write_length(&mut writer, length);
+            write_distance(&mut writer, distance);
+            i += length;
+        } else {
+            write_literal(&mut writer, data[i] as u16);
+            i += 1;
+        }
+    }
+
+    // end of block
+    write_literal(&mut writer, 256);
+
+    let mut output = vec![0x78, 0x01];
+    output.extend(writer.finish());
+    output.extend_from_slice(&adler32(data).to_be_bytes());
+
+    output
+}
+
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/**
+ * How many bytes starting at `position` repeat the bytes `distance` back, up to the deflate maximum of 258
+ */
+fn match_length(data: &[u8], position: usize, distance: usize) -> usize {
+    if distance == 0 || position < distance || distance > 32768 {
+        return 0;
+    }
+
+    let mut length = 0;
+    while length < 258
+        && position + length < data.len()
+        && data[position + length] == data[position + length - distance]
+    {
+        length += 1;
+    }
+
+    length
+}
+
+fn write_literal(writer: &mut BitWriter, value: u16) {
+    let (code, len) = match value {
+        0..=143 => (0b00110000 + value, 8),
+        144..=255 => (0b110010000 + value - 144, 9),
+        256..=279 => (value - 256, 7),
+        _ => (0b11000000 + value - 280, 8),
+    };
+
+    writer.write_huffman(code as u32, len);
+}
+
+fn write_length(writer: &mut BitWriter, length: usize) {
+    let index = LENGTH_BASE
+        .iter()
+        .rposition(|&base| base <= length)
+        .unwrap();
+
+    write_literal(writer, 257 + index as u16);
+    writer.write_bits(
+        (length - LENGTH_BASE[index]) as u32,
+        LENGTH_EXTRA_BITS[index],
+    );
+}
+
+fn write_distance(writer: &mut BitWriter, distance: usize) {
+    let index = DISTANCE_BASE
+        .iter()
+        .rposition(|&base| base <= distance)
+        .unwrap();
+
+    writer.write_huffman(index as u32, 5);
+    writer.write_bits(
+        (distance - DISTANCE_BASE[index]) as u32,
+        DISTANCE_EXTRA_BITS[index],
+    );
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+/**
+ * Deflate packs values starting from the least significant bit, except Huffman codes which start
+ * from the most significant bit
+ */
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u32,
+    bit_count: u8,
+}
+
+impl BitWriter {
+    fn new() -> BitWriter {
+        BitWriter {
+            bytes: Vec::new(),
+            current: 0,
+            bit_count: 0,
+        }
+    }
+
+    fn write_bits(&mut self, value: u32, len: u8) {
+        for i in 0..len {
+            self.current |= ((value >> i) & 1) << self.bit_count;
+            self.bit_count += 1;
+
+            if self.bit_count == 8 {
+                self.bytes.push(self.current as u8);
+                self.current = 0;
+                self.bit_count = 0;
+            }
+        }
+    }
+
+    fn write_huffman(&mut self, code: u32, len: u8) {
+        for i in (0..len).rev() {
+            self.write_bits((code >> i) & 1, 1);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bit_count > 0 {
+            self.bytes.push(self.current as u8);
+        }
+
+        self.bytes
+    }
+}
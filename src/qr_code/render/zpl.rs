@@ -0,0 +1,104 @@
+use crate::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode};
+
+/**
+ * A label with the printer's own QR code command, in manual input mode so the printer uses the
+ * same error correction level and encoding mode as the crate. The printer still picks the version
+ * and mask.
+ *
+ * based on the ^BQ and ^FD commands of https://docs.zebra.com/us/en/printers/software/zpl-pg/c-zpl-zpl-commands.html
+ */
+pub fn render_zpl(
+    qr_code: &QrCode,
+    magnification: usize,
+    quiet_zone: usize,
+) -> Result<String, String> {
+    if !(1..=10).contains(&magnification) {
+        return Err(format!(
+            "ZPL QR codes can be magnified 1 to 10 times, found {}",
+            magnification
+        ));
+    }
+
+    let error_correction = match qr_code.error_correction_level {
+        ErrorCorrectionLevel::Low => 'L',
+        ErrorCorrectionLevel::Medium => 'M',
+        ErrorCorrectionLevel::Quartile => 'Q',
+        ErrorCorrectionLevel::High => 'H',
+    };
+
+    let data = match qr_code.encoding_mode {
+        EncodingMode::Numeric => format!("N{}", qr_code.data),
+        EncodingMode::Alphanumeric => format!("A{}", qr_code.data),
+        // byte mode data starts with its length in 4 digits
+        EncodingMode::Byte => format!("B{:04}{}", qr_code.data.len(), qr_code.data),
+    };
+
+    let offset = quiet_zone * magnification;
+
+    Ok(format!(
+        "^XA\n^FO{},{}\n^BQN,2,{}\n^FH^FD{}M,{}^FS\n^XZ\n",
+        offset,
+        offset,
+        magnification,
+        error_correction,
+        escape_field_data(&data)
+    ))
+}
+
+/**
+ * A label with the image as a graphic field, which prints exactly the matrix the crate created
+ *
+ * based on the ^GF command of https://docs.zebra.com/us/en/printers/software/zpl-pg/c-zpl-zpl-commands.html
+ */
+pub fn render_zpl_graphic(image: &[Vec<bool>]) -> String {
+    let pixels = image.len();
+    let row_bytes = pixels.div_ceil(8);
+    let total_bytes = row_bytes * pixels;
+
+    let mut data = String::with_capacity(total_bytes * 2 + pixels);
+
+    for row in image {
+        let mut line = vec![0u8; row_bytes];
+
+        // a set bit prints a black dot
+        for (x, &dark) in row.iter().enumerate() {
+            if dark {
+                line[x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+
+        for byte in line {
+            data += &format!("{:02X}", byte);
+        }
+        data.push('\n');
+    }
+
+    format!(
+        "^XA\n^FO0,0\n^GFA,{0},{0},{1},\n{2}^FS\n^XZ\n",
+        total_bytes, row_bytes, data
+    )
+}
+
+/**
+ * ^FH turns on hex escapes with _, which are needed for the characters ZPL uses for commands and
+ * for anything outside printable ASCII
+ */
+fn escape_field_data(data: &str) -> String {
+    let mut escaped = String::with_capacity(data.len());
+
+    for byte in data.bytes() {
+        match byte {
+            b'^' | b'~' | b'_' => escaped += &format!("_{:02X}", byte),
+            0x20..=0x7E => escaped.push(byte as char),
+            _ => escaped += &format!("_{:02X}", byte),
+        }
+    }
+
+    escaped
+}
+
+#[test]
+fn test_escape_field_data() {
+    assert_eq!(escape_field_data("A^B~C_D"), "A_5EB_7EC_5FD");
+    assert_eq!(escape_field_data("é\n"), "_C3_A9_0A");
+}
@@ -0,0 +1,257 @@
+use std::fmt;
+
+use crate::qr_code::character_count::{
+    calculate_binary_indicator_bit_length, create_character_count_indicator,
+};
+use crate::qr_code::encoding::encode;
+use crate::qr_code::error_correction::{
+    code_word_blocks, generate_error_correction, get_group_layout, GroupConfig,
+};
+use crate::qr_code::matrix::{
+    format_information_bits, mask_penalties, remainder_bits, version_information_bits,
+};
+use crate::qr_code::version::determine_data_bits_required_for_version;
+use crate::qr_code::{
+    construct_data, BitBuffer, EncodingMode, ErrorCorrectionLevel, Padding, QrCode, Version,
+};
+
+/**
+ * Every intermediate value of the encoding pipeline, useful when a scanner refuses a code
+ */
+pub struct EncodingReport {
+    pub version: Version,
+    pub error_correction_level: ErrorCorrectionLevel,
+    pub encoding_mode: EncodingMode,
+    pub mode_indicator: BitBuffer,
+    pub character_count: usize,
+    pub character_count_indicator: BitBuffer,
+    pub encoded_data: BitBuffer,
+    /// zero bits marking the end of the data
+    pub terminator_bits: usize,
+    /// zero bits added to reach a multiple of 8
+    pub alignment_bits: usize,
+    /// alternating 11101100 and 00010001 bytes filling the remaining capacity
+    pub pad_bytes: Vec<u8>,
+    pub data_code_words: Vec<u8>,
+    pub group_layout: GroupConfig,
+    pub blocks: Vec<BlockReport>,
+    /// data and error correction code words in the order they are placed in the matrix
+    pub final_message: Vec<u8>,
+    /// light modules left over after the final message is placed
+    pub remainder_bits: usize,
+    /// penalty score of each mask pattern, indexed by mask pattern
+    pub mask_penalties: [i32; 8],
+    pub mask: u8,
+    pub format_information: u32,
+    /// only versions 7 and up include version information
+    pub version_information: Option<u32>,
+}
+
+pub struct BlockReport {
+    pub group: u8,
+    pub data: Vec<u8>,
+    pub error_correction: Vec<u8>,
+}
+
+impl QrCode {
+    /**
+     * Repeats every stage of the pipeline for this QR code and collects the intermediate values.
+     * Fails when the public fields were changed to something no code can be created from, such as
+     * a version outside 1 to 40 or data the encoding mode cannot hold.
+     */
+    pub fn report(&self) -> Result<EncodingReport, String> {
+        let data_bits_required =
+            determine_data_bits_required_for_version(&self.version, &self.error_correction_level)?;
+
+        if self.mask > 7 {
+            return Err(format!(
+                "Mask pattern {} must be between 0 and 7",
+                self.mask
+            ));
+        }
+
+        let count_bits = calculate_binary_indicator_bit_length(&self.encoding_mode, &self.version);
+        if self.data.len() >> count_bits != 0 {
+            return Err(format!(
+                "{} characters do not fit in the {} bit character count indicator of {}",
+                self.data.len(),
+                count_bits,
+                self.version
+            ));
+        }
+
+        let mode_indicator = self.encoding_mode.to_bits();
+        let character_count_indicator =
+            create_character_count_indicator(&self.data, &self.encoding_mode, &self.version);
+        let encoded_data = encode(&self.data, &self.encoding_mode)?;
+
+        let constructed_data = construct_data(
+            &mode_indicator,
+            &character_count_indicator,
+            &encoded_data,
+            data_bits_required,
+        )?;
+
+        let padding = Padding::new(
+            mode_indicator.len() + character_count_indicator.len() + encoded_data.len(),
+            data_bits_required as usize,
+        );
+
+        let group_layout = get_group_layout(&self.error_correction_level, &self.version)?;
+        let final_message = generate_error_correction(
+            &constructed_data,
+            &self.error_correction_level,
+            &self.version,
+        )?;
+
+        // deinterleave the final message, each block starts with its data code words
+        let block_of = code_word_blocks(&self.error_correction_level, &self.version)?;
+        let group_one = group_layout.blocks_in_group_one as usize;
+        let mut blocks: Vec<BlockReport> = (0..group_layout.block_lengths().count())
+            .map(|block| BlockReport {
+                group: if block < group_one { 1 } else { 2 },
+                data: Vec::new(),
+                error_correction: Vec::new(),
+            })
+            .collect();
+
+        for (code_word, &block) in final_message.bytes().zip(&block_of) {
+            blocks[block].data.push(code_word);
+        }
+        for (block, len) in blocks.iter_mut().zip(group_layout.block_lengths()) {
+            block.error_correction = block.data.split_off(len);
+        }
+
+        let version_information = if self.version.version() >= 7 {
+            Some(version_information_bits(&self.version))
+        } else {
+            None
+        };
+
+        Ok(EncodingReport {
+            version: self.version,
+            error_correction_level: self.error_correction_level,
+            encoding_mode: self.encoding_mode,
+            mode_indicator,
+            character_count: self.data.len(),
+            character_count_indicator,
+            encoded_data,
+            terminator_bits: padding.terminator_bits,
+            alignment_bits: padding.alignment_bits,
+            pad_bytes: padding.pad_bytes().collect(),
+            data_code_words: constructed_data.bytes().collect(),
+            group_layout,
+            blocks,
+            final_message: final_message.bytes().collect(),
+            remainder_bits: remainder_bits(&final_message, &self.version),
+            mask_penalties: mask_penalties(
+                &final_message,
+                &self.error_correction_level,
+                &self.version,
+            ),
+            mask: self.mask,
+            format_information: format_information_bits(&self.error_correction_level, self.mask),
+            version_information,
+        })
+    }
+}
+
+impl fmt::Display for EncodingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "---- {} Encoding Report ----\n", self.version)?;
+        writeln!(f, "Encoding Mode: {}", self.encoding_mode)?;
+        writeln!(f, "Error Correction Level: {}", self.error_correction_level)?;
+
+        writeln!(f, "\nData")?;
+        writeln!(f, "  Mode indicator: {}", self.mode_indicator)?;
+        writeln!(
+            f,
+            "  Character count indicator ({} characters, {} bits): {}",
+            self.character_count,
+            self.character_count_indicator.len(),
+            self.character_count_indicator
+        )?;
+        writeln!(
+            f,
+            "  Encoded data ({} bits): {}",
+            self.encoded_data.len(),
+            self.encoded_data
+        )?;
+        writeln!(f, "  Terminator: {} bits", self.terminator_bits)?;
+        writeln!(
+            f,
+            "  Padding to a multiple of 8: {} bits",
+            self.alignment_bits
+        )?;
+        writeln!(
+            f,
+            "  Pad bytes ({}): {}",
+            self.pad_bytes.len(),
+            format_bytes(&self.pad_bytes)
+        )?;
+        writeln!(
+            f,
+            "  Data code words ({}): {}",
+            self.data_code_words.len(),
+            format_bytes(&self.data_code_words)
+        )?;
+
+        let layout = &self.group_layout;
+        writeln!(f, "\nBlocks")?;
+        writeln!(
+            f,
+            "  Group 1: {} blocks of {} data code words",
+            layout.blocks_in_group_one, layout.code_words_in_group_one_blocks
+        )?;
+        writeln!(
+            f,
+            "  Group 2: {} blocks of {} data code words",
+            layout.blocks_in_group_two, layout.code_words_in_group_two_blocks
+        )?;
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            writeln!(f, "  Block {} (group {})", i + 1, block.group)?;
+            writeln!(f, "    Data: {}", format_bytes(&block.data))?;
+            writeln!(
+                f,
+                "    Error correction ({}): {}",
+                block.error_correction.len(),
+                format_bytes(&block.error_correction)
+            )?;
+        }
+
+        writeln!(
+            f,
+            "\nInterleaved message ({} code words, {} remainder bits)",
+            self.final_message.len(),
+            self.remainder_bits
+        )?;
+        writeln!(f, "  {}", format_bytes(&self.final_message))?;
+
+        writeln!(f, "\nMask penalties")?;
+        for (mask, penalty) in self.mask_penalties.iter().enumerate() {
+            let chosen = if mask as u8 == self.mask {
+                " (chosen)"
+            } else {
+                ""
+            };
+
+            writeln!(f, "  Mask {}: {}{}", mask, penalty, chosen)?;
+        }
+
+        writeln!(f, "\nFormat information: {:015b}", self.format_information)?;
+
+        match self.version_information {
+            Some(bits) => writeln!(f, "Version information: {:018b}", bits),
+            None => writeln!(f, "Version information: none, versions 7 and up only"),
+        }
+    }
+}
+
+fn format_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| byte.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -1,15 +1,47 @@
 use super::{EncodingMode, ErrorCorrectionLevel, Version};
-//TODO: fix the values in the table
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, string::ToString};
+
 /**
  * Determines the smallest QR code version based on the EncodingMode, ErrorCorrectionLevel, and character count.
  *
  * based on https://www.thonky.com/qr-code-tutorial/character-capacities
  */
+#[cfg(feature = "alloc")]
 pub fn determine_optimal_qr_code_version(
     encoding_mode: &EncodingMode,
     error_correction_level: &ErrorCorrectionLevel,
     character_count: i32,
+    min_version: i16,
+    max_version: i16,
 ) -> Result<Version, String> {
+    if min_version < 1 || max_version > 40 || min_version > max_version {
+        return Err(format!(
+            "Version range {} to {} must be within 1 to 40",
+            min_version, max_version
+        ));
+    }
+
+    smallest_version(
+        encoding_mode,
+        error_correction_level,
+        character_count,
+        min_version,
+        max_version,
+    )
+    .ok_or_else(|| "Data is too large, no version found".to_string())
+}
+
+/**
+ * The smallest version in the range that holds the characters, the range must be within 1 to 40
+ */
+pub fn smallest_version(
+    encoding_mode: &EncodingMode,
+    error_correction_level: &ErrorCorrectionLevel,
+    character_count: i32,
+    min_version: i16,
+    max_version: i16,
+) -> Option<Version> {
     let version_limits: &[i32] = match (encoding_mode, error_correction_level) {
         (EncodingMode::Numeric, ErrorCorrectionLevel::Low) => &[
             41, 77, 127, 187, 255, 322, 370, 461, 552, 652, 772, 883, 1022, 1101, 1250, 1408, 1548,
@@ -73,13 +105,9 @@ pub fn determine_optimal_qr_code_version(
         ],
     };
 
-    for (i, &limit) in version_limits.iter().enumerate() {
-        if character_count <= limit {
-            return Ok(Version::Normal((i + 1) as i16));
-        }
-    }
-
-    Err("Data is too large, no version found".to_string())
+    (min_version..=max_version)
+        .find(|&version| character_count <= version_limits[version as usize - 1])
+        .map(Version::Normal)
 }
 
 /**
@@ -87,37 +115,74 @@ pub fn determine_optimal_qr_code_version(
 *
 * Based on https://www.thonky.com/qr-code-tutorial/error-correction-table
 */
+#[cfg(feature = "alloc")]
 pub fn determine_data_bits_required_for_version(
     version: &Version,
     error_correction_level: &ErrorCorrectionLevel,
 ) -> Result<i32, String> {
+    // multiply the number of code words by 8 to convert them to bits
+    data_code_words(version, error_correction_level)
+        .map(|code_words| code_words as i32 * 8)
+        .ok_or_else(|| format!("Version {} must be between 1 and 40", version.version()))
+}
+
+/**
+ * The number of data code words of the version, none for versions outside 1 to 40
+ */
+pub fn data_code_words(
+    version: &Version,
+    error_correction_level: &ErrorCorrectionLevel,
+) -> Option<usize> {
     let version = version.version();
 
-    let total_number_of_data_code_words: Vec<i32> = match error_correction_level {
-        ErrorCorrectionLevel::Low => vec![
+    if !(1..=40).contains(&version) {
+        return None;
+    }
+
+    let total_number_of_data_code_words: [usize; 40] = match error_correction_level {
+        ErrorCorrectionLevel::Low => [
             19, 34, 55, 80, 108, 136, 156, 194, 232, 274, 324, 370, 428, 461, 523, 589, 647, 721,
             795, 861, 932, 1006, 1094, 1174, 1276, 1370, 1468, 1531, 1631, 1735, 1843, 1955, 2071,
             2191, 2306, 2434, 2566, 2702, 2812, 2956,
         ],
-        ErrorCorrectionLevel::Medium => vec![
+        ErrorCorrectionLevel::Medium => [
             16, 28, 44, 64, 86, 108, 124, 154, 182, 216, 254, 290, 334, 365, 415, 453, 507, 563,
             627, 669, 714, 782, 860, 914, 1000, 1062, 1128, 1193, 1267, 1373, 1455, 1541, 1631,
             1725, 1812, 1914, 1992, 2102, 2216, 2334,
         ],
-        ErrorCorrectionLevel::Quartile => vec![
+        ErrorCorrectionLevel::Quartile => [
             13, 22, 34, 48, 62, 76, 88, 110, 132, 154, 180, 206, 244, 261, 295, 325, 367, 397, 445,
             485, 512, 568, 614, 664, 718, 754, 808, 871, 911, 985, 1033, 1115, 1171, 1231, 1286,
             1354, 1426, 1502, 1582, 1666,
         ],
-        ErrorCorrectionLevel::High => vec![
+        ErrorCorrectionLevel::High => [
             9, 16, 26, 36, 46, 60, 66, 86, 100, 122, 140, 158, 180, 197, 223, 253, 283, 313, 341,
             385, 406, 442, 464, 514, 538, 596, 628, 661, 701, 745, 793, 845, 901, 961, 986, 1054,
             1096, 1142, 1222, 1276,
         ],
     };
 
-    let bits_required = total_number_of_data_code_words[version as usize - 1];
+    Some(total_number_of_data_code_words[version as usize - 1])
+}
 
-    // multiply the number of code words by 8 to convert them to bits
-    Ok(bits_required * 8)
+#[cfg(feature = "alloc")]
+#[test]
+fn test_data_bits_required_for_version() {
+    assert_eq!(
+        determine_data_bits_required_for_version(
+            &Version::Normal(1),
+            &ErrorCorrectionLevel::Medium
+        ),
+        Ok(128)
+    );
+    assert!(determine_data_bits_required_for_version(
+        &Version::Normal(0),
+        &ErrorCorrectionLevel::Low
+    )
+    .is_err());
+    assert!(determine_data_bits_required_for_version(
+        &Version::Normal(41),
+        &ErrorCorrectionLevel::Low
+    )
+    .is_err());
 }
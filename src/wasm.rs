@@ -0,0 +1,237 @@
+use wasm_bindgen::prelude::*;
+
+use crate::qr_code::render::{Color, Fill, RenderOptions};
+use crate::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode, QrCodeOptions};
+
+/// the widest quiet zone rendering accepts, in modules
+pub const MAX_QUIET_ZONE: usize = 100;
+/// the largest module size rendering accepts, in pixels
+pub const MAX_MODULE_SIZE: usize = 100;
+
+/**
+ * Settings for creating and rendering a code, every field starts at the same default as
+ * `QrCodeOptions` and `RenderOptions`
+ */
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// auto, numeric, alphanumeric or byte
+    pub mode: String,
+    /// l, m, q or h, or the level written out
+    #[wasm_bindgen(js_name = errorCorrection)]
+    pub error_correction: String,
+    #[wasm_bindgen(js_name = minVersion)]
+    pub min_version: i16,
+    #[wasm_bindgen(js_name = maxVersion)]
+    pub max_version: i16,
+    /// picks the mask pattern with the lowest penalty score when not set
+    pub mask: Option<u8>,
+    /// 0 to 100 modules
+    #[wasm_bindgen(js_name = quietZone)]
+    pub quiet_zone: usize,
+    /// 1 to 100 pixels
+    #[wasm_bindgen(js_name = moduleSize)]
+    pub module_size: usize,
+    /// #RRGGBB or #RGB
+    pub foreground: String,
+    /// #RRGGBB or #RGB
+    pub background: String,
+    pub invert: bool,
+}
+
+#[wasm_bindgen]
+impl Options {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Options {
+        let qr_code_options = QrCodeOptions::default();
+        let render_options = RenderOptions::default();
+
+        Options {
+            mode: "auto".to_string(),
+            error_correction: "m".to_string(),
+            min_version: qr_code_options.min_version,
+            max_version: qr_code_options.max_version,
+            mask: qr_code_options.mask,
+            quiet_zone: render_options.quiet_zone,
+            module_size: render_options.module_size,
+            foreground: Color::BLACK.to_hex(),
+            background: render_options.colors.background.to_hex(),
+            invert: render_options.invert,
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options::new()
+    }
+}
+
+impl Options {
+    fn encoding_mode(&self, data: &str) -> Result<EncodingMode, String> {
+        match self.mode.to_ascii_lowercase().as_str() {
+            "auto" => Ok(EncodingMode::detect(data)),
+            "numeric" => Ok(EncodingMode::Numeric),
+            "alphanumeric" => Ok(EncodingMode::Alphanumeric),
+            "byte" => Ok(EncodingMode::Byte),
+            _ => Err(format!(
+                "Unknown mode \"{}\", expected auto, numeric, alphanumeric or byte",
+                self.mode
+            )),
+        }
+    }
+
+    fn error_correction_level(&self) -> Result<ErrorCorrectionLevel, String> {
+        match self.error_correction.to_ascii_lowercase().as_str() {
+            "l" | "low" => Ok(ErrorCorrectionLevel::Low),
+            "m" | "medium" => Ok(ErrorCorrectionLevel::Medium),
+            "q" | "quartile" => Ok(ErrorCorrectionLevel::Quartile),
+            "h" | "high" => Ok(ErrorCorrectionLevel::High),
+            _ => Err(format!(
+                "Unknown error correction level \"{}\", expected low, medium, quartile or high",
+                self.error_correction
+            )),
+        }
+    }
+
+    fn qr_code_options(&self) -> QrCodeOptions {
+        QrCodeOptions {
+            min_version: self.min_version,
+            max_version: self.max_version,
+            mask: self.mask,
+            ..QrCodeOptions::default()
+        }
+    }
+
+    fn render_options(&self) -> Result<RenderOptions, String> {
+        // larger sizes would make the renderers allocate more memory than the instance has
+        if self.quiet_zone > MAX_QUIET_ZONE {
+            return Err(format!(
+                "The quiet zone must be 0 to {} modules, found {}",
+                MAX_QUIET_ZONE, self.quiet_zone
+            ));
+        }
+        if !(1..=MAX_MODULE_SIZE).contains(&self.module_size) {
+            return Err(format!(
+                "The module size must be 1 to {} pixels, found {}",
+                MAX_MODULE_SIZE, self.module_size
+            ));
+        }
+
+        let mut render_options = RenderOptions {
+            quiet_zone: self.quiet_zone,
+            module_size: self.module_size,
+            invert: self.invert,
+            ..RenderOptions::default()
+        };
+        render_options.colors.foreground = Fill::Solid(Color::from_hex(&self.foreground)?);
+        render_options.colors.background = Color::from_hex(&self.background)?;
+
+        Ok(render_options)
+    }
+}
+
+/**
+ * A created code, rendered with the same renderers as `QrCode`
+ */
+#[wasm_bindgen(js_name = QrCode)]
+pub struct WasmQrCode {
+    qr_code: QrCode,
+}
+
+#[wasm_bindgen(js_class = QrCode)]
+impl WasmQrCode {
+    /**
+     * The number of modules along each side, without the quiet zone
+     */
+    pub fn width(&self) -> usize {
+        self.qr_code.width()
+    }
+
+    pub fn version(&self) -> i16 {
+        self.qr_code.version.version()
+    }
+
+    pub fn mask(&self) -> u8 {
+        self.qr_code.mask
+    }
+
+    /**
+     * Every module row by row without the quiet zone, 1 for dark and 0 for light, as a Uint8Array
+     * of width * width entries
+     */
+    pub fn modules(&self) -> Vec<u8> {
+        self.qr_code
+            .rows()
+            .flatten()
+            .map(|&dark| dark as u8)
+            .collect()
+    }
+
+    #[wasm_bindgen(js_name = toSvg)]
+    pub fn to_svg(&self, options: &Options) -> Result<String, JsError> {
+        let render_options = options.render_options().map_err(|e| JsError::new(&e))?;
+
+        Ok(self.qr_code.to_svg(&render_options))
+    }
+
+    /**
+     * The PNG file as a Uint8Array
+     */
+    #[wasm_bindgen(js_name = toPng)]
+    pub fn to_png(&self, options: &Options) -> Result<Vec<u8>, JsError> {
+        let render_options = options.render_options().map_err(|e| JsError::new(&e))?;
+
+        Ok(self.qr_code.to_png(&render_options))
+    }
+}
+
+/**
+ * Creates a code for the text, throwing an Error with the same message as the Rust API when it
+ * cannot be created
+ */
+#[wasm_bindgen]
+pub fn generate(data: &str, options: &Options) -> Result<WasmQrCode, JsError> {
+    create(data, options).map_err(|e| JsError::new(&e))
+}
+
+/**
+ * Creates a code for a Uint8Array of UTF-8 text, such as the output of a TextEncoder. Throws when
+ * the bytes are not UTF-8, binary data cannot be encoded.
+ */
+#[wasm_bindgen(js_name = generateUtf8)]
+pub fn generate_utf8(data: &[u8], options: &Options) -> Result<WasmQrCode, JsError> {
+    let data =
+        std::str::from_utf8(data).map_err(|_| JsError::new("The data is not valid UTF-8"))?;
+
+    generate(data, options)
+}
+
+fn create(data: &str, options: &Options) -> Result<WasmQrCode, String> {
+    let qr_code = QrCode::create_with_options(
+        data.to_string(),
+        options.encoding_mode(data)?,
+        options.error_correction_level()?,
+        &options.qr_code_options(),
+    )?;
+
+    Ok(WasmQrCode { qr_code })
+}
+
+#[test]
+fn test_render_options_limits() {
+    let mut options = Options::new();
+    options.quiet_zone = MAX_QUIET_ZONE;
+    options.module_size = MAX_MODULE_SIZE;
+    assert!(options.render_options().is_ok());
+
+    options.quiet_zone = usize::MAX;
+    assert!(options.render_options().is_err());
+
+    options.quiet_zone = 4;
+    options.module_size = 0;
+    assert!(options.render_options().is_err());
+
+    options.module_size = MAX_MODULE_SIZE + 1;
+    assert!(options.render_options().is_err());
+}
@@ -1,3 +1,6 @@
+// the assertions are kept as they were written
+#![allow(clippy::nonminimal_bool)]
+
 use qr_code::qr_code::encoding::encode;
 use qr_code::qr_code::EncodingMode;
 
@@ -13,7 +16,7 @@ fn test_alphanumeric_proper() {
         encoded_data.unwrap()
     );
 
-    let encoded_data = encoded_data.unwrap();
+    let encoded_data = encoded_data.unwrap().to_string();
 
     assert_eq!(
         encoded_data, "0110000101101111000110100010111001011011100010011010100001101",
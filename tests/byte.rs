@@ -13,7 +13,7 @@ fn test_byte_encoding_proper() {
         encoded_data.unwrap()
     );
 
-    let encoded_data = encoded_data.unwrap();
+    let encoded_data = encoded_data.unwrap().to_string();
 
     assert_eq!(
         encoded_data, "01001000011001010110110001101100011011110010110000100000010101110110111101110010011011000110010000100001",
@@ -0,0 +1,134 @@
+use std::process::Command;
+
+fn qr_creator(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_qr-creator"))
+        .args(args)
+        .output()
+        .expect("Failed to run qr-creator")
+}
+
+#[test]
+fn test_cli_svg_output() {
+    let output = qr_creator(&["HELLO WORLD", "--format", "svg", "--quiet-zone", "2"]);
+
+    assert!(output.status.success(), "qr-creator exited with an error");
+
+    let svg = String::from_utf8(output.stdout).unwrap();
+
+    assert!(svg.starts_with("<?xml"), "Output was not an SVG document");
+    assert!(
+        svg.contains("viewBox=\"0 0 25 25\""),
+        "Expected a 21 module symbol with a 2 module quiet zone"
+    );
+}
+
+#[test]
+fn test_cli_help_fits_100_columns() {
+    for args in [&["--help"][..], &["batch", "--help"]] {
+        let output = qr_creator(args);
+        assert!(output.status.success());
+
+        for line in String::from_utf8(output.stdout).unwrap().lines() {
+            assert!(line.len() <= 100, "Help line is too wide: {}", line);
+        }
+    }
+}
+
+#[test]
+fn test_cli_exit_codes() {
+    assert_eq!(qr_creator(&["--unknown"]).status.code(), Some(1));
+    assert_eq!(qr_creator(&["--mask", "8", "DATA"]).status.code(), Some(1));
+    assert_eq!(
+        qr_creator(&["--mode", "numeric", "12AB"]).status.code(),
+        Some(2),
+        "Characters outside the mode should be invalid input"
+    );
+    assert_eq!(
+        qr_creator(&["--max-version", "1", &"9".repeat(100)])
+            .status
+            .code(),
+        Some(3),
+        "Data too large for the version range should be a capacity error"
+    );
+    assert_eq!(
+        qr_creator(&["--format", "png", "--size", "5mm", "--dpi", "300", "DATA"])
+            .status
+            .code(),
+        Some(1),
+        "A size too small for the smallest printable module should be a usage error"
+    );
+
+    for args in [["foo", "-"], ["-", "foo"]] {
+        let output = qr_creator(&args);
+        assert_eq!(
+            output.status.code(),
+            Some(1),
+            "DATA given twice with {:?}",
+            args
+        );
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("Only one DATA argument"));
+    }
+}
+
+#[test]
+fn test_cli_batch_reports_failed_rows() {
+    let directory = std::env::temp_dir().join(format!("qr-creator-batch-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let input = directory.join("tags.csv");
+    std::fs::write(
+        &input,
+        format!(
+            "data,ec,filename\nHELLO,high,first\n{},,\nWORLD,,{{row}}-second\n",
+            "9".repeat(8000)
+        ),
+    )
+    .unwrap();
+
+    let output_directory = directory.join("out");
+    let output = qr_creator(&[
+        "batch",
+        input.to_str().unwrap(),
+        "--output-dir",
+        output_directory.to_str().unwrap(),
+    ]);
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(5), "Expected a partial failure");
+    assert!(
+        stderr.contains("Row 3:"),
+        "Failed row was not reported: {}",
+        stderr
+    );
+    assert!(output_directory.join("first.png").exists());
+    assert!(output_directory.join("4-second.png").exists());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_cli_contrast_check() {
+    let low = qr_creator(&["--foreground", "#EEEEEE", "--format", "svg", "DATA"]);
+    assert_eq!(
+        low.status.code(),
+        Some(2),
+        "Unreadable colors should be invalid input"
+    );
+
+    let inverted = qr_creator(&[
+        "--foreground",
+        "#FFFFFF",
+        "--background",
+        "#000000",
+        "--format",
+        "svg",
+        "DATA",
+    ]);
+    assert!(inverted.status.success());
+    assert!(String::from_utf8(inverted.stderr)
+        .unwrap()
+        .contains("inverted"));
+}
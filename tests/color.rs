@@ -0,0 +1,82 @@
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::{Color, Colors, Fill, RenderOptions};
+
+fn options(colors: Colors) -> RenderOptions {
+    RenderOptions {
+        colors,
+        ..RenderOptions::default()
+    }
+}
+
+#[test]
+fn test_gradient_svg() {
+    let svg = hello_world().to_svg(&options(Colors {
+        foreground: Fill::LinearGradient {
+            start: Color::BLACK,
+            end: Color::rgb(0x33, 0x66, 0x99),
+            angle: 0,
+        },
+        finder: Some(Fill::Solid(Color::rgb(0x99, 0, 0))),
+        background: Color::rgb(0xFF, 0xFF, 0xEE),
+        background_image: None,
+    }));
+
+    // the gradient runs across the symbol, inside the 4 module quiet zone
+    assert!(svg.contains("<linearGradient id=\"foreground\" gradientUnits=\"userSpaceOnUse\" x1=\"4.000\" y1=\"14.500\" x2=\"25.000\" y2=\"14.500\">"));
+    assert!(svg.contains("fill=\"url(#foreground)\""));
+    assert!(svg.contains("fill=\"#990000\""));
+    assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#FFFFEE\"/>"));
+}
+
+#[test]
+fn test_colored_png_is_truecolor() {
+    let qr_code = hello_world();
+    let black_and_white = qr_code.to_png(&RenderOptions::default());
+    let colored = qr_code.to_png(&options(Colors {
+        foreground: Fill::Solid(Color::rgb(0x33, 0x66, 0x99)),
+        ..Colors::default()
+    }));
+
+    // bit depth and color type in the IHDR chunk
+    assert_eq!(black_and_white[24..26], [1, 0]);
+    assert_eq!(colored[24..26], [8, 2]);
+}
+
+#[test]
+fn test_contrast_check() {
+    let black_on_white = Colors::default().check_contrast();
+    assert_eq!(black_on_white.grade(), 'A');
+    assert!(!black_on_white.inverted);
+    assert!(black_on_white.warnings().is_empty());
+
+    // the lightest point of the gradient decides the grade
+    let gradient = Colors {
+        foreground: Fill::RadialGradient {
+            center: Color::BLACK,
+            edge: Color::rgb(0xBB, 0xBB, 0xBB),
+        },
+        ..Colors::default()
+    }
+    .check_contrast();
+    assert_eq!(gradient.grade(), 'C');
+
+    let inverted = Colors {
+        foreground: Fill::Solid(Color::WHITE),
+        background: Color::BLACK,
+        ..Colors::default()
+    }
+    .check_contrast();
+    assert!(inverted.inverted);
+    assert!(inverted.validate().is_ok());
+    assert_eq!(inverted.warnings().len(), 1);
+
+    let unreadable = Colors {
+        foreground: Fill::Solid(Color::rgb(0xEE, 0xEE, 0xEE)),
+        ..Colors::default()
+    }
+    .check_contrast();
+    assert_eq!(unreadable.grade(), 'F');
+    assert!(unreadable.validate().is_err());
+}
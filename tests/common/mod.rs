@@ -0,0 +1,13 @@
+use qr_code::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode};
+
+/**
+ * The "HELLO WORLD" code at error correction level Q that the rendering tests share
+ */
+pub fn hello_world() -> QrCode {
+    QrCode::create(
+        "HELLO WORLD".to_string(),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Quartile,
+    )
+    .unwrap()
+}
@@ -0,0 +1,542 @@
+//! Checks the capacity and block tables against ISO/IEC 18004:2015, for every version and error
+//! correction level.
+//!
+//! The reference values are derived rather than copied from the crate's own tables: the number of
+//! code words comes from the size of the function patterns (section 7.1), the block structure from
+//! the error correction code words per block and number of blocks of table 9, and the character
+//! capacities of table 7 from the data code words that are left.
+
+use qr_code::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode, QrCodeOptions, Version};
+
+const LEVELS: [ErrorCorrectionLevel; 4] = [
+    ErrorCorrectionLevel::Low,
+    ErrorCorrectionLevel::Medium,
+    ErrorCorrectionLevel::Quartile,
+    ErrorCorrectionLevel::High,
+];
+
+/// error correction code words per block, by level and version, from table 9
+const ERROR_CORRECTION_CODE_WORDS: [[usize; 40]; 4] = [
+    [
+        7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30,
+        30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28,
+        28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+    ],
+    [
+        13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30,
+        30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+];
+
+/// error correction blocks, by level and version, from table 9
+const BLOCKS: [[usize; 40]; 4] = [
+    [
+        1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13,
+        14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+    ],
+    [
+        1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23,
+        25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+    ],
+    [
+        1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29,
+        34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+    ],
+    [
+        1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35,
+        37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+    ],
+];
+
+/**
+ * Modules left for the data and error correction code words once the finder, separator, timing and
+ * alignment patterns, the format and version information and the dark module are placed
+ */
+fn data_modules(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+
+    if version >= 2 {
+        let alignment_patterns = version / 7 + 2;
+        modules -= (25 * alignment_patterns - 10) * alignment_patterns - 55;
+    }
+    if version >= 7 {
+        modules -= 36;
+    }
+
+    modules
+}
+
+fn data_code_words(version: usize, level: usize) -> usize {
+    data_modules(version) / 8
+        - ERROR_CORRECTION_CODE_WORDS[level][version - 1] * BLOCKS[level][version - 1]
+}
+
+fn character_count_bits(version: usize, mode: EncodingMode) -> usize {
+    let class = match version {
+        1..=9 => 0,
+        10..=26 => 1,
+        _ => 2,
+    };
+
+    match mode {
+        EncodingMode::Numeric => [10, 12, 14][class],
+        EncodingMode::Alphanumeric => [9, 11, 13][class],
+        EncodingMode::Byte => [8, 16, 16][class],
+    }
+}
+
+/**
+ * The most characters of the mode that fit in the data code words, as in table 7
+ */
+fn capacity(version: usize, level: usize, mode: EncodingMode) -> usize {
+    let bits = data_code_words(version, level) * 8 - 4 - character_count_bits(version, mode);
+
+    match mode {
+        EncodingMode::Numeric => bits / 10 * 3 + [0, 0, 0, 0, 1, 1, 1, 2, 2, 2][bits % 10],
+        EncodingMode::Alphanumeric => bits / 11 * 2 + usize::from(bits % 11 >= 6),
+        EncodingMode::Byte => bits / 8,
+    }
+}
+
+fn payload(mode: EncodingMode, length: usize) -> String {
+    let characters = match mode {
+        EncodingMode::Numeric => "0123456789",
+        EncodingMode::Alphanumeric => "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:",
+        EncodingMode::Byte => "Hello, World!",
+    };
+
+    characters.chars().cycle().take(length).collect()
+}
+
+fn only_version(version: usize) -> QrCodeOptions {
+    QrCodeOptions {
+        min_version: version as i16,
+        max_version: version as i16,
+        // any fixed mask, choosing one does not change the capacity
+        mask: Some(0),
+        ..QrCodeOptions::default()
+    }
+}
+
+#[test]
+fn test_reference_tables() {
+    // spot checks of the derived values against table 1 and table 7
+    assert_eq!(data_modules(1) / 8, 26);
+    assert_eq!(data_modules(40) / 8, 3706);
+    assert_eq!(data_modules(40) % 8, 0);
+    assert_eq!(data_modules(21) % 8, 4);
+    assert_eq!(capacity(1, 0, EncodingMode::Numeric), 41);
+    assert_eq!(capacity(40, 0, EncodingMode::Numeric), 7089);
+    assert_eq!(capacity(40, 0, EncodingMode::Alphanumeric), 4296);
+    assert_eq!(capacity(40, 3, EncodingMode::Byte), 1273);
+}
+
+#[test]
+fn test_block_structure() {
+    for version in 1..=40 {
+        for (level, error_correction_level) in LEVELS.into_iter().enumerate() {
+            let qr_code = QrCode::create_with_options(
+                String::new(),
+                EncodingMode::Byte,
+                error_correction_level,
+                &only_version(version),
+            )
+            .unwrap();
+            let report = qr_code.report().unwrap();
+            let name = format!("{}-{:?}", version, error_correction_level);
+
+            let blocks = BLOCKS[level][version - 1];
+            let data = data_code_words(version, level);
+            let layout = &report.group_layout;
+
+            assert_eq!(
+                report.data_code_words.len(),
+                data,
+                "data code words of {}",
+                name
+            );
+            assert_eq!(
+                (layout.blocks_in_group_one + layout.blocks_in_group_two) as usize,
+                blocks,
+                "blocks of {}",
+                name
+            );
+            // the blocks of group two hold one more code word than those of group one
+            assert_eq!(
+                layout.code_words_in_group_one_blocks as usize,
+                data / blocks,
+                "group one of {}",
+                name
+            );
+            assert_eq!(
+                layout.blocks_in_group_two as usize,
+                data % blocks,
+                "group two of {}",
+                name
+            );
+            if layout.blocks_in_group_two > 0 {
+                assert_eq!(
+                    layout.code_words_in_group_two_blocks,
+                    layout.code_words_in_group_one_blocks + 1,
+                    "group two of {}",
+                    name
+                );
+            }
+
+            assert!(report
+                .blocks
+                .iter()
+                .all(|block| block.error_correction.len()
+                    == ERROR_CORRECTION_CODE_WORDS[level][version - 1]));
+            assert_eq!(report.final_message.len(), data_modules(version) / 8);
+            assert_eq!(report.remainder_bits, data_modules(version) % 8);
+        }
+    }
+}
+
+#[test]
+fn test_capacities() {
+    for version in 1..=40 {
+        for (level, error_correction_level) in LEVELS.into_iter().enumerate() {
+            for mode in [
+                EncodingMode::Numeric,
+                EncodingMode::Alphanumeric,
+                EncodingMode::Byte,
+            ] {
+                let capacity = capacity(version, level, mode);
+                let name = format!("{}-{:?} in {} mode", version, error_correction_level, mode);
+
+                let full = QrCode::create_with_options(
+                    payload(mode, capacity),
+                    mode,
+                    error_correction_level,
+                    &only_version(version),
+                );
+                assert!(full.is_ok(), "{} characters should fit {}", capacity, name);
+
+                let overflow = QrCode::create_with_options(
+                    payload(mode, capacity + 1),
+                    mode,
+                    error_correction_level,
+                    &only_version(version),
+                );
+                assert!(
+                    overflow.is_err(),
+                    "{} characters should not fit {}",
+                    capacity + 1,
+                    name
+                );
+
+                // the smallest version that fits is picked
+                if version > 1 {
+                    let smallest = QrCode::create_with_options(
+                        payload(mode, self::capacity(version - 1, level, mode) + 1),
+                        mode,
+                        error_correction_level,
+                        &QrCodeOptions {
+                            mask: Some(0),
+                            ..QrCodeOptions::default()
+                        },
+                    )
+                    .unwrap();
+                    assert_eq!(
+                        smallest.version,
+                        Version::Normal(version as i16),
+                        "{}",
+                        name
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// format information after masking, by level and mask pattern, from table C.1
+const FORMAT_INFORMATION: [[&str; 8]; 4] = [
+    [
+        "111011111000100",
+        "111001011110011",
+        "111110110101010",
+        "111100010011101",
+        "110011000101111",
+        "110001100011000",
+        "110110001000001",
+        "110100101110110",
+    ],
+    [
+        "101010000010010",
+        "101000100100101",
+        "101111001111100",
+        "101101101001011",
+        "100010111111001",
+        "100000011001110",
+        "100111110010111",
+        "100101010100000",
+    ],
+    [
+        "011010101011111",
+        "011000001101000",
+        "011111100110001",
+        "011101000000110",
+        "010010010110100",
+        "010000110000011",
+        "010111011011010",
+        "010101111101101",
+    ],
+    [
+        "001011010001001",
+        "001001110111110",
+        "001110011100111",
+        "001100111010000",
+        "000011101100010",
+        "000001001010101",
+        "000110100001100",
+        "000100000111011",
+    ],
+];
+
+/// version information of versions 7 to 40, from table D.1
+const VERSION_INFORMATION: [&str; 34] = [
+    "000111110010010100",
+    "001000010110111100",
+    "001001101010011001",
+    "001010010011010011",
+    "001011101111110110",
+    "001100011101100010",
+    "001101100001000111",
+    "001110011000001101",
+    "001111100100101000",
+    "010000101101111000",
+    "010001010001011101",
+    "010010101000010111",
+    "010011010100110010",
+    "010100100110100110",
+    "010101011010000011",
+    "010110100011001001",
+    "010111011111101100",
+    "011000111011000100",
+    "011001000111100001",
+    "011010111110101011",
+    "011011000010001110",
+    "011100110000011010",
+    "011101001100111111",
+    "011110110101110101",
+    "011111001001010000",
+    "100000100111010101",
+    "100001011011110000",
+    "100010100010111010",
+    "100011011110011111",
+    "100100101100001011",
+    "100101010000101110",
+    "100110101001100100",
+    "100111010101000001",
+    "101000110001101001",
+];
+
+/**
+ * Reads bits into a string with the most significant bit first, `bit(i)` is bit i from the least
+ * significant
+ */
+fn read_bits(count: usize, bit: impl Fn(usize) -> bool) -> String {
+    (0..count)
+        .rev()
+        .map(|i| if bit(i) { '1' } else { '0' })
+        .collect()
+}
+
+#[test]
+fn test_format_information() {
+    for (level, error_correction_level) in LEVELS.into_iter().enumerate() {
+        for mask in 0..8u8 {
+            let qr_code = QrCode::create_with_options(
+                "01234567".to_string(),
+                EncodingMode::Numeric,
+                error_correction_level,
+                &QrCodeOptions {
+                    mask: Some(mask),
+                    ..QrCodeOptions::default()
+                },
+            )
+            .unwrap();
+            let size = qr_code.width();
+            let module = |x: usize, y: usize| qr_code.get_module(x, y);
+
+            // around the top left finder pattern, skipping the timing patterns (figure 25)
+            let first = read_bits(15, |i| match i {
+                0..=5 => module(8, i),
+                6 => module(8, 7),
+                7 => module(8, 8),
+                8 => module(7, 8),
+                _ => module(14 - i, 8),
+            });
+            // split between the other two finder patterns
+            let second = read_bits(15, |i| match i {
+                0..=7 => module(size - 1 - i, 8),
+                _ => module(8, size - 15 + i),
+            });
+
+            let expected = FORMAT_INFORMATION[level][mask as usize];
+            assert_eq!(
+                first, expected,
+                "{:?} mask {}",
+                error_correction_level, mask
+            );
+            assert_eq!(
+                second, expected,
+                "{:?} mask {}",
+                error_correction_level, mask
+            );
+            // the dark module above the second copy
+            assert!(module(8, size - 8));
+        }
+    }
+}
+
+#[test]
+fn test_version_information() {
+    for version in 7..=40 {
+        let qr_code = QrCode::create_with_options(
+            String::new(),
+            EncodingMode::Byte,
+            ErrorCorrectionLevel::Low,
+            &only_version(version),
+        )
+        .unwrap();
+        let size = qr_code.width();
+
+        // a 6x3 block above the bottom left finder pattern and its transpose left of the top right
+        let bottom_left = read_bits(18, |i| qr_code.get_module(i / 3, size - 11 + i % 3));
+        let top_right = read_bits(18, |i| qr_code.get_module(size - 11 + i % 3, i / 3));
+
+        let expected = VERSION_INFORMATION[version - 7];
+        assert_eq!(bottom_left, expected, "version {}", version);
+        assert_eq!(top_right, expected, "version {}", version);
+    }
+}
+
+#[test]
+fn test_annex_example() {
+    // the worked example of annex I: 01234567 as a 1-M symbol with mask pattern 010
+    let qr_code = QrCode::create_with_options(
+        "01234567".to_string(),
+        EncodingMode::Numeric,
+        ErrorCorrectionLevel::Medium,
+        &QrCodeOptions {
+            mask: Some(2),
+            ..QrCodeOptions::default()
+        },
+    )
+    .unwrap();
+    let report = qr_code.report().unwrap();
+
+    assert_eq!(qr_code.version, Version::Normal(1));
+    assert_eq!(
+        report.data_code_words,
+        vec![16, 32, 12, 86, 97, 128, 236, 17, 236, 17, 236, 17, 236, 17, 236, 17]
+    );
+    assert_eq!(
+        report.blocks[0].error_correction,
+        vec![165, 36, 212, 193, 237, 54, 199, 135, 44, 85]
+    );
+}
+
+/// the annex I symbol, 01234567 as 1-M with mask pattern 010
+const ANNEX_SYMBOL: &str = "\
+111111100101101111111
+100000100111101000001
+101110101000001011101
+101110101100001011101
+101110101011101011101
+100000101000101000001
+111111101010101111111
+000000001001100000000
+101111100100101111100
+000101011010100101100
+001000110101010011111
+000010000100000111100
+000111111001010010000
+000000001011111001100
+111111100110101100000
+100000101011111000101
+101110101000100101100
+101110101100100100000
+101110101011010010100
+100000100000000110110
+111111101111010010100
+";
+
+/// HELLO WORLD as 1-Q with mask pattern 110, whose code words match the tutorial the encoder follows
+const HELLO_WORLD_SYMBOL: &str = "\
+111111100001001111111
+100000101100101000001
+101110100101101011101
+101110101111101011101
+101110101101001011101
+100000100100101000001
+111111101010101111111
+000000001101100000000
+010111101100111011010
+101111010000111101110
+001010110001001100000
+101101000101100011000
+110111111110111011111
+000000001000100101000
+111111100110011001111
+100000101010010010111
+101110101101001000111
+101110101011100010100
+101110100100001000011
+100000101110011100110
+111111100101000000010
+";
+
+#[test]
+fn test_golden_symbols() {
+    for (data, mode, level, mask, expected) in [
+        (
+            "01234567",
+            EncodingMode::Numeric,
+            ErrorCorrectionLevel::Medium,
+            2,
+            ANNEX_SYMBOL,
+        ),
+        (
+            "HELLO WORLD",
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Quartile,
+            6,
+            HELLO_WORLD_SYMBOL,
+        ),
+    ] {
+        let qr_code = QrCode::create_with_options(
+            data.to_string(),
+            mode,
+            level,
+            &QrCodeOptions {
+                mask: Some(mask),
+                ..QrCodeOptions::default()
+            },
+        )
+        .unwrap();
+
+        let rows: String = qr_code
+            .rows()
+            .map(|row| {
+                let line: String = row
+                    .iter()
+                    .map(|&dark| if dark { '1' } else { '0' })
+                    .collect();
+                line + "\n"
+            })
+            .collect();
+
+        assert_eq!(rows, expected, "{}", data);
+    }
+}
@@ -0,0 +1,152 @@
+use qr_code::qr_code::{
+    Encoder, EncodingMode, ErrorCorrectionLevel, Knockout, QrCode, QrCodeOptions,
+};
+
+fn assert_same_code(reused: &QrCode, created: &QrCode) {
+    assert_eq!(reused.data, created.data);
+    assert_eq!(reused.encoding_mode, created.encoding_mode);
+    assert_eq!(
+        reused.error_correction_level,
+        created.error_correction_level
+    );
+    assert_eq!(reused.version, created.version);
+    assert_eq!(reused.mask, created.mask);
+    assert_eq!(reused.knockout, created.knockout);
+    assert_eq!(reused.encoded_data, created.encoded_data);
+    assert_eq!(reused.code_words, created.code_words);
+    assert!(reused.rows().eq(created.rows()));
+    assert!((0..created.width()).all(|y| (0..created.width())
+        .all(|x| reused.is_function_module(x, y) == created.is_function_module(x, y))));
+}
+
+#[test]
+fn test_encode_into_matches_create() {
+    let long_url = format!("https://example.com/{}", "a".repeat(400));
+    let codes = [
+        (
+            "HELLO WORLD",
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Quartile,
+            QrCodeOptions::default(),
+        ),
+        (
+            long_url.as_str(),
+            EncodingMode::Byte,
+            ErrorCorrectionLevel::Low,
+            QrCodeOptions::default(),
+        ),
+        (
+            "8675309",
+            EncodingMode::Numeric,
+            ErrorCorrectionLevel::High,
+            QrCodeOptions {
+                mask: Some(5),
+                ..QrCodeOptions::default()
+            },
+        ),
+        (
+            "https://example.com/a/longer/path",
+            EncodingMode::Byte,
+            ErrorCorrectionLevel::Low,
+            QrCodeOptions {
+                knockout: Some(Knockout::new(5, 5)),
+                raise_error_correction: true,
+                ..QrCodeOptions::default()
+            },
+        ),
+        (
+            "HELLO WORLD",
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Quartile,
+            QrCodeOptions {
+                min_version: 7,
+                ..QrCodeOptions::default()
+            },
+        ),
+    ];
+
+    let mut encoder = Encoder::new();
+    let mut reused = encoder
+        .encode(
+            "0",
+            EncodingMode::Numeric,
+            ErrorCorrectionLevel::Low,
+            &QrCodeOptions::default(),
+        )
+        .unwrap();
+
+    // twice over, so every code is encoded into one of a different version
+    for (data, encoding_mode, error_correction_level, options) in codes.iter().chain(&codes) {
+        encoder
+            .encode_into(
+                &mut reused,
+                data,
+                *encoding_mode,
+                *error_correction_level,
+                options,
+            )
+            .unwrap();
+        let created = QrCode::create_with_options(
+            data.to_string(),
+            *encoding_mode,
+            *error_correction_level,
+            options,
+        )
+        .unwrap();
+
+        assert_same_code(&reused, &created);
+    }
+}
+
+#[test]
+fn test_encode_into_failure_keeps_code() {
+    let mut encoder = Encoder::new();
+    let options = QrCodeOptions::default();
+    let mut qr_code = encoder
+        .encode(
+            "HELLO WORLD",
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Medium,
+            &options,
+        )
+        .unwrap();
+    let before = QrCode::create_with_options(
+        "HELLO WORLD".to_string(),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Medium,
+        &options,
+    )
+    .unwrap();
+
+    assert!(encoder
+        .encode_into(
+            &mut qr_code,
+            "hello world",
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Medium,
+            &options
+        )
+        .is_err());
+    assert!(encoder
+        .encode_into(
+            &mut qr_code,
+            &"1".repeat(8000),
+            EncodingMode::Numeric,
+            ErrorCorrectionLevel::Low,
+            &options
+        )
+        .is_err());
+    assert_same_code(&qr_code, &before);
+
+    // the encoder is still usable after a failure
+    encoder
+        .encode_into(
+            &mut qr_code,
+            "12345",
+            EncodingMode::Numeric,
+            ErrorCorrectionLevel::Low,
+            &options,
+        )
+        .unwrap();
+    assert_eq!(qr_code.data, "12345");
+}
@@ -0,0 +1,173 @@
+use qr_code::qr_code::{
+    fixed_buffer_len, EncodingMode, ErrorCorrectionLevel, FixedError, FixedQrCode, Knockout,
+    QrCode, QrCodeOptions, Version,
+};
+
+const VERSION_10: usize = fixed_buffer_len(10);
+const VERSION_40: usize = fixed_buffer_len(40);
+
+fn assert_same_code<const N: usize>(fixed: &FixedQrCode<N>, qr_code: &QrCode) {
+    assert_eq!(fixed.encoding_mode, qr_code.encoding_mode);
+    assert_eq!(fixed.error_correction_level, qr_code.error_correction_level);
+    assert_eq!(fixed.version, qr_code.version);
+    assert_eq!(fixed.mask, qr_code.mask);
+    assert_eq!(fixed.width(), qr_code.width());
+
+    for y in 0..qr_code.width() {
+        for x in 0..qr_code.width() {
+            assert_eq!(
+                fixed.get_module(x, y),
+                qr_code.get_module(x, y),
+                "({}, {})",
+                x,
+                y
+            );
+            assert_eq!(
+                fixed.is_function_module(x, y),
+                qr_code.is_function_module(x, y)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_fixed_buffer_len() {
+    assert_eq!(fixed_buffer_len(1), 56);
+    assert_eq!(fixed_buffer_len(40), 3917);
+}
+
+#[test]
+fn test_fixed_matches_qr_code() {
+    let long_url = format!("https://example.com/{}", "a".repeat(1500));
+    let codes = [
+        (
+            "HELLO WORLD",
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Quartile,
+        ),
+        ("8675309", EncodingMode::Numeric, ErrorCorrectionLevel::High),
+        (
+            "https://example.com",
+            EncodingMode::Byte,
+            ErrorCorrectionLevel::Medium,
+        ),
+        (
+            long_url.as_str(),
+            EncodingMode::Byte,
+            ErrorCorrectionLevel::Low,
+        ),
+    ];
+
+    for (data, encoding_mode, error_correction_level) in codes {
+        let fixed =
+            FixedQrCode::<VERSION_40>::create(data, encoding_mode, error_correction_level).unwrap();
+        let qr_code =
+            QrCode::create(data.to_string(), encoding_mode, error_correction_level).unwrap();
+
+        assert_same_code(&fixed, &qr_code);
+    }
+}
+
+#[test]
+fn test_fixed_matches_qr_code_with_options() {
+    for mask in 0..8 {
+        let options = QrCodeOptions {
+            mask: Some(mask),
+            min_version: 7,
+            ..QrCodeOptions::default()
+        };
+        let fixed = FixedQrCode::<VERSION_10>::create_with_options(
+            "HELLO WORLD",
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Low,
+            &options,
+        )
+        .unwrap();
+        let qr_code = QrCode::create_with_options(
+            "HELLO WORLD".to_string(),
+            EncodingMode::Alphanumeric,
+            ErrorCorrectionLevel::Low,
+            &options,
+        )
+        .unwrap();
+
+        assert_same_code(&fixed, &qr_code);
+    }
+}
+
+#[test]
+fn test_fixed_errors() {
+    let create = |data: &str, encoding_mode, options| {
+        FixedQrCode::<VERSION_10>::create_with_options(
+            data,
+            encoding_mode,
+            ErrorCorrectionLevel::Low,
+            &options,
+        )
+        .map(|qr_code| qr_code.version)
+    };
+    let options = QrCodeOptions::default();
+
+    assert_eq!(
+        create("12a", EncodingMode::Numeric, options),
+        Err(FixedError::InvalidCharacter('a'))
+    );
+    assert_eq!(
+        create("hello", EncodingMode::Alphanumeric, options),
+        Err(FixedError::InvalidCharacter('h'))
+    );
+
+    // version 10 holds 271 bytes at level L, version 11 does not fit the buffers
+    assert_eq!(
+        create(&"a".repeat(271), EncodingMode::Byte, options),
+        Ok(Version::Normal(10))
+    );
+    assert_eq!(
+        create(&"a".repeat(272), EncodingMode::Byte, options),
+        Err(FixedError::DataTooLong)
+    );
+    assert_eq!(
+        create(
+            "1",
+            EncodingMode::Numeric,
+            QrCodeOptions {
+                min_version: 11,
+                ..options
+            }
+        ),
+        Err(FixedError::DataTooLong)
+    );
+    assert_eq!(
+        create(
+            "1",
+            EncodingMode::Numeric,
+            QrCodeOptions {
+                min_version: 0,
+                ..options
+            }
+        ),
+        Err(FixedError::InvalidVersionRange(0, 40))
+    );
+    assert_eq!(
+        create(
+            "1",
+            EncodingMode::Numeric,
+            QrCodeOptions {
+                mask: Some(8),
+                ..options
+            }
+        ),
+        Err(FixedError::InvalidMask(8))
+    );
+    assert_eq!(
+        create(
+            "1",
+            EncodingMode::Numeric,
+            QrCodeOptions {
+                knockout: Some(Knockout::new(3, 3)),
+                ..options
+            }
+        ),
+        Err(FixedError::KnockoutNotSupported)
+    );
+}
@@ -0,0 +1,119 @@
+use qr_code::qr_code::render::RenderOptions;
+use qr_code::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode};
+
+fn options() -> RenderOptions {
+    RenderOptions {
+        quiet_zone: 2,
+        module_size: 3,
+        ..RenderOptions::default()
+    }
+}
+
+// a version 1 symbol with a 2 module quiet zone at 3 pixels per module
+const PIXELS: usize = 25 * 3;
+
+#[test]
+fn test_zpl() {
+    let numeric = QrCode::create(
+        "0123456789".to_string(),
+        EncodingMode::Numeric,
+        ErrorCorrectionLevel::High,
+    )
+    .unwrap();
+    assert_eq!(
+        numeric.to_zpl(&options()).unwrap(),
+        "^XA\n^FO6,6\n^BQN,2,3\n^FH^FDHM,N0123456789^FS\n^XZ\n"
+    );
+
+    let byte = QrCode::create(
+        "a^b".to_string(),
+        EncodingMode::Byte,
+        ErrorCorrectionLevel::Low,
+    )
+    .unwrap();
+    assert_eq!(
+        byte.to_zpl(&options()).unwrap(),
+        "^XA\n^FO6,6\n^BQN,2,3\n^FH^FDLM,B0003a_5Eb^FS\n^XZ\n"
+    );
+
+    let too_large = RenderOptions {
+        module_size: 11,
+        ..options()
+    };
+    assert!(byte.to_zpl(&too_large).is_err());
+}
+
+#[test]
+fn test_zpl_graphic() {
+    let qr_code = QrCode::create(
+        "HELLO WORLD".to_string(),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Quartile,
+    )
+    .unwrap();
+    let zpl = qr_code.to_zpl_graphic(&options());
+
+    let row_bytes = PIXELS.div_ceil(8);
+    assert!(zpl.starts_with(&format!(
+        "^XA\n^FO0,0\n^GFA,{0},{0},{1},\n",
+        row_bytes * PIXELS,
+        row_bytes
+    )));
+    assert!(zpl.ends_with("^FS\n^XZ\n"));
+
+    let rows: Vec<&str> = zpl.lines().skip(3).take(PIXELS).collect();
+    assert!(rows.iter().all(|row| row.len() == row_bytes * 2));
+    // the quiet zone is 6 pixels, so the first finder pattern starts on the 7th row and column
+    assert_eq!(rows[0], "0".repeat(row_bytes * 2));
+    assert!(rows[6].starts_with("03FF"));
+}
+
+#[test]
+fn test_escpos() {
+    let qr_code = QrCode::create(
+        "HELLO".to_string(),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Medium,
+    )
+    .unwrap();
+    let escpos = qr_code.to_escpos(&options()).unwrap();
+
+    let expected: Vec<u8> = [
+        &[0x1B, b'@'][..],
+        &[0x1D, b'(', b'k', 4, 0, 49, 65, 50, 0],
+        &[0x1D, b'(', b'k', 3, 0, 49, 67, 3],
+        &[0x1D, b'(', b'k', 3, 0, 49, 69, 49],
+        &[0x1D, b'(', b'k', 8, 0, 49, 80, 48],
+        b"HELLO",
+        &[0x1D, b'(', b'k', 3, 0, 49, 81, 48],
+        b"\n",
+    ]
+    .concat();
+    assert_eq!(escpos, expected);
+
+    let too_large = RenderOptions {
+        module_size: 17,
+        ..options()
+    };
+    assert!(qr_code.to_escpos(&too_large).is_err());
+}
+
+#[test]
+fn test_escpos_raster() {
+    let qr_code = QrCode::create(
+        "HELLO WORLD".to_string(),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Quartile,
+    )
+    .unwrap();
+    let escpos = qr_code.to_escpos_raster(&options());
+
+    let row_bytes = PIXELS.div_ceil(8);
+    assert_eq!(&escpos[..6], &[0x1B, b'@', 0x1D, b'v', b'0', 0]);
+    assert_eq!(
+        u16::from_le_bytes([escpos[6], escpos[7]]) as usize,
+        row_bytes
+    );
+    assert_eq!(u16::from_le_bytes([escpos[8], escpos[9]]) as usize, PIXELS);
+    assert_eq!(escpos.len(), 10 + row_bytes * PIXELS + 1);
+}
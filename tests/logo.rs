@@ -0,0 +1,90 @@
+use qr_code::qr_code::render::{Logo, RenderOptions};
+use qr_code::qr_code::{
+    EncodingMode, ErrorCorrectionLevel, Knockout, KnockoutShape, QrCode, QrCodeOptions,
+};
+
+const URL: &str = "https://github.com/cqb13/qr-creator";
+
+fn create(
+    error_correction_level: ErrorCorrectionLevel,
+    knockout: Knockout,
+    raise_error_correction: bool,
+) -> Result<QrCode, String> {
+    QrCode::create_with_options(
+        URL.to_string(),
+        EncodingMode::Byte,
+        error_correction_level,
+        &QrCodeOptions {
+            knockout: Some(knockout),
+            raise_error_correction,
+            ..QrCodeOptions::default()
+        },
+    )
+}
+
+#[test]
+fn test_knockout_clears_data_modules() {
+    let knockout = Knockout::new(5, 5);
+    let qr_code = create(ErrorCorrectionLevel::High, knockout, false).unwrap();
+    let size = qr_code.width();
+
+    assert_eq!(qr_code.knockout, Some(knockout));
+
+    for y in 0..size {
+        for x in 0..size {
+            if knockout.covers(size, x, y) && !qr_code.is_function_module(x, y) {
+                assert!(!qr_code.get_module(x, y), "({}, {}) should be light", x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_knockout_too_large_for_error_correction() {
+    let Err(error) = create(ErrorCorrectionLevel::Low, Knockout::new(9, 9), false) else {
+        panic!("a 9x9 area should be too large for error correction level Low");
+    };
+
+    assert!(error.contains("can only recover"), "{}", error);
+}
+
+#[test]
+fn test_knockout_raises_error_correction() {
+    let qr_code = create(ErrorCorrectionLevel::Low, Knockout::new(9, 9), true).unwrap();
+
+    assert_ne!(qr_code.error_correction_level, ErrorCorrectionLevel::Low);
+}
+
+#[test]
+fn test_knockout_cannot_cover_finder_patterns() {
+    let Err(error) = create(ErrorCorrectionLevel::High, Knockout::new(30, 30), true) else {
+        panic!("a 30x30 area should not fit in the symbol");
+    };
+
+    assert!(error.contains("finder patterns"), "{}", error);
+}
+
+#[test]
+fn test_logo_svg() {
+    let qr_code = create(
+        ErrorCorrectionLevel::High,
+        Knockout::new(7, 7).shape(KnockoutShape::Circle),
+        false,
+    )
+    .unwrap();
+
+    let svg = qr_code.to_svg(&RenderOptions {
+        logo: Some(Logo::new("image/png", b"foo".to_vec())),
+        ..RenderOptions::default()
+    });
+
+    assert!(svg.contains("<clipPath id=\"logo-clip\">"));
+    assert!(svg.contains("href=\"data:image/png;base64,Zm9v\""));
+}
+
+#[test]
+fn test_logo_mime_type() {
+    assert_eq!(Logo::mime_type_for_path("logo.PNG"), Some("image/png"));
+    assert_eq!(Logo::mime_type_for_path("logo.svg"), Some("image/svg+xml"));
+    assert_eq!(Logo::mime_type_for_path("logo"), None);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::hello_world;
+
+#[test]
+fn test_matrix_width() {
+    let qr_code = hello_world();
+
+    assert_eq!(qr_code.width(), 21, "Version 1 should be 21 modules wide");
+    assert_eq!(qr_code.rows().len(), 21);
+    assert!(qr_code.rows().all(|row| row.len() == 21));
+}
+
+#[test]
+fn test_matrix_finder_patterns() {
+    let qr_code = hello_world();
+    let width = qr_code.width();
+
+    // top left, top right and bottom left corners of each finder pattern are dark
+    for (x, y) in [
+        (0, 0),
+        (6, 6),
+        (width - 1, 0),
+        (width - 7, 6),
+        (0, width - 1),
+    ] {
+        assert!(
+            qr_code.get_module(x, y),
+            "Expected dark module at ({}, {})",
+            x,
+            y
+        );
+        assert!(qr_code.is_function_module(x, y));
+    }
+
+    // separators are light
+    for (x, y) in [(7, 0), (0, 7), (width - 8, 0), (7, width - 1)] {
+        assert!(
+            !qr_code.get_module(x, y),
+            "Expected light module at ({}, {})",
+            x,
+            y
+        );
+        assert!(qr_code.is_function_module(x, y));
+    }
+
+    assert!(!qr_code.is_function_module(width - 1, width - 1));
+}
+
+#[test]
+fn test_matrix_quiet_zone() {
+    let qr_code = hello_world();
+
+    let matrix = qr_code.to_matrix(4);
+
+    assert_eq!(matrix.len(), 29);
+    assert!(matrix[..4].iter().flatten().all(|&dark| !dark));
+    assert!(matrix[4][4], "Symbol should start after the quiet zone");
+
+    let without_quiet_zone = qr_code.to_matrix(0);
+    let rows: Vec<Vec<bool>> = qr_code.rows().map(|row| row.to_vec()).collect();
+
+    assert_eq!(without_quiet_zone, rows);
+    assert!(
+        !qr_code.get_module(21, 0),
+        "Modules outside the symbol are light"
+    );
+}
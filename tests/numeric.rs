@@ -1,3 +1,6 @@
+// the assertions are kept as they were written
+#![allow(clippy::nonminimal_bool)]
+
 use qr_code::qr_code::encoding::encode;
 use qr_code::qr_code::EncodingMode;
 
@@ -13,7 +16,7 @@ fn test_numeric_encoding_proper() {
         encoded_data.unwrap()
     );
 
-    let encoded_data = encoded_data.unwrap();
+    let encoded_data = encoded_data.unwrap().to_string();
 
     assert_eq!(
         encoded_data, "110110001110000100101001",
@@ -33,3 +36,18 @@ fn test_numeric_encoding_fail() {
         "Failed to detect invalid characters in numeric encoding data"
     )
 }
+
+#[test]
+fn test_numeric_encoding_leading_zeros() {
+    // each group is as wide as its number of digits says, whatever its value
+    let encoded_data = encode("007050", &EncodingMode::Numeric)
+        .unwrap()
+        .to_string();
+    assert_eq!(encoded_data, "00000001110000110010");
+
+    let encoded_data = encode("0050", &EncodingMode::Numeric).unwrap().to_string();
+    assert_eq!(encoded_data, "00000001010000");
+
+    let encoded_data = encode("12301", &EncodingMode::Numeric).unwrap().to_string();
+    assert_eq!(encoded_data, "00011110110000001");
+}
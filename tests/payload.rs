@@ -0,0 +1,453 @@
+use qr_code::qr_code::payload::{
+    parse_payload, Address, CalendarEvent, Contact, Email, EventTime, GeoLocation, MeCard,
+    MerchantAccount, MerchantPayment, ParsedPayload, Payload, PhoneType, SepaCreditTransfer, Sms,
+    SwissAddress, SwissCurrency, SwissQrBill, SwissReference, Telephone, VCard, VCardVersion,
+    WifiNetwork, WifiSecurity,
+};
+use qr_code::qr_code::render::RenderOptions;
+use qr_code::qr_code::{EncodingMode, ErrorCorrectionLevel};
+
+#[test]
+fn test_wifi_payload_escaping() {
+    let wifi = WifiNetwork::new("Cafe; \"Guest\"")
+        .password(WifiSecurity::Wpa, "pa:ss\\word,1")
+        .hidden(true);
+
+    assert_eq!(
+        wifi.to_payload_string().unwrap(),
+        "WIFI:T:WPA;S:Cafe\\; \\\"Guest\\\";P:pa\\:ss\\\\word\\,1;H:true;;"
+    );
+
+    assert_eq!(
+        WifiNetwork::new("Open").to_payload_string().unwrap(),
+        "WIFI:T:nopass;S:Open;;"
+    );
+
+    assert!(WifiNetwork::new("").to_payload_string().is_err());
+}
+
+#[test]
+fn test_vcard_payload() {
+    let contact = Contact::new("Jane", "Doe")
+        .organization("Acme, Inc.")
+        .phone(PhoneType::Cell, "+1 555 0100")
+        .email("jane@example.com")
+        .address(Address {
+            street: "1 Main St".to_string(),
+            city: "Springfield".to_string(),
+            ..Address::default()
+        })
+        .note("Line one\nLine two; done");
+
+    let v3 = VCard::new(contact.clone(), VCardVersion::V3)
+        .to_payload_string()
+        .unwrap();
+
+    assert!(v3.starts_with("BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;Jane;;;\r\nFN:Jane Doe\r\n"));
+    assert!(v3.contains("\r\nORG:Acme\\, Inc.\r\n"));
+    assert!(v3.contains("\r\nTEL;TYPE=CELL:+1 555 0100\r\n"));
+    assert!(v3.contains("\r\nADR:;;1 Main St;Springfield;;;\r\n"));
+    assert!(v3.contains("\r\nNOTE:Line one\\nLine two\\; done\r\n"));
+    assert!(v3.ends_with("\r\nEND:VCARD"));
+
+    let v4 = VCard::new(contact, VCardVersion::V4)
+        .to_payload_string()
+        .unwrap();
+
+    assert!(v4.contains("\r\nVERSION:4.0\r\n"));
+    assert!(v4.contains("\r\nTEL;VALUE=uri;TYPE=cell:tel:+1 555 0100\r\n"));
+}
+
+#[test]
+fn test_mecard_payload() {
+    let contact = Contact::new("Jane", "Doe")
+        .phone(PhoneType::Work, "+15550100")
+        .url("https://example.com")
+        .birthday(1990, 2, 28);
+
+    assert_eq!(
+        MeCard::new(contact).to_payload_string().unwrap(),
+        "MECARD:N:Doe,Jane;TEL:+15550100;URL:https\\://example.com;BDAY:19900228;;"
+    );
+
+    let invalid_birthday = Contact::new("Jane", "Doe").birthday(1990, 2, 30);
+    assert!(MeCard::new(invalid_birthday).to_payload_string().is_err());
+}
+
+#[test]
+fn test_uri_payloads() {
+    assert_eq!(
+        GeoLocation::new(40.7128, -74.006)
+            .to_payload_string()
+            .unwrap(),
+        "geo:40.7128,-74.006"
+    );
+    assert!(GeoLocation::new(91.0, 0.0).to_payload_string().is_err());
+
+    assert_eq!(
+        Telephone::new("+1 (555) 010-0")
+            .to_payload_string()
+            .unwrap(),
+        "tel:+1(555)010-0"
+    );
+    assert!(Telephone::new("call me").to_payload_string().is_err());
+
+    assert_eq!(
+        Sms::new("+15550100")
+            .message("Hi: there")
+            .to_payload_string()
+            .unwrap(),
+        "SMSTO:+15550100:Hi: there"
+    );
+
+    assert_eq!(
+        Email::new("jane+qr@example.com")
+            .cc("john@example.com")
+            .subject("Hello & welcome")
+            .body("Line 1\nLine 2")
+            .to_payload_string()
+            .unwrap(),
+        "mailto:jane+qr@example.com?cc=john@example.com&subject=Hello%20%26%20welcome&body=Line%201%0D%0ALine%202"
+    );
+    assert!(Email::new("not an address").to_payload_string().is_err());
+}
+
+#[test]
+fn test_calendar_event_payload() {
+    let event = CalendarEvent::new("Launch, party", EventTime::utc(2026, 10, 19, 17, 0, 0))
+        .end(EventTime::utc(2026, 10, 19, 20, 30, 0))
+        .location("Room 1");
+
+    assert_eq!(
+        event.to_payload_string().unwrap(),
+        "BEGIN:VEVENT\r\nSUMMARY:Launch\\, party\r\nDTSTART:20261019T170000Z\r\nDTEND:20261019T203000Z\r\nLOCATION:Room 1\r\nEND:VEVENT"
+    );
+
+    let all_day = CalendarEvent::new("Holiday", EventTime::date(2026, 12, 25));
+    assert!(all_day
+        .to_payload_string()
+        .unwrap()
+        .contains("DTSTART;VALUE=DATE:20261225"));
+
+    let backwards = CalendarEvent::new("Oops", EventTime::date(2026, 12, 25))
+        .end(EventTime::date(2026, 12, 24));
+    assert!(backwards.to_payload_string().is_err());
+}
+
+#[test]
+fn test_payload_encoding_mode() {
+    let telephone = Telephone::new("5550100");
+
+    assert_eq!(telephone.encoding_mode().unwrap(), EncodingMode::Byte);
+
+    let qr_code = WifiNetwork::new("HOME")
+        .to_qr_code(ErrorCorrectionLevel::Medium)
+        .unwrap();
+
+    assert_eq!(qr_code.data, "WIFI:T:nopass;S:HOME;;");
+    assert_eq!(qr_code.encoding_mode, EncodingMode::Byte);
+}
+
+#[test]
+fn test_sepa_credit_transfer_payload() {
+    let transfer = SepaCreditTransfer::new("Red Cross of Belgium", "BE72 0000 0000 1616")
+        .bic("BPOTBEB1")
+        .amount("1")
+        .purpose("CHAR")
+        .text("Urgency fund");
+
+    assert_eq!(
+        transfer.to_payload_string().unwrap(),
+        "BCD\n002\n1\nSCT\nBPOTBEB1\nRed Cross of Belgium\nBE72000000001616\nEUR1.00\nCHAR\n\nUrgency fund"
+    );
+
+    let qr_code = transfer.to_qr_code(ErrorCorrectionLevel::Medium).unwrap();
+    assert_eq!(qr_code.error_correction_level, ErrorCorrectionLevel::Medium);
+    assert!(transfer.to_qr_code(ErrorCorrectionLevel::High).is_err());
+
+    assert!(SepaCreditTransfer::new("Someone", "BE73 0000 0000 1616")
+        .to_payload_string()
+        .is_err());
+    assert!(SepaCreditTransfer::new("Someone", "BE72 0000 0000 1616")
+        .amount("1.005")
+        .to_payload_string()
+        .is_err());
+    assert!(SepaCreditTransfer::new("Someone", "BE72 0000 0000 1616")
+        .reference("RF18539007547034")
+        .text("Both")
+        .to_payload_string()
+        .is_err());
+}
+
+fn swiss_creditor() -> SwissAddress {
+    SwissAddress {
+        name: "Robert Schneider AG".to_string(),
+        street: "Rue du Lac".to_string(),
+        building_number: "1268".to_string(),
+        postal_code: "2501".to_string(),
+        town: "Biel".to_string(),
+        country: "CH".to_string(),
+    }
+}
+
+#[test]
+fn test_swiss_qr_bill_payload() {
+    let bill = SwissQrBill::new(
+        "CH44 3199 9123 0008 8901 2",
+        swiss_creditor(),
+        SwissCurrency::Chf,
+    )
+    .amount("1949.75")
+    .reference(SwissReference::Qr(
+        "21 00000 00003 13947 14300 09017".to_string(),
+    ))
+    .message("Order of 15 June 2020");
+
+    let payload = bill.to_payload_string().unwrap();
+    let lines: Vec<&str> = payload.split("\r\n").collect();
+
+    assert_eq!(lines.len(), 31);
+    assert_eq!(
+        &lines[..11],
+        &[
+            "SPC",
+            "0200",
+            "1",
+            "CH4431999123000889012",
+            "S",
+            "Robert Schneider AG",
+            "Rue du Lac",
+            "1268",
+            "2501",
+            "Biel",
+            "CH"
+        ]
+    );
+    assert_eq!(&lines[18..20], &["1949.75", "CHF"]);
+    assert_eq!(
+        &lines[27..],
+        &[
+            "QRR",
+            "210000000003139471430009017",
+            "Order of 15 June 2020",
+            "EPD"
+        ]
+    );
+
+    let qr_code = bill.to_qr_code(ErrorCorrectionLevel::Medium).unwrap();
+    assert!(qr_code.version.version() <= 25);
+    assert!(bill.to_qr_code(ErrorCorrectionLevel::Low).is_err());
+    assert!(qr_code
+        .to_swiss_qr_bill_svg(&RenderOptions::default())
+        .contains("fill=\"#FFFFFF\"/>\n</svg>"));
+
+    // a QR-IBAN needs a QR reference and a regular IBAN cannot use one
+    assert!(SwissQrBill::new(
+        "CH44 3199 9123 0008 8901 2",
+        swiss_creditor(),
+        SwissCurrency::Chf
+    )
+    .to_payload_string()
+    .is_err());
+    assert!(SwissQrBill::new(
+        "CH93 0076 2011 6238 5295 7",
+        swiss_creditor(),
+        SwissCurrency::Eur
+    )
+    .reference(SwissReference::Qr(
+        "210000000003139471430009017".to_string()
+    ))
+    .to_payload_string()
+    .is_err());
+    assert!(SwissQrBill::new(
+        "CH93 0076 2011 6238 5295 7",
+        swiss_creditor(),
+        SwissCurrency::Eur
+    )
+    .reference(SwissReference::Creditor("RF18 5390 0754 7034".to_string()))
+    .to_payload_string()
+    .is_ok());
+}
+
+#[test]
+fn test_merchant_payment_payload() {
+    let payment = MerchantPayment::new("Best Transport", "Beijing", "CN", "4111", "156")
+        .account(MerchantAccount::new(29, "A000000333").field(1, "12345678"))
+        .amount("23.72")
+        .bill_number("1234");
+
+    let payload = payment.to_payload_string().unwrap();
+
+    assert!(payload.starts_with(
+        "000201010212292600 10A0000003330108123456785204411153031565405 23.725802CN"
+            .replace(' ', "")
+            .as_str()
+    ));
+    assert!(payload.contains("5914Best Transport6007Beijing62080104123463"));
+    assert!(payload.len() == payload.find("6304").unwrap() + 8);
+
+    assert!(
+        MerchantPayment::new("Best Transport", "Beijing", "CN", "4111", "156")
+            .to_payload_string()
+            .is_err()
+    );
+    assert!(
+        MerchantPayment::new("Best Transport", "Beijing", "CN", "41", "156")
+            .account(MerchantAccount::new(29, "A000000333"))
+            .to_payload_string()
+            .is_err()
+    );
+}
+
+fn round_trip(payload: &impl Payload) -> ParsedPayload {
+    parse_payload(&payload.to_payload_string().unwrap()).unwrap()
+}
+
+#[test]
+fn test_parse_round_trip() {
+    let wifi = WifiNetwork::new("Cafe; \"Guest\"")
+        .password(WifiSecurity::Wpa, "pa:ss\\word,1")
+        .hidden(true);
+    assert_eq!(round_trip(&wifi), ParsedPayload::Wifi(wifi));
+
+    let contact = Contact::new("Jane", "Doe")
+        .organization("Acme, Inc.")
+        .phone(PhoneType::Cell, "+1 555 0100")
+        .email("jane@example.com")
+        .url("https://example.com")
+        .address(Address {
+            street: "1 Main St".to_string(),
+            city: "Springfield".to_string(),
+            ..Address::default()
+        })
+        .birthday(1990, 2, 28)
+        .note(&format!("Line one\nLine two; {}", "long ".repeat(20)));
+
+    for version in [VCardVersion::V3, VCardVersion::V4] {
+        let vcard = VCard::new(contact.clone(), version);
+        assert_eq!(round_trip(&vcard), ParsedPayload::VCard(vcard));
+    }
+
+    let mecard_contact = Contact::new("Jane", "Doe")
+        .phone(PhoneType::Other, "+15550100")
+        .url("https://example.com")
+        .note("a;b,c");
+    let mecard = MeCard::new(mecard_contact);
+    assert_eq!(round_trip(&mecard), ParsedPayload::MeCard(mecard));
+
+    let geo = GeoLocation::new(40.7128, -74.006).altitude(10.5);
+    assert_eq!(round_trip(&geo), ParsedPayload::Geo(geo));
+
+    let sms = Sms::new("+15550100").message("Hi: there");
+    assert_eq!(round_trip(&sms), ParsedPayload::Sms(sms));
+
+    let email = Email::new("jane+qr@example.com")
+        .cc("john@example.com")
+        .subject("Hello & welcome")
+        .body("Line 1\nLine 2");
+    assert_eq!(round_trip(&email), ParsedPayload::Email(email));
+
+    let transfer = SepaCreditTransfer::new("Red Cross of Belgium", "BE72000000001616")
+        .bic("BPOTBEB1")
+        .amount("1.00")
+        .text("Urgency fund");
+    assert_eq!(
+        round_trip(&transfer),
+        ParsedPayload::SepaCreditTransfer(transfer)
+    );
+
+    let payment = MerchantPayment::new("Best Transport", "Beijing", "CN", "4111", "156")
+        .account(MerchantAccount::new(29, "A000000333").field(1, "12345678"))
+        .amount("23.72")
+        .reference_label("Order 7");
+    assert_eq!(
+        round_trip(&payment),
+        ParsedPayload::MerchantPayment(payment)
+    );
+}
+
+#[test]
+fn test_parse_other_payloads() {
+    assert_eq!(
+        parse_payload("https://example.com").unwrap(),
+        ParsedPayload::Url("https://example.com".to_string())
+    );
+    assert_eq!(
+        parse_payload("sms:+15550100?body=Hello%20there").unwrap(),
+        ParsedPayload::Sms(Sms::new("+15550100").message("Hello there"))
+    );
+    assert_eq!(
+        parse_payload("TEL:+15550100").unwrap(),
+        ParsedPayload::Telephone(Telephone::new("+15550100"))
+    );
+    assert_eq!(
+        parse_payload("(555) 0100").unwrap(),
+        ParsedPayload::Text("(555) 0100".to_string())
+    );
+
+    match parse_payload("WIFI:S:Home;T:WPA2;P:secret;;").unwrap() {
+        ParsedPayload::Wifi(network) => {
+            assert_eq!(network.security, WifiSecurity::Wpa);
+            assert_eq!(network.password.as_deref(), Some("secret"));
+        }
+        parsed => panic!("Expected a WiFi network, got {:?}", parsed),
+    }
+
+    match parse_payload(
+        "BEGIN:VCARD\nVERSION:2.1\nFN:Jane Doe\nitem1.TEL;WORK;VOICE:555\nEND:VCARD",
+    )
+    .unwrap()
+    {
+        ParsedPayload::VCard(vcard) => {
+            assert_eq!(vcard.contact.first_name, "Jane Doe");
+            assert_eq!(
+                vcard.contact.phones,
+                vec![(PhoneType::Work, "555".to_string())]
+            );
+        }
+        parsed => panic!("Expected a vCard, got {:?}", parsed),
+    }
+
+    assert!(parse_payload("WIFI:T:WPA;P:secret;;").is_err());
+    assert!(parse_payload("geo:north,south").is_err());
+}
+
+#[test]
+fn test_parse_gs1_element_string() {
+    let label = "]Q300106141411234567897\u{1d}3103000750\u{1d}10LOT-42";
+
+    let ParsedPayload::Gs1(element_string) = parse_payload(label).unwrap() else {
+        panic!("Expected a GS1 element string");
+    };
+
+    assert_eq!(element_string.get("00"), Some("106141411234567897"));
+    assert_eq!(element_string.get("3103"), Some("000750"));
+    assert_eq!(element_string.get("10"), Some("LOT-42"));
+    assert_eq!(element_string.elements[0].data_title(), Some("SSCC"));
+    assert_eq!(
+        element_string.elements[1].data_title(),
+        Some("NET WEIGHT (kg)")
+    );
+
+    assert_eq!(
+        parse_payload("(01)09501101530003(17)140704(10)AB-123").unwrap(),
+        parse_payload("]Q3010950110153000317140704\u{1d}10AB-123").unwrap()
+    );
+
+    // the last digit of the SSCC is its check digit
+    assert!(parse_payload("]Q300106141411234567898").is_err());
+    assert!(parse_payload("(01)123").is_err());
+}
+
+#[test]
+fn test_parse_merchant_payment_crc() {
+    let payload = MerchantPayment::new("Best Transport", "Beijing", "CN", "4111", "156")
+        .account(MerchantAccount::new(29, "A000000333"))
+        .to_payload_string()
+        .unwrap();
+
+    let tampered = payload.replace("Beijing", "Beijinh");
+
+    assert!(parse_payload(&payload).is_ok());
+    assert!(parse_payload(&tampered).is_err());
+}
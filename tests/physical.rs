@@ -0,0 +1,59 @@
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::{Length, PhysicalSize, RenderOptions};
+
+#[test]
+fn test_fit_physical_size() {
+    let qr_code = hello_world();
+    let physical = PhysicalSize::new(Length::millimeters(25.0), 300);
+    let report = qr_code
+        .fit_physical_size(&RenderOptions::default(), &physical)
+        .unwrap();
+
+    // 25 mm at 300 dpi is 295 pixels, 29 modules of 10 pixels fit
+    assert_eq!(report.module_size, 10);
+    assert_eq!(report.pixels, 290);
+    assert!((report.printed_size.to_millimeters() - 290.0 / 300.0 * 25.4).abs() < 1e-9);
+    assert!(report.printed_size.to_points() <= physical.size.to_points());
+
+    let options = report.apply(&RenderOptions::default());
+    assert_eq!(options.module_size, 10);
+    assert_eq!(options.dpi, Some(300));
+}
+
+#[test]
+fn test_modules_too_small_to_print() {
+    let physical = PhysicalSize::new(Length::millimeters(8.0), 300);
+    let error = hello_world()
+        .fit_physical_size(&RenderOptions::default(), &physical)
+        .unwrap_err();
+
+    assert!(error.contains("smallest printable module"), "{}", error);
+}
+
+#[test]
+fn test_dpi_metadata() {
+    let qr_code = hello_world();
+    let options = RenderOptions {
+        dpi: Some(300),
+        ..RenderOptions::default()
+    };
+
+    // 300 dpi is 11811 pixels per metre
+    let png = qr_code.to_png(&options);
+    let chunk = png.windows(4).position(|window| window == b"pHYs").unwrap();
+    assert_eq!(png[chunk + 4..chunk + 8], 11811u32.to_be_bytes());
+    assert_eq!(png[chunk + 12], 1, "The unit should be the metre");
+    assert!(!qr_code
+        .to_png(&RenderOptions::default())
+        .windows(4)
+        .any(|window| window == b"pHYs"));
+
+    let bmp = qr_code.to_bmp(&options);
+    assert_eq!(bmp[38..42], 11811u32.to_le_bytes());
+
+    // 290 pixels at 300 dpi
+    let svg = qr_code.to_svg(&options);
+    assert!(svg.contains("width=\"24.553mm\" height=\"24.553mm\""));
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::{Cmyk, Length, PrintOptions, RenderOptions};
+
+fn one_inch() -> PrintOptions {
+    PrintOptions {
+        size: Length::inches(1.0),
+        foreground: Cmyk::new(100, 60, 0, 20),
+        background: Some(Cmyk::new(0, 0, 10, 0)),
+    }
+}
+
+#[test]
+fn test_module_size() {
+    // 21 modules and a quiet zone of 4 on each side
+    let module_size = one_inch().module_size(29);
+
+    assert!((module_size.to_points() - 72.0 / 29.0).abs() < 1e-9);
+    assert!((Length::millimeters(30.0).to_millimeters() - 30.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_eps() {
+    let eps = hello_world().to_eps(&RenderOptions::default(), &one_inch());
+
+    assert!(eps.starts_with("%!PS-Adobe-3.0 EPSF-3.0\n%%BoundingBox: 0 0 72 72\n"));
+    assert!(eps.contains("1 0.6 0 0.2 setcmykcolor\n"));
+    assert!(eps.contains("0 0 0.1 0 setcmykcolor\n0 0 72 72 rectfill\n"));
+    assert!(!eps.contains("showpage"));
+    assert!(eps.ends_with("fill\n%%EOF\n"));
+}
+
+#[test]
+fn test_postscript() {
+    let postscript = hello_world().to_postscript(&RenderOptions::default(), &one_inch());
+
+    assert!(postscript.contains("<< /PageSize [72 72] >> setpagedevice\n"));
+    assert!(postscript.ends_with("showpage\n%%EOF\n"));
+}
+
+#[test]
+fn test_pdf() {
+    let pdf = hello_world().to_pdf(&RenderOptions::default(), &PrintOptions::default());
+    // keep byte offsets, the binary comment on the second line is not UTF-8
+    let text: String = pdf
+        .iter()
+        .map(|&byte| if byte.is_ascii() { byte as char } else { '?' })
+        .collect();
+
+    assert!(pdf.starts_with(b"%PDF-1.4\n"));
+    // 30 mm in points
+    assert!(text.contains("/MediaBox [0 0 85.039 85.039] /TrimBox [0 0 85.039 85.039]"));
+    assert!(text.contains("0 0 0 1 k\n"));
+    assert!(
+        !text.contains(" re f\n"),
+        "The paper should be left unprinted"
+    );
+
+    // every object the cross-reference table lists starts at its offset
+    let start = text.rfind("startxref\n").unwrap();
+    let xref: usize = text[start + 10..].lines().next().unwrap().parse().unwrap();
+    let entries: Vec<&str> = text[xref..].lines().skip(3).take(5).collect();
+
+    for (i, entry) in entries.iter().enumerate() {
+        let offset: usize = entry[..10].parse().unwrap();
+        assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+    }
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::RenderOptions;
+
+fn options() -> RenderOptions {
+    RenderOptions {
+        quiet_zone: 2,
+        module_size: 3,
+        ..RenderOptions::default()
+    }
+}
+
+// a version 1 symbol with a 2 module quiet zone at 3 pixels per module
+const PIXELS: usize = 25 * 3;
+
+#[test]
+fn test_bmp() {
+    let bmp = hello_world().to_bmp(&options());
+
+    assert_eq!(&bmp[..2], b"BM");
+    assert_eq!(
+        u32::from_le_bytes(bmp[2..6].try_into().unwrap()) as usize,
+        bmp.len()
+    );
+    assert_eq!(
+        i32::from_le_bytes(bmp[18..22].try_into().unwrap()),
+        PIXELS as i32
+    );
+    // 1 bit per pixel
+    assert_eq!(bmp[28], 1);
+    // 62 header bytes and 75 pixel rows padded to 12 bytes
+    assert_eq!(bmp.len(), 62 + 12 * PIXELS);
+}
+
+#[test]
+fn test_netpbm() {
+    let qr_code = hello_world();
+
+    let pbm = qr_code.to_pbm(&options());
+    assert!(pbm.starts_with(b"P4\n75 75\n"));
+    assert_eq!(pbm.len(), 9 + PIXELS.div_ceil(8) * PIXELS);
+
+    let plain = String::from_utf8(qr_code.to_plain_pbm(&options())).unwrap();
+    assert!(plain.starts_with("P1\n75 75\n"));
+    assert!(plain.lines().all(|line| line.len() <= 70));
+    assert_eq!(plain.matches(['0', '1']).count(), 1 + PIXELS * PIXELS);
+
+    let pgm = qr_code.to_pgm(&options());
+    let header = b"P5\n75 75\n255\n";
+    assert!(pgm.starts_with(header));
+    assert_eq!(pgm.len(), header.len() + PIXELS * PIXELS);
+    // the top left pixel is in the quiet zone, the first finder pattern starts 6 pixels in
+    assert_eq!(pgm[header.len()], 255);
+    assert_eq!(pgm[header.len() + 6 * PIXELS + 6], 0);
+}
+
+#[test]
+fn test_gif() {
+    let gif = hello_world().to_gif(&options());
+
+    assert_eq!(&gif[..6], b"GIF89a");
+    assert_eq!(u16::from_le_bytes([gif[6], gif[7]]), PIXELS as u16);
+    assert_eq!(gif.last(), Some(&b';'));
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::{RenderOptions, Renderer};
+use qr_code::qr_code::QrCode;
+
+/**
+ * A renderer from outside the crate that only implements draw_module, like a plotter would
+ */
+#[derive(Default)]
+struct Plotter {
+    width: usize,
+    finders: Vec<(usize, usize)>,
+    modules: Vec<Vec<Option<bool>>>,
+}
+
+impl Renderer for Plotter {
+    type Output = Plotter;
+
+    fn begin(&mut self, _qr_code: &QrCode, _options: &RenderOptions, width: usize) {
+        self.width = width;
+        self.modules = vec![vec![None; width]; width];
+    }
+
+    fn draw_finder(&mut self, x: usize, y: usize) {
+        self.finders.push((x, y));
+    }
+
+    fn draw_module(&mut self, x: usize, y: usize, dark: bool) {
+        assert!(
+            self.modules[y][x].is_none(),
+            "module ({}, {}) drawn twice",
+            x,
+            y
+        );
+        self.modules[y][x] = Some(dark);
+    }
+
+    fn end(self) -> Plotter {
+        self
+    }
+}
+
+#[test]
+fn test_custom_renderer() {
+    let qr_code = hello_world();
+    let options = RenderOptions {
+        quiet_zone: 2,
+        ..RenderOptions::default()
+    };
+
+    let plotter = qr_code.render(Plotter::default(), &options);
+
+    assert_eq!(plotter.width, 25);
+    assert_eq!(plotter.finders, vec![(2, 2), (16, 2), (2, 16)]);
+
+    let matrix = qr_code.to_matrix(2);
+    for (y, row) in matrix.iter().enumerate() {
+        for (x, &dark) in row.iter().enumerate() {
+            assert_eq!(plotter.modules[y][x], Some(dark));
+        }
+    }
+}
+
+#[test]
+fn test_invert() {
+    let qr_code = hello_world();
+    let options = RenderOptions {
+        quiet_zone: 1,
+        invert: true,
+        ..RenderOptions::default()
+    };
+
+    let plotter = qr_code.render(Plotter::default(), &options);
+    let matrix = qr_code.to_matrix(1);
+    for (y, row) in matrix.iter().enumerate() {
+        for (x, &dark) in row.iter().enumerate() {
+            assert_eq!(plotter.modules[y][x], Some(!dark));
+        }
+    }
+
+    // the quiet zone is dark and the finder pattern rings are light
+    let text = qr_code.to_text(&options, "1", "0");
+    assert!(text.starts_with("11111111111111111111111\n1000000011"));
+
+    // finder patterns are drawn module by module rather than as a dark ring
+    let svg = qr_code.to_svg(&options);
+    assert!(svg.contains("M0,0h1v1h-1z"));
+    assert!(!svg.contains("M1,1h7v7h-7z"));
+}
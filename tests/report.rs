@@ -0,0 +1,79 @@
+use qr_code::qr_code::{EncodingMode, ErrorCorrectionLevel, QrCode, Version};
+
+#[test]
+fn test_report_hello_world() {
+    // HELLO WORLD as a 1-Q code from https://www.thonky.com/qr-code-tutorial/
+    let qr_code = QrCode::create(
+        "HELLO WORLD".to_string(),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Quartile,
+    )
+    .unwrap();
+
+    let report = qr_code.report().unwrap();
+
+    assert_eq!(report.mode_indicator.to_string(), "0010");
+    assert_eq!(report.character_count_indicator.to_string(), "000001011");
+    assert_eq!(report.terminator_bits, 4);
+    assert_eq!(report.alignment_bits, 2);
+    assert_eq!(report.pad_bytes, vec![236, 17, 236]);
+    assert_eq!(report.blocks.len(), 1);
+    assert_eq!(
+        report.blocks[0].error_correction,
+        vec![168, 72, 22, 82, 217, 54, 156, 0, 46, 15, 180, 122, 16]
+    );
+    assert_eq!(report.final_message.len(), 26);
+    assert_eq!(report.remainder_bits, 0);
+    assert_eq!(report.mask, qr_code.mask);
+    assert_eq!(
+        report.mask_penalties[report.mask as usize],
+        *report.mask_penalties.iter().min().unwrap(),
+        "The chosen mask should have the lowest penalty"
+    );
+    assert!(report.version_information.is_none());
+}
+
+#[test]
+fn test_report_interleaves_blocks() {
+    let qr_code = QrCode::create(
+        "0123456789".repeat(20),
+        EncodingMode::Numeric,
+        ErrorCorrectionLevel::High,
+    )
+    .unwrap();
+
+    let report = qr_code.report().unwrap();
+
+    assert!(report.blocks.len() > 1, "Expected more than one block");
+    assert_eq!(report.final_message[0], report.blocks[0].data[0]);
+    assert_eq!(report.final_message[1], report.blocks[1].data[0]);
+}
+
+#[test]
+fn test_report_of_changed_fields() {
+    let create = || {
+        QrCode::create(
+            "12345".to_string(),
+            EncodingMode::Numeric,
+            ErrorCorrectionLevel::Medium,
+        )
+        .unwrap()
+    };
+
+    // the fields are public, a report of values no code can have fails rather than panicking
+    let mut qr_code = create();
+    qr_code.version = Version::Normal(41);
+    assert!(qr_code.report().is_err());
+
+    let mut qr_code = create();
+    qr_code.mask = 8;
+    assert!(qr_code.report().is_err());
+
+    let mut qr_code = create();
+    qr_code.data = "12a".to_string();
+    assert!(qr_code.report().is_err());
+
+    let mut qr_code = create();
+    qr_code.data = "1".repeat(2000);
+    assert!(qr_code.report().is_err());
+}
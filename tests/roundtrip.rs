@@ -0,0 +1,263 @@
+use proptest::prelude::*;
+use qr_code::qr_code::{
+    decode_matrix, EncodingMode, ErrorCorrectionLevel, Knockout, QrCode, QrCodeOptions,
+};
+
+const LEVELS: [ErrorCorrectionLevel; 4] = [
+    ErrorCorrectionLevel::Low,
+    ErrorCorrectionLevel::Medium,
+    ErrorCorrectionLevel::Quartile,
+    ErrorCorrectionLevel::High,
+];
+
+fn level() -> impl Strategy<Value = ErrorCorrectionLevel> {
+    prop::sample::select(LEVELS.to_vec())
+}
+
+/**
+ * Payloads of every mode, long enough to reach versions 10 to 26 where the character count
+ * indicators are wider. None fill a version above 26, so `min_version` reaches those.
+ */
+fn payload() -> impl Strategy<Value = (String, EncodingMode)> {
+    prop_oneof![
+        "[0-9]{0,900}".prop_map(|data| (data, EncodingMode::Numeric)),
+        "[0-9A-Z $%*+./:-]{0,500}".prop_map(|data| (data, EncodingMode::Alphanumeric)),
+        "\\PC{0,120}".prop_map(|data| (data, EncodingMode::Byte)),
+        "[0-9A-Z]{0,300}".prop_map(|data| (data, EncodingMode::Byte)),
+    ]
+}
+
+/**
+ * Half of the codes pick their own version, the others are at least version 27, which has the
+ * widest character count indicators and the most blocks
+ */
+fn min_version() -> impl Strategy<Value = i16> {
+    prop_oneof![Just(1), 27i16..=40]
+}
+
+fn create(
+    data: &str,
+    encoding_mode: EncodingMode,
+    error_correction_level: ErrorCorrectionLevel,
+    min_version: i16,
+    mask: u8,
+) -> QrCode {
+    let options = QrCodeOptions {
+        min_version,
+        mask: Some(mask),
+        ..QrCodeOptions::default()
+    };
+
+    QrCode::create_with_options(
+        data.to_string(),
+        encoding_mode,
+        error_correction_level,
+        &options,
+    )
+    .unwrap()
+}
+
+fn rows(qr_code: &QrCode) -> Vec<Vec<bool>> {
+    qr_code.rows().map(|row| row.to_vec()).collect()
+}
+
+/**
+ * The modules of each code word of the final message, in the order they are placed: two columns
+ * at a time from the right, zigzagging up and down and skipping the vertical timing pattern
+ */
+fn code_word_modules(qr_code: &QrCode) -> Vec<Vec<(usize, usize)>> {
+    let size = qr_code.width();
+    let mut positions = Vec::new();
+    let mut right = size - 1;
+
+    loop {
+        if right == 6 {
+            right = 5;
+        }
+
+        let upward = (right + 1) & 2 == 0;
+        for vertical in 0..size {
+            let y = if upward {
+                size - 1 - vertical
+            } else {
+                vertical
+            };
+
+            for x in [right, right - 1] {
+                if !qr_code.is_function_module(x, y) {
+                    positions.push((x, y));
+                }
+            }
+        }
+
+        if right < 2 {
+            break;
+        }
+        right -= 2;
+    }
+
+    positions.truncate(qr_code.report().unwrap().final_message.len() * 8);
+    positions
+        .chunks(8)
+        .map(|modules| modules.to_vec())
+        .collect()
+}
+
+/**
+ * The block of each code word of the final message, the data code words of the blocks are
+ * interleaved followed by their error correction code words
+ */
+fn code_word_blocks(qr_code: &QrCode) -> Vec<usize> {
+    let blocks = qr_code.report().unwrap().blocks;
+    let longest = blocks.iter().map(|block| block.data.len()).max().unwrap();
+    let mut order = Vec::new();
+
+    for i in 0..longest {
+        order.extend((0..blocks.len()).filter(|&block| i < blocks[block].data.len()));
+    }
+    for _ in 0..blocks[0].error_correction.len() {
+        order.extend(0..blocks.len());
+    }
+
+    order
+}
+
+/**
+ * ISO/IEC 18004 table 9, the smallest versions set aside code words against misdecoding
+ */
+fn correctable_code_words(qr_code: &QrCode) -> usize {
+    let error_correction = qr_code.report().unwrap().blocks[0].error_correction.len();
+    let misdecode_protection = match (qr_code.version.version(), qr_code.error_correction_level) {
+        (1, ErrorCorrectionLevel::Low) => 3,
+        (1, ErrorCorrectionLevel::Medium) | (2, ErrorCorrectionLevel::Low) => 2,
+        (1, _) | (3, ErrorCorrectionLevel::Low) => 1,
+        _ => 0,
+    };
+
+    (error_correction - misdecode_protection) / 2
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_roundtrip(
+        (data, encoding_mode) in payload(),
+        error_correction_level in level(),
+        min_version in min_version(),
+        mask in 0u8..8,
+    ) {
+        let qr_code = create(&data, encoding_mode, error_correction_level, min_version, mask);
+        prop_assert!(qr_code.version.version() >= min_version);
+        let decoded = decode_matrix(&rows(&qr_code)).unwrap();
+
+        prop_assert_eq!(&decoded.data, &data);
+        prop_assert_eq!(decoded.encoding_mode, encoding_mode);
+        prop_assert_eq!(decoded.error_correction_level, error_correction_level);
+        prop_assert_eq!(decoded.version, qr_code.version);
+        prop_assert_eq!(decoded.mask, mask);
+        prop_assert_eq!(decoded.corrected_code_words, 0);
+    }
+
+    #[test]
+    fn test_damage_recovery(
+        (data, encoding_mode) in payload(),
+        error_correction_level in level(),
+        min_version in min_version(),
+        mask in 0u8..8,
+        seed in any::<u64>(),
+    ) {
+        let qr_code = create(&data, encoding_mode, error_correction_level, min_version, mask);
+        let code_words = code_word_modules(&qr_code);
+        let blocks = code_word_blocks(&qr_code);
+        let correctable = correctable_code_words(&qr_code);
+
+        // damage the most each block can take, in code words picked by the seed
+        let mut modules = rows(&qr_code);
+        let mut damaged = vec![0; qr_code.report().unwrap().blocks.len()];
+        let mut state = seed;
+
+        for i in 0..code_words.len() {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let index = (i + (state >> 33) as usize) % code_words.len();
+
+            if damaged[blocks[index]] == correctable {
+                continue;
+            }
+            damaged[blocks[index]] += 1;
+
+            // flip at least one module of the code word
+            let flips = (state >> 16) as u8 | 1;
+            for (bit, &(x, y)) in code_words[index].iter().enumerate() {
+                if flips & (0x80 >> bit) != 0 {
+                    modules[y][x] = !modules[y][x];
+                }
+            }
+        }
+
+        let decoded = decode_matrix(&modules).unwrap();
+
+        prop_assert_eq!(&decoded.data, &data);
+        prop_assert!(decoded.corrected_code_words <= damaged.iter().sum::<usize>());
+    }
+}
+
+#[test]
+fn test_damage_of_one_block() {
+    // as many code words of the first block of 5-Q as can be corrected, then one more
+    let qr_code = create(
+        &"A".repeat(70),
+        EncodingMode::Alphanumeric,
+        ErrorCorrectionLevel::Quartile,
+        1,
+        3,
+    );
+    let code_words = code_word_modules(&qr_code);
+    let blocks = code_word_blocks(&qr_code);
+    let correctable = correctable_code_words(&qr_code);
+    assert_eq!(correctable, 9);
+
+    let mut modules = rows(&qr_code);
+    let first_block = (0..code_words.len()).filter(|&i| blocks[i] == 0);
+
+    for (count, index) in first_block.take(correctable).enumerate() {
+        let (x, y) = code_words[index][count % 8];
+        modules[y][x] = !modules[y][x];
+    }
+
+    let decoded = decode_matrix(&modules).unwrap();
+    assert_eq!(decoded.data, qr_code.data);
+    assert_eq!(decoded.corrected_code_words, correctable);
+
+    let (x, y) = code_words[blocks.iter().rposition(|&block| block == 0).unwrap()][0];
+    modules[y][x] = !modules[y][x];
+
+    assert!(decode_matrix(&modules).is_err());
+}
+
+#[test]
+fn test_quiet_zone_and_knockout() {
+    let qr_code = QrCode::create_with_options(
+        "https://example.com/a/longer/path".to_string(),
+        EncodingMode::Byte,
+        ErrorCorrectionLevel::High,
+        &QrCodeOptions {
+            knockout: Some(Knockout::new(7, 7)),
+            ..QrCodeOptions::default()
+        },
+    )
+    .unwrap();
+
+    let decoded = decode_matrix(&qr_code.to_matrix(4)).unwrap();
+
+    assert_eq!(decoded.data, qr_code.data);
+    assert!(decoded.corrected_code_words > 0);
+}
+
+#[test]
+fn test_not_a_qr_code() {
+    assert!(decode_matrix(&[]).is_err());
+    assert!(decode_matrix(&vec![vec![false; 21]; 21]).is_err());
+    assert!(decode_matrix(&vec![vec![true; 22]; 22]).is_err());
+    assert!(decode_matrix(&vec![vec![true; 21]; 21]).is_err());
+}
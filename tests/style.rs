@@ -0,0 +1,51 @@
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::{FinderShape, ModuleShape, RenderOptions, Style};
+
+fn render(style: Style) -> String {
+    hello_world().to_svg(&RenderOptions {
+        style,
+        ..RenderOptions::default()
+    })
+}
+
+#[test]
+fn test_default_style_is_square() {
+    let svg = render(Style::default());
+
+    assert!(svg.contains("shape-rendering=\"crispEdges\""));
+    assert!(!svg.contains('A'), "Square modules should not have arcs");
+    // the top left finder pattern is a 7x7 ring around a 3x3 eye
+    assert!(svg.contains("M4,4h7v7h-7zM5,5v5h5v-5zM6,6h3v3h-3z"));
+}
+
+#[test]
+fn test_circle_finder() {
+    let svg = render(Style {
+        finder_ring: FinderShape::Circle,
+        finder_eye: FinderShape::Circle,
+        ..Style::default()
+    });
+
+    assert!(svg.contains("shape-rendering=\"geometricPrecision\""));
+    assert!(svg.contains("M7.5,4H7.5A3.5,3.5 0 0 1 11,7.5"));
+    assert!(svg.contains("M7.5,6H7.5A1.5,1.5 0 0 1 9,7.5"));
+}
+
+#[test]
+fn test_png_size_does_not_depend_on_style() {
+    let qr_code = hello_world();
+    let square = qr_code.to_png(&RenderOptions::default());
+    let circles = qr_code.to_png(&RenderOptions {
+        style: Style {
+            module_shape: ModuleShape::Circle,
+            ..Style::default()
+        },
+        ..RenderOptions::default()
+    });
+
+    // the width and height in the IHDR chunk
+    assert_eq!(square[16..24], circles[16..24]);
+    assert_ne!(square, circles);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::{HtmlLayout, RenderOptions};
+
+fn options() -> RenderOptions {
+    RenderOptions {
+        quiet_zone: 2,
+        module_size: 3,
+        ..RenderOptions::default()
+    }
+}
+
+#[test]
+fn test_text() {
+    let qr_code = hello_world();
+    let text = qr_code.to_text(&options(), "1", "0");
+
+    let expected: String = qr_code
+        .to_matrix(2)
+        .iter()
+        .map(|row| {
+            let line: String = row
+                .iter()
+                .map(|&dark| if dark { '1' } else { '0' })
+                .collect();
+            line + "\n"
+        })
+        .collect();
+    assert_eq!(text, expected);
+
+    let wide = qr_code.to_text(&options(), "██", "  ");
+    assert_eq!(wide.lines().count(), 25);
+    assert!(wide.lines().all(|line| line.chars().count() == 50));
+    assert!(wide
+        .lines()
+        .nth(2)
+        .unwrap()
+        .starts_with("    ██████████████  "));
+}
+
+#[test]
+fn test_html() {
+    let qr_code = hello_world();
+
+    let table = qr_code.to_html(&options(), HtmlLayout::Table);
+    assert!(table.starts_with("<table"));
+    assert!(table.contains("<col span=\"25\" style=\"width:3px\">"));
+    assert_eq!(table.matches("<tr").count(), 25);
+    // the quiet zone rows are a single light cell
+    assert!(table
+        .contains("<tr style=\"height:3px\"><td colspan=\"25\" style=\"padding:0\"></td></tr>"));
+    // every row spans the full width
+    for row in table.lines().filter(|line| line.starts_with("<tr")) {
+        let columns: usize = row
+            .split("colspan=\"")
+            .skip(1)
+            .map(|cell| cell.split('"').next().unwrap().parse::<usize>().unwrap())
+            .sum();
+        assert_eq!(columns, 25);
+    }
+
+    let grid = qr_code.to_html(&options(), HtmlLayout::Grid);
+    assert!(grid.starts_with("<div style=\"display:grid;grid-template-columns:repeat(25,3px);"));
+    // the top of the first finder pattern is a run of 7 dark modules
+    assert!(grid.contains("<div style=\"grid-area:3/3/span 1/span 7;background:#000000\"></div>"));
+    assert!(grid.ends_with("</div>\n"));
+}
+
+#[test]
+fn test_json() {
+    let qr_code = hello_world();
+    let json = qr_code.to_json(&options());
+
+    assert!(json.contains("\"version\": 1,"));
+    assert!(json.contains("\"error_correction_level\": \"Quartile\","));
+    assert!(json.contains(&format!("\"mask\": {},", qr_code.mask)));
+    assert!(json.contains("\"width\": 21,"));
+    assert!(json.contains("\"data\": \"HELLO WORLD\","));
+    // 4 mode bits, 9 character count bits and 61 data bits
+    assert!(json.contains("{\"mode\": \"Alphanumeric\", \"characters\": 11, \"bits\": 74}"));
+
+    let rows: Vec<&str> = json
+        .lines()
+        .filter(|line| line.starts_with("    ["))
+        .collect();
+    assert_eq!(rows.len(), 25);
+    assert!(rows.iter().all(|row| row.matches(['0', '1']).count() == 25));
+    // the quiet zone is 2 modules, then the top of the finder patterns
+    assert!(rows[2].starts_with("    [0,0,1,1,1,1,1,1,1,0,"));
+    assert!(rows[2].ends_with(",0,1,1,1,1,1,1,1,0,0],"));
+    assert!(json.ends_with("  ]\n}\n"));
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "wasm")]
+// Runs natively with `cargo test --features wasm`, and under Node with
+// `cargo test --target wasm32-unknown-unknown --features wasm --test wasm`. Errors become
+// JavaScript exceptions, so only the tests for them are limited to wasm32.
+
+mod common;
+
+use common::hello_world;
+use qr_code::qr_code::render::RenderOptions;
+use qr_code::wasm::{generate, generate_utf8, Options};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_generate_matches_qr_code() {
+    let mut options = Options::new();
+    options.error_correction = "Q".to_string();
+    let generated = generate("HELLO WORLD", &options).unwrap();
+    let qr_code = hello_world();
+
+    assert_eq!(generated.width(), 21);
+    assert_eq!(generated.mask(), qr_code.mask);
+
+    let modules: Vec<u8> = qr_code.rows().flatten().map(|&dark| dark as u8).collect();
+    assert_eq!(generated.modules(), modules);
+
+    let render_options = RenderOptions::default();
+    assert_eq!(
+        generated.to_svg(&options).unwrap(),
+        qr_code.to_svg(&render_options)
+    );
+    assert_eq!(
+        generated.to_png(&options).unwrap(),
+        qr_code.to_png(&render_options)
+    );
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_generate_with_options() {
+    let mut options = Options::new();
+    options.mode = "byte".to_string();
+    options.error_correction = "H".to_string();
+    options.min_version = 5;
+    options.mask = Some(3);
+    options.quiet_zone = 2;
+    options.module_size = 4;
+    options.foreground = "#336699".to_string();
+
+    let generated = generate_utf8("hello world".as_bytes(), &options).unwrap();
+    assert_eq!(generated.version(), 5);
+    assert_eq!(generated.mask(), 3);
+
+    let svg = generated.to_svg(&options).unwrap();
+    assert!(svg.contains("#336699"));
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_test]
+fn test_generate_errors() {
+    let mut options = Options::new();
+    options.mode = "numeric".to_string();
+    assert!(generate("12a", &options).is_err());
+
+    options.mode = "kanji".to_string();
+    assert!(generate("12", &options).is_err());
+
+    // only UTF-8 text can be encoded, not arbitrary bytes
+    assert!(generate_utf8(&[0xff, 0xfe], &Options::new()).is_err());
+
+    let qr_code = generate("12", &Options::new()).unwrap();
+    options.foreground = "blue".to_string();
+    assert!(qr_code.to_svg(&options).is_err());
+
+    let mut options = Options::new();
+    options.module_size = 1 << 20;
+    assert!(qr_code.to_png(&options).is_err());
+}